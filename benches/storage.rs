@@ -0,0 +1,196 @@
+//! Benchmarks comparing storage backends under a common harness.
+//!
+//! Every backend implements [`BenchTarget`], and each benchmark group runs the
+//! same workload against all enabled targets. The Redis target is only built
+//! with the `redis` feature and connects to `TSOT_BENCH_REDIS_HOST` /
+//! `TSOT_BENCH_REDIS_PORT` (defaulting to `127.0.0.1:6379`).
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tokio::runtime::Runtime;
+use tsot::asynchronous::{AtomicStorage, RawStorage, StringStorage};
+
+/// Value sizes, in bytes, used by the raw storage benchmarks.
+const VALUE_SIZES: [usize; 3] = [16, 1024, 64 * 1024];
+
+/// Number of concurrent tasks used by the concurrency benchmarks.
+const CONCURRENCY_LEVELS: [usize; 3] = [1, 8, 64];
+
+/// A storage backend that can be benchmarked.
+trait BenchTarget: StringStorage + RawStorage + AtomicStorage + Clone + Send + Sync + 'static {
+    /// Name used to label the backend in benchmark reports.
+    const NAME: &'static str;
+
+    /// Creates a fresh instance of the backend.
+    fn setup(rt: &Runtime) -> Self;
+}
+
+impl BenchTarget for tsot::storage::imc::IMCModule {
+    const NAME: &'static str = "imc";
+
+    fn setup(_rt: &Runtime) -> Self {
+        Self::new(tsot::storage::imc::IMCConfig {
+            intern_values: false,
+            hash_keys_over: None,
+            eviction_interval: None,
+            max_entries: None,
+            max_bytes: None,
+            track_last_modified: false,
+            shard_amount: None,
+            #[cfg(feature = "persistence")]
+            wal_path: None,
+            key_validator: None,
+        })
+    }
+}
+
+#[cfg(feature = "redis")]
+impl BenchTarget for tsot::storage::redis::RedisStorageModule {
+    const NAME: &'static str = "redis";
+
+    fn setup(rt: &Runtime) -> Self {
+        let host =
+            std::env::var("TSOT_BENCH_REDIS_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+        let port = std::env::var("TSOT_BENCH_REDIS_PORT")
+            .ok()
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(6379);
+
+        rt.block_on(Self::new(tsot::storage::redis::RedisStorageConfig {
+            topology: tsot::storage::redis::RedisTopology::Centralized { host, port },
+            username: None,
+            password: None,
+            default_expiry: None,
+            hash_keys_over: None,
+            database: None,
+            reconnect_attempts: None,
+            reconnect_backoff_ms: tsot::storage::redis::DEFAULT_RECONNECT_BACKOFF_MS,
+            command_retries: tsot::storage::redis::DEFAULT_COMMAND_RETRIES,
+            key_validator: None,
+            #[cfg(feature = "redis-tls")]
+            tls: None,
+        }))
+        .expect("Failed to connect to Redis")
+    }
+}
+
+fn runtime() -> Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build runtime")
+}
+
+fn bench_raw<T: BenchTarget>(c: &mut Criterion) {
+    let rt = runtime();
+    let storage = T::setup(&rt);
+    let mut group = c.benchmark_group(format!("{}/raw", T::NAME));
+
+    for size in VALUE_SIZES {
+        let key = format!("bench:raw:{size}");
+        let value = vec![0xAB; size];
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("store", size), &value, |b, value| {
+            b.iter(|| {
+                rt.block_on(storage.store_raw(key.clone(), value.clone()))
+                    .expect("store failed")
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("load", size), &value, |b, _| {
+            b.iter(|| {
+                rt.block_on(storage.load_raw(key.clone()))
+                    .expect("load failed")
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("delete", size), &value, |b, value| {
+            b.iter(|| {
+                rt.block_on(async {
+                    storage.store_raw(key.clone(), value.clone()).await?;
+                    storage.delete_raw(key.clone()).await
+                })
+                .expect("delete failed")
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_atomic<T: BenchTarget>(c: &mut Criterion) {
+    let rt = runtime();
+    let storage = T::setup(&rt);
+    let key = "bench:atomic".to_string();
+    rt.block_on(storage.atomic_store(key.clone(), 0))
+        .expect("store failed");
+
+    let mut group = c.benchmark_group(format!("{}/atomic", T::NAME));
+    group.bench_function("increment", |b| {
+        b.iter(|| {
+            rt.block_on(storage.atomic_increment(key.clone(), 1))
+                .expect("increment failed")
+        })
+    });
+    group.finish();
+}
+
+fn bench_concurrent<T: BenchTarget>(c: &mut Criterion) {
+    let rt = runtime();
+    let storage = T::setup(&rt);
+    let mut group = c.benchmark_group(format!("{}/concurrent", T::NAME));
+
+    for tasks in CONCURRENCY_LEVELS {
+        group.throughput(Throughput::Elements(tasks as u64));
+        group.bench_with_input(
+            BenchmarkId::new("store_load_string", tasks),
+            &tasks,
+            |b, &tasks| {
+                b.iter(|| {
+                    rt.block_on(async {
+                        let handles = (0..tasks)
+                            .map(|task| {
+                                let storage = storage.clone();
+                                tokio::spawn(async move {
+                                    let key = format!("bench:concurrent:{task}");
+                                    storage
+                                        .store_string(key.clone(), "value".to_string())
+                                        .await?;
+                                    storage.load_string(key).await
+                                })
+                            })
+                            .collect::<Vec<_>>();
+
+                        for handle in handles {
+                            handle
+                                .await
+                                .expect("task panicked")
+                                .expect("operation failed");
+                        }
+                    })
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn imc(c: &mut Criterion) {
+    bench_raw::<tsot::storage::imc::IMCModule>(c);
+    bench_atomic::<tsot::storage::imc::IMCModule>(c);
+    bench_concurrent::<tsot::storage::imc::IMCModule>(c);
+}
+
+#[cfg(feature = "redis")]
+fn redis(c: &mut Criterion) {
+    bench_raw::<tsot::storage::redis::RedisStorageModule>(c);
+    bench_atomic::<tsot::storage::redis::RedisStorageModule>(c);
+    bench_concurrent::<tsot::storage::redis::RedisStorageModule>(c);
+}
+
+#[cfg(not(feature = "redis"))]
+criterion_group!(benches, imc);
+#[cfg(feature = "redis")]
+criterion_group!(benches, imc, redis);
+criterion_main!(benches);
@@ -3,6 +3,9 @@
 //! This module provides traits for implementing various storage mechanisms with
 //! different data types and storage capabilities. It includes support for string storage,
 //! raw bytes storage, and atomic numerical operations.
+//!
+//! Keys of the string, raw and atomic traits accept any `impl AsRef<str>`, so both
+//! `"key"` and an owned `String` can be passed without converting first.
 
 use crate::{errors, types};
 use async_trait::async_trait;
@@ -26,7 +29,7 @@ pub trait StringStorage: StringStorageWithExpiry {
     /// * `Result<types::StoreState, errors::StorageError>` - The result of the storage operation
     async fn store_string(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: String,
     ) -> Result<types::StoreState, errors::StorageError> {
         self.store_with_expiry(key, value, None).await
@@ -39,7 +42,10 @@ pub trait StringStorage: StringStorageWithExpiry {
     ///
     /// # Returns
     /// * `Result<Option<String>, errors::StorageError>` - The stored string if it exists
-    async fn load_string(&self, key: String) -> Result<Option<String>, errors::StorageError>;
+    async fn load_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, errors::StorageError>;
 
     /// Deletes a string value associated with the given key.
     ///
@@ -48,7 +54,21 @@ pub trait StringStorage: StringStorageWithExpiry {
     ///
     /// # Returns
     /// * `Result<(), errors::StorageError>` - Success or error status of the deletion
-    async fn delete_string(&self, key: String) -> Result<(), errors::StorageError>;
+    async fn delete_string(&self, key: impl AsRef<str> + Send) -> Result<(), errors::StorageError>;
+
+    /// Atomically loads and deletes a string value.
+    ///
+    /// Only one of several concurrent callers taking the same key receives the value.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be taken
+    ///
+    /// # Returns
+    /// * `Result<Option<String>, errors::StorageError>` - The stored string if it existed
+    async fn take_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, errors::StorageError>;
 }
 
 /// Trait for string storage operations with expiration support.
@@ -58,6 +78,11 @@ pub trait StringStorage: StringStorageWithExpiry {
 pub trait StringStorageWithExpiry {
     /// Stores a string value with an optional expiration time.
     ///
+    /// Passing `None` stores the value without expiration, clearing any
+    /// expiration previously set on the key. Use
+    /// [`StringStorageWithExpiry::store_keep_ttl`] to update a value while
+    /// keeping its existing expiration.
+    ///
     /// # Arguments
     /// * `key` - The key under which to store the value
     /// * `value` - The string value to store
@@ -67,10 +92,26 @@ pub trait StringStorageWithExpiry {
     /// * `Result<types::StoreState, errors::StorageError>` - The result of the storage operation
     async fn store_with_expiry(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: String,
         expiry: Option<u64>,
     ) -> Result<types::StoreState, errors::StorageError>;
+
+    /// Stores a string value while preserving the key's existing expiration.
+    ///
+    /// If the key doesn't exist, the value is stored without expiration.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The string value to store
+    ///
+    /// # Returns
+    /// * `Result<types::StoreState, errors::StorageError>` - The result of the storage operation
+    async fn store_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<types::StoreState, errors::StorageError>;
 }
 
 /// Trait for basic raw bytes storage operations.
@@ -92,7 +133,7 @@ pub trait RawStorage: RawStorageWithExpiry {
     /// * `Result<types::StoreState, errors::StorageError>` - The result of the storage operation
     async fn store_raw(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: Vec<u8>,
     ) -> Result<types::StoreState, errors::StorageError> {
         self.store_raw_with_expiry(key, value, None).await
@@ -105,7 +146,10 @@ pub trait RawStorage: RawStorageWithExpiry {
     ///
     /// # Returns
     /// * `Result<Option<Vec<u8>>, errors::StorageError>` - The stored bytes if they exist
-    async fn load_raw(&self, key: String) -> Result<Option<Vec<u8>>, errors::StorageError>;
+    async fn load_raw(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<Vec<u8>>, errors::StorageError>;
 
     /// Deletes raw bytes associated with the given key.
     ///
@@ -114,7 +158,7 @@ pub trait RawStorage: RawStorageWithExpiry {
     ///
     /// # Returns
     /// * `Result<(), errors::StorageError>` - Success or error status of the deletion
-    async fn delete_raw(&self, key: String) -> Result<(), errors::StorageError>;
+    async fn delete_raw(&self, key: impl AsRef<str> + Send) -> Result<(), errors::StorageError>;
 }
 
 /// Trait for raw bytes storage operations with expiration support.
@@ -124,6 +168,11 @@ pub trait RawStorage: RawStorageWithExpiry {
 pub trait RawStorageWithExpiry {
     /// Stores raw bytes with an optional expiration time.
     ///
+    /// Passing `None` stores the value without expiration, clearing any
+    /// expiration previously set on the key. Use
+    /// [`RawStorageWithExpiry::store_raw_keep_ttl`] to update a value while
+    /// keeping its existing expiration.
+    ///
     /// # Arguments
     /// * `key` - The key under which to store the value
     /// * `value` - The byte vector to store
@@ -133,10 +182,26 @@ pub trait RawStorageWithExpiry {
     /// * `Result<types::StoreState, errors::StorageError>` - The result of the storage operation
     async fn store_raw_with_expiry(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: Vec<u8>,
         expiry: Option<u64>,
     ) -> Result<types::StoreState, errors::StorageError>;
+
+    /// Stores raw bytes while preserving the key's existing expiration.
+    ///
+    /// If the key doesn't exist, the value is stored without expiration.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The byte vector to store
+    ///
+    /// # Returns
+    /// * `Result<types::StoreState, errors::StorageError>` - The result of the storage operation
+    async fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+    ) -> Result<types::StoreState, errors::StorageError>;
 }
 
 /// Trait for atomic operations on integer values.
@@ -155,7 +220,7 @@ pub trait AtomicStorage {
     /// * `Result<types::StoreState, errors::StorageError>` - The result of the storage operation
     async fn atomic_store(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: i64,
     ) -> Result<types::StoreState, errors::StorageError>;
 
@@ -166,7 +231,10 @@ pub trait AtomicStorage {
     ///
     /// # Returns
     /// * `Result<Option<i64>, errors::StorageError>` - The stored integer if it exists
-    async fn atomic_load(&self, key: String) -> Result<Option<i64>, errors::StorageError>;
+    async fn atomic_load(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<i64>, errors::StorageError>;
 
     /// Atomically deletes an integer value.
     ///
@@ -175,10 +243,15 @@ pub trait AtomicStorage {
     ///
     /// # Returns
     /// * `Result<(), errors::StorageError>` - Success or error status of the deletion
-    async fn atomic_delete(&self, key: String) -> Result<(), errors::StorageError>;
+    async fn atomic_delete(&self, key: impl AsRef<str> + Send) -> Result<(), errors::StorageError>;
 
     /// Atomically increments an integer value.
     ///
+    /// A missing key is treated as `0` and created by the increment. Returns
+    /// the value *after* the increment on every backend. If the result would
+    /// fall outside the `i64` range, every backend fails with
+    /// `StorageError::Overflow` and leaves the stored value unchanged.
+    ///
     /// # Arguments
     /// * `key` - The key whose value should be incremented
     /// * `value` - The amount to increment by (can be negative for decrements)
@@ -187,7 +260,953 @@ pub trait AtomicStorage {
     /// * `Result<Option<i64>, errors::StorageError>` - The new value after incrementing if successful
     async fn atomic_increment(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: i64,
     ) -> Result<Option<i64>, errors::StorageError>;
+
+    /// Atomically decrements an integer value.
+    ///
+    /// A missing key is treated as `0` and created by the decrement, so values
+    /// can go below zero. Returns the value *after* the decrement. Results
+    /// beyond the `i64` range fail with `StorageError::Overflow` and leave the
+    /// stored value unchanged.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be decremented
+    /// * `value` - The amount to decrement by
+    ///
+    /// # Returns
+    /// * `Result<Option<i64>, errors::StorageError>` - The new value after decrementing if
+    ///   successful, or `StorageError::Overflow` if `value` is `i64::MIN` or the
+    ///   result overflows
+    async fn atomic_decrement(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<Option<i64>, errors::StorageError> {
+        match value.checked_neg() {
+            Some(delta) => self.atomic_increment(key, delta).await,
+            None => Err(errors::StorageError::Overflow(format!(
+                "cannot decrement by {value}"
+            ))),
+        }
+    }
+
+    /// Atomically increments an integer value, creating it first if absent.
+    ///
+    /// If the key doesn't exist it is initialized to `init` before `delta` is
+    /// applied. Behaves identically on every backend, including failing with
+    /// `StorageError::Overflow` if the result is outside the `i64` range. A
+    /// missing key is still initialized to `init` in that case.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be incremented
+    /// * `delta` - The amount to increment by (can be negative for decrements)
+    /// * `init` - The value to initialize a missing key with
+    ///
+    /// # Returns
+    /// * `Result<i64, errors::StorageError>` - The new value after incrementing
+    async fn atomic_increment_or_init(
+        &self,
+        key: impl AsRef<str> + Send,
+        delta: i64,
+        init: i64,
+    ) -> Result<i64, errors::StorageError>;
+
+    /// Atomically replaces an integer value if it currently equals `expected`.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be swapped
+    /// * `expected` - The value the key must currently hold
+    /// * `new` - The integer value to store
+    ///
+    /// # Returns
+    /// * `Result<bool, errors::StorageError>` - Whether the swap succeeded; always `false`
+    ///   if the key doesn't exist
+    async fn atomic_compare_and_swap(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected: i64,
+        new: i64,
+    ) -> Result<bool, errors::StorageError>;
+}
+
+/// Trait for exporting raw entries together with their remaining lifetime.
+///
+/// Provides the scanning and export primitives needed to copy data out of a
+/// backend, e.g. when migrating between storage systems.
+#[async_trait]
+pub trait RawExport {
+    /// Lists the keys of all live raw entries.
+    ///
+    /// # Arguments
+    /// * `prefix` - Optional prefix that returned keys must start with
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>, errors::StorageError>` - The matching keys, in no particular order
+    async fn scan_raw_keys(
+        &self,
+        prefix: Option<String>,
+    ) -> Result<Vec<String>, errors::StorageError>;
+
+    /// Exports raw bytes along with their remaining time-to-live.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be exported
+    ///
+    /// # Returns
+    /// * `Result<Option<(Vec<u8>, Option<u64>)>, errors::StorageError>` - The stored bytes and
+    ///   remaining expiration time in seconds, or `None` if the key doesn't exist or has expired
+    async fn export_raw(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<(Vec<u8>, Option<u64>)>, errors::StorageError>;
+}
+
+/// Trait for versioned records with optimistic concurrency control.
+///
+/// Each record stores a value together with a version counter. Keys that
+/// have never been written are treated as being at version `0`.
+#[async_trait]
+pub trait VersionedStorage {
+    /// Loads a versioned record.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose record should be loaded
+    ///
+    /// # Returns
+    /// * `Result<Option<(u64, String)>, errors::StorageError>` - The current version and value
+    ///   if the record exists
+    async fn load_versioned(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<(u64, String)>, errors::StorageError>;
+
+    /// Atomically replaces a record's value if its version matches.
+    ///
+    /// On success the version is incremented by one.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose record should be updated
+    /// * `expected_version` - The version the record must currently be at
+    /// * `new_value` - The value to store
+    ///
+    /// # Returns
+    /// * `Result<Option<u64>, errors::StorageError>` - The new version, or `None` if the
+    ///   current version didn't match
+    async fn compare_and_set_versioned(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected_version: u64,
+        new_value: String,
+    ) -> Result<Option<u64>, errors::StorageError>;
+}
+
+/// Marker trait for storage backends supporting every basic data type.
+///
+/// Automatically implemented for any type implementing [`StringStorage`],
+/// [`RawStorage`] and [`AtomicStorage`], so generic code can use a single
+/// `S: FullStorage` bound.
+pub trait FullStorage: StringStorage + RawStorage + AtomicStorage {}
+
+impl<T: StringStorage + RawStorage + AtomicStorage> FullStorage for T {}
+
+/// Trait for checking whether a key is present without loading its value.
+#[async_trait]
+pub trait KeyExists {
+    /// Checks whether a key exists.
+    ///
+    /// Expired keys are reported as absent.
+    ///
+    /// # Arguments
+    /// * `key` - The key to check
+    ///
+    /// # Returns
+    /// * `Result<bool, errors::StorageError>` - Whether the key exists
+    async fn exists(&self, key: impl AsRef<str> + Send) -> Result<bool, errors::StorageError>;
+
+    /// Checks whether each of several keys exists.
+    ///
+    /// Expired keys are reported as absent.
+    ///
+    /// # Arguments
+    /// * `keys` - The keys to check
+    ///
+    /// # Returns
+    /// * `Result<Vec<bool>, errors::StorageError>` - Whether each key exists, in the same
+    ///   order as `keys`
+    async fn exists_many(&self, keys: Vec<String>) -> Result<Vec<bool>, errors::StorageError>;
+}
+
+/// Trait for inspecting and managing the expiration of string values.
+#[async_trait]
+pub trait StringExpiry {
+    /// Gets the remaining time-to-live of a string value.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose expiration should be inspected
+    ///
+    /// # Returns
+    /// * `Result<Option<u64>, errors::StorageError>` - The remaining seconds until expiry, or
+    ///   `None` if the key has no expiration, doesn't exist or has expired; use
+    ///   [`KeyExists::exists`] to tell these apart
+    async fn ttl(&self, key: impl AsRef<str> + Send) -> Result<Option<u64>, errors::StorageError>;
+
+    /// Sets the expiration of an existing string value without rewriting it.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose expiration should be set
+    /// * `expiry` - Expiration time in seconds from now
+    ///
+    /// # Returns
+    /// * `Result<bool, errors::StorageError>` - Whether the key existed and its expiration was set
+    async fn expire(
+        &self,
+        key: impl AsRef<str> + Send,
+        expiry: u64,
+    ) -> Result<bool, errors::StorageError>;
+
+    /// Sets the expiration of an existing string value only if it extends it.
+    ///
+    /// The check and update happen atomically, so concurrent callers can only
+    /// ever push the expiration forward. A key without expiration already
+    /// lives forever and is left unchanged.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose expiration should be extended
+    /// * `expiry` - Expiration time in seconds from now
+    ///
+    /// # Returns
+    /// * `Result<bool, errors::StorageError>` - Whether the expiration was changed
+    async fn expire_if_longer(
+        &self,
+        key: impl AsRef<str> + Send,
+        expiry: u64,
+    ) -> Result<bool, errors::StorageError>;
+
+    /// Removes the expiration of a string value, making it permanent.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose expiration should be removed
+    ///
+    /// # Returns
+    /// * `Result<bool, errors::StorageError>` - Whether an expiration was removed
+    async fn persist(&self, key: impl AsRef<str> + Send) -> Result<bool, errors::StorageError>;
+
+    /// Stores a string value that expires at an absolute point in time.
+    ///
+    /// Unlike [`StringStorageWithExpiry::store_with_expiry`], the expiration is
+    /// not relative to the current time, so no conversion is needed for
+    /// callers that already hold a Unix timestamp. A timestamp in the past
+    /// leaves the key expired.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The string value to store
+    /// * `at_unix_secs` - The Unix timestamp in seconds at which the value expires
+    ///
+    /// # Returns
+    /// * `Result<types::StoreState, errors::StorageError>` - The result of the storage operation
+    async fn store_with_expiry_at(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        at_unix_secs: u64,
+    ) -> Result<types::StoreState, errors::StorageError>;
+
+    /// Stores a string value with an optional expiration in milliseconds.
+    ///
+    /// Behaves like [`StringStorageWithExpiry::store_with_expiry`], but allows
+    /// sub-second expiration times for short-lived values.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The string value to store
+    /// * `expiry_ms` - Optional expiration time in milliseconds from now
+    ///
+    /// # Returns
+    /// * `Result<types::StoreState, errors::StorageError>` - The result of the storage operation
+    async fn store_with_expiry_ms(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry_ms: Option<u64>,
+    ) -> Result<types::StoreState, errors::StorageError>;
+}
+
+/// Trait for conditional string writes that check and store atomically.
+#[async_trait]
+pub trait ConditionalStringStorage {
+    /// Stores a string value only if the key doesn't already exist.
+    ///
+    /// The existence check and the write happen atomically, making this
+    /// suitable for simple locks and idempotency guards.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The string value to store
+    /// * `expiry` - Optional expiration time in seconds from now
+    ///
+    /// # Returns
+    /// * `Result<types::StoreState, errors::StorageError>` - `New` if the value
+    ///   was written, `Unchanged` if the key already existed
+    async fn store_if_absent(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<types::StoreState, errors::StorageError>;
+
+    /// Replaces a string value only if it currently matches `expected`.
+    ///
+    /// The comparison and the write happen atomically. When the swap succeeds,
+    /// any existing expiration of the key is preserved.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be swapped
+    /// * `expected` - The value the key must currently hold, or `None` to require
+    ///   that the key is absent
+    /// * `new` - The string value to store
+    ///
+    /// # Returns
+    /// * `Result<types::StoreState, errors::StorageError>` - `Updated` (or `New`
+    ///   when `expected` is `None`) if the swap succeeded, `Unchanged` if the
+    ///   current value didn't match
+    async fn compare_and_swap(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected: Option<String>,
+        new: String,
+    ) -> Result<types::StoreState, errors::StorageError>;
+}
+
+/// Trait for atomic operations on floating-point values.
+///
+/// Provides methods for storing, loading and incrementing `f64` values
+/// atomically, e.g. for accumulating metric sums.
+#[async_trait]
+pub trait FloatAtomicStorage {
+    /// Atomically stores a floating-point value.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The floating-point value to store
+    ///
+    /// # Returns
+    /// * `Result<types::StoreState, errors::StorageError>` - The result of the storage operation
+    async fn atomic_store_f64(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: f64,
+    ) -> Result<types::StoreState, errors::StorageError>;
+
+    /// Atomically loads a floating-point value.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be loaded
+    ///
+    /// # Returns
+    /// * `Result<Option<f64>, errors::StorageError>` - The stored value if it exists
+    async fn atomic_load_f64(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<f64>, errors::StorageError>;
+
+    /// Atomically increments a floating-point value.
+    ///
+    /// A missing key is treated as `0.0` and created by the increment. Backends
+    /// round differently: Redis adds with extended precision and stores the
+    /// result as text, while the in-memory cache adds `f64`s, so repeated
+    /// increments may differ in the last digits and should be compared with a
+    /// tolerance.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be incremented
+    /// * `value` - The amount to increment by (can be negative for decrements)
+    ///
+    /// # Returns
+    /// * `Result<f64, errors::StorageError>` - The new value after incrementing
+    async fn atomic_increment_f64(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: f64,
+    ) -> Result<f64, errors::StorageError>;
+}
+
+/// Trait for storing and loading string values in bulk.
+///
+/// Lets backends amortize per-call overhead, such as network round-trips,
+/// across a group of keys.
+#[async_trait]
+pub trait BatchStringStorage {
+    /// Stores multiple string values with an optional expiration time.
+    ///
+    /// # Arguments
+    /// * `entries` - The (key, value) pairs to store
+    /// * `expiry` - Optional expiration time in seconds applied to every entry
+    ///
+    /// # Returns
+    /// * `Result<Vec<types::StoreState>, errors::StorageError>` - The result of each
+    ///   store, in the same order as `entries`
+    async fn store_many(
+        &self,
+        entries: Vec<(String, String)>,
+        expiry: Option<u64>,
+    ) -> Result<Vec<types::StoreState>, errors::StorageError>;
+
+    /// Loads multiple string values.
+    ///
+    /// # Arguments
+    /// * `keys` - The keys whose values should be loaded
+    ///
+    /// # Returns
+    /// * `Result<Vec<Option<String>>, errors::StorageError>` - The value of each key if
+    ///   it exists, in the same order as `keys`
+    async fn load_many(
+        &self,
+        keys: Vec<String>,
+    ) -> Result<Vec<Option<String>>, errors::StorageError>;
+}
+
+/// Trait for enumerating stored keys.
+///
+/// Intended for administrative tooling such as listing every entry under a
+/// namespace; listings may be expensive on large stores.
+#[async_trait]
+pub trait KeyListing {
+    /// Lists the keys of all live entries that start with a prefix.
+    ///
+    /// Keys replaced by their hash (see [`crate::keys::HASHED_KEY_PREFIX`]) are
+    /// listed and matched in their hashed form.
+    ///
+    /// # Arguments
+    /// * `prefix` - The prefix that returned keys must start with
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>, errors::StorageError>` - The matching keys, in no particular order
+    async fn keys_with_prefix(&self, prefix: String) -> Result<Vec<String>, errors::StorageError>;
+}
+
+/// Trait for administrative operations affecting a whole backend.
+///
+/// These operations act on every key at once and are intended for tooling,
+/// monitoring and tests.
+#[async_trait]
+pub trait AdminStorage {
+    /// Removes every entry from the storage.
+    ///
+    /// # Returns
+    /// * `Result<(), errors::StorageError>` - The result of the clear operation
+    async fn clear(&self) -> Result<(), errors::StorageError>;
+
+    /// Counts the entries held by the storage.
+    ///
+    /// Backends that evict expired entries lazily may include entries that
+    /// have expired but not been removed yet.
+    ///
+    /// # Returns
+    /// * `Result<usize, errors::StorageError>` - The number of stored entries
+    async fn len(&self) -> Result<usize, errors::StorageError>;
+
+    /// Checks whether the storage holds no entries.
+    ///
+    /// # Returns
+    /// * `Result<bool, errors::StorageError>` - Whether [`AdminStorage::len`] is `0`
+    async fn is_empty(&self) -> Result<bool, errors::StorageError> {
+        Ok(self.len().await? == 0)
+    }
+}
+
+/// Trait for checking that a storage backend is reachable.
+///
+/// Lets callers such as readiness probes treat every backend uniformly.
+#[async_trait]
+pub trait HealthCheck {
+    /// Checks that the storage can currently serve requests.
+    ///
+    /// # Returns
+    /// * `Result<(), errors::StorageError>` - `Ok(())` if the storage is reachable
+    async fn ping(&self) -> Result<(), errors::StorageError>;
+}
+
+/// Convenience methods built on top of [`StringStorage`].
+///
+/// Automatically implemented for every [`StringStorage`].
+#[async_trait]
+pub trait StringStorageExt: StringStorage {
+    /// Loads a string value, computing and storing it first if it is absent.
+    ///
+    /// The load and the store are separate operations, so concurrent callers
+    /// missing the same key may all run `init`; the last one to store wins.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be loaded
+    /// * `expiry` - Optional expiration time in seconds for a newly stored value
+    /// * `init` - Produces the value when the key is absent
+    ///
+    /// # Returns
+    /// * `Result<String, errors::StorageError>` - The existing or newly stored value
+    async fn get_or_insert_with<F, Fut>(
+        &self,
+        key: impl AsRef<str> + Send,
+        expiry: Option<u64>,
+        init: F,
+    ) -> Result<String, errors::StorageError>
+    where
+        F: FnOnce() -> Fut + Send,
+        Fut: std::future::Future<Output = String> + Send,
+    {
+        let key = key.as_ref();
+        if let Some(value) = self.load_string(key).await? {
+            return Ok(value);
+        }
+
+        let value = init().await;
+        self.store_with_expiry(key, value.clone(), expiry).await?;
+
+        Ok(value)
+    }
+}
+
+impl<T: StringStorage + ?Sized> StringStorageExt for T {}
+
+/// Trait for fixed-window rate limiting backed by expiring counters.
+#[async_trait]
+pub trait RateLimiter {
+    /// Counts a request against a key's limit for the current window.
+    ///
+    /// The first request in a window creates a counter expiring after
+    /// `window_secs`; later requests increment it until it expires and a new
+    /// window starts. Requests over the limit are still counted.
+    ///
+    /// # Arguments
+    /// * `key` - The key identifying the rate-limited subject
+    /// * `limit` - The number of requests allowed per window
+    /// * `window_secs` - The length of a window in seconds
+    ///
+    /// # Returns
+    /// * `Result<bool, errors::StorageError>` - Whether the request is within the limit
+    async fn check_rate(
+        &self,
+        key: impl AsRef<str> + Send,
+        limit: i64,
+        window_secs: u64,
+    ) -> Result<bool, errors::StorageError>;
+}
+
+/// Trait for appending to string values in place.
+#[async_trait]
+pub trait StringAppend {
+    /// Appends to a string value, creating it if it doesn't exist.
+    ///
+    /// Appending to a key with an expiration does not reset its time-to-live.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be appended to
+    /// * `value` - The string to append
+    ///
+    /// # Returns
+    /// * `Result<u64, errors::StorageError>` - The length of the value after appending, in bytes
+    async fn append(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<u64, errors::StorageError>;
+}
+
+/// Trait for reading part of a string value without loading all of it.
+#[async_trait]
+pub trait StringRange {
+    /// Loads the bytes `start..end` of a string value.
+    ///
+    /// Offsets are in bytes and the range is clamped to the value's length, so
+    /// an `end` past the end returns the available portion and an empty range
+    /// returns an empty string. A range splitting a multi-byte character yields
+    /// U+FFFD replacement characters in its place.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be read
+    /// * `start` - The offset of the first byte to return
+    /// * `end` - The offset one past the last byte to return
+    ///
+    /// # Returns
+    /// * `Result<Option<String>, errors::StorageError>` - The requested portion if the key exists
+    async fn get_range(
+        &self,
+        key: impl AsRef<str> + Send,
+        start: usize,
+        end: usize,
+    ) -> Result<Option<String>, errors::StorageError>;
+}
+
+/// Trait for lists of strings usable as queues and stacks.
+///
+/// A list is created by the first push and removed once its last element is
+/// popped, so a missing key behaves like an empty list.
+#[async_trait]
+pub trait ListStorage {
+    /// Appends a value to the end of a list.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    /// * `value` - The value to append
+    ///
+    /// # Returns
+    /// * `Result<u64, errors::StorageError>` - The length of the list after the push
+    async fn push_back(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<u64, errors::StorageError>;
+
+    /// Prepends a value to the start of a list.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    /// * `value` - The value to prepend
+    ///
+    /// # Returns
+    /// * `Result<u64, errors::StorageError>` - The length of the list after the push
+    async fn push_front(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<u64, errors::StorageError>;
+
+    /// Removes and returns the last value of a list.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    ///
+    /// # Returns
+    /// * `Result<Option<String>, errors::StorageError>` - The removed value, or `None` if the
+    ///   list is empty
+    async fn pop_back(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, errors::StorageError>;
+
+    /// Removes and returns the first value of a list.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    ///
+    /// # Returns
+    /// * `Result<Option<String>, errors::StorageError>` - The removed value, or `None` if the
+    ///   list is empty
+    async fn pop_front(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, errors::StorageError>;
+
+    /// Gets the number of values in a list.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    ///
+    /// # Returns
+    /// * `Result<u64, errors::StorageError>` - The length of the list, `0` if it doesn't exist
+    async fn list_len(&self, key: impl AsRef<str> + Send) -> Result<u64, errors::StorageError>;
+
+    /// Gets the values of a list between two indices, inclusive.
+    ///
+    /// Negative indices count from the end of the list, so `-1` is the last
+    /// value. Indices past the end are clamped, as in Redis `LRANGE`.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    /// * `start` - The index of the first value to return
+    /// * `stop` - The index of the last value to return
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>, errors::StorageError>` - The values in the range, in list order
+    async fn list_range(
+        &self,
+        key: impl AsRef<str> + Send,
+        start: i64,
+        stop: i64,
+    ) -> Result<Vec<String>, errors::StorageError>;
+}
+
+/// Trait for unordered sets of unique strings.
+///
+/// A set is created by the first added member and removed once its last
+/// member is removed, so a missing key behaves like an empty set.
+#[async_trait]
+pub trait SetStorage {
+    /// Adds a member to a set.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the set
+    /// * `member` - The member to add
+    ///
+    /// # Returns
+    /// * `Result<bool, errors::StorageError>` - Whether the member was newly added
+    async fn set_add(
+        &self,
+        key: impl AsRef<str> + Send,
+        member: String,
+    ) -> Result<bool, errors::StorageError>;
+
+    /// Removes a member from a set.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the set
+    /// * `member` - The member to remove
+    ///
+    /// # Returns
+    /// * `Result<bool, errors::StorageError>` - Whether the member was present
+    async fn set_remove(
+        &self,
+        key: impl AsRef<str> + Send,
+        member: String,
+    ) -> Result<bool, errors::StorageError>;
+
+    /// Checks whether a set contains a member.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the set
+    /// * `member` - The member to look for
+    ///
+    /// # Returns
+    /// * `Result<bool, errors::StorageError>` - Whether the member is present
+    async fn set_contains(
+        &self,
+        key: impl AsRef<str> + Send,
+        member: String,
+    ) -> Result<bool, errors::StorageError>;
+
+    /// Gets every member of a set.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the set
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>, errors::StorageError>` - The members, in no particular order
+    async fn set_members(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Vec<String>, errors::StorageError>;
+
+    /// Gets the number of members in a set.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the set
+    ///
+    /// # Returns
+    /// * `Result<u64, errors::StorageError>` - The size of the set, `0` if it doesn't exist
+    async fn set_len(&self, key: impl AsRef<str> + Send) -> Result<u64, errors::StorageError>;
+}
+
+/// Trait for maps of string fields to string values stored under one key.
+///
+/// Fields can be read and updated individually, without rewriting the whole
+/// map. A map is created by the first field set and removed once its last
+/// field is deleted, so a missing key behaves like an empty map.
+#[async_trait]
+pub trait HashStorage {
+    /// Sets a field of a map.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the map
+    /// * `field` - The field to set
+    /// * `value` - The value to store in the field
+    ///
+    /// # Returns
+    /// * `Result<types::StoreState, errors::StorageError>` - Whether the field was created or updated
+    async fn hash_set(
+        &self,
+        key: impl AsRef<str> + Send,
+        field: String,
+        value: String,
+    ) -> Result<types::StoreState, errors::StorageError>;
+
+    /// Gets a field of a map.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the map
+    /// * `field` - The field to get
+    ///
+    /// # Returns
+    /// * `Result<Option<String>, errors::StorageError>` - The field's value if it exists
+    async fn hash_get(
+        &self,
+        key: impl AsRef<str> + Send,
+        field: String,
+    ) -> Result<Option<String>, errors::StorageError>;
+
+    /// Gets every field of a map.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the map
+    ///
+    /// # Returns
+    /// * `Result<HashMap<String, String>, errors::StorageError>` - The fields and their values,
+    ///   empty if the map doesn't exist
+    async fn hash_get_all(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<std::collections::HashMap<String, String>, errors::StorageError>;
+
+    /// Deletes a field of a map.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the map
+    /// * `field` - The field to delete
+    ///
+    /// # Returns
+    /// * `Result<bool, errors::StorageError>` - Whether the field existed
+    async fn hash_delete(
+        &self,
+        key: impl AsRef<str> + Send,
+        field: String,
+    ) -> Result<bool, errors::StorageError>;
+
+    /// Gets the number of fields in a map.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the map
+    ///
+    /// # Returns
+    /// * `Result<u64, errors::StorageError>` - The number of fields, `0` if the map doesn't exist
+    async fn hash_len(&self, key: impl AsRef<str> + Send) -> Result<u64, errors::StorageError>;
+}
+
+/// Stream of messages received on a subscribed channel.
+pub type MessageStream = std::pin::Pin<Box<dyn futures::Stream<Item = String> + Send>>;
+
+/// Trait for publishing messages to channels and subscribing to them.
+///
+/// Delivery is fire-and-forget: messages are only received by subscriptions
+/// that exist when they are published, and are never stored.
+#[async_trait]
+pub trait PubSub {
+    /// Publishes a message to a channel.
+    ///
+    /// # Arguments
+    /// * `channel` - The channel to publish to
+    /// * `message` - The message to publish
+    ///
+    /// # Returns
+    /// * `Result<u64, errors::StorageError>` - The number of subscribers that received the message
+    async fn publish(&self, channel: String, message: String) -> Result<u64, errors::StorageError>;
+
+    /// Subscribes to a channel.
+    ///
+    /// # Arguments
+    /// * `channel` - The channel to subscribe to
+    ///
+    /// # Returns
+    /// * `Result<MessageStream, errors::StorageError>` - A stream of the messages published to the
+    ///   channel from now on; dropping it ends the subscription
+    async fn subscribe(&self, channel: String) -> Result<MessageStream, errors::StorageError>;
+}
+
+/// Trait for deleting many keys at once.
+#[async_trait]
+pub trait BulkDelete {
+    /// Deletes every key that starts with a prefix, whatever kind of value it holds.
+    ///
+    /// Keys replaced by their hash (see [`crate::keys::HASHED_KEY_PREFIX`]) are
+    /// matched in their hashed form. Backends may not delete the keys
+    /// atomically, so keys written concurrently under the prefix can survive.
+    ///
+    /// # Arguments
+    /// * `prefix` - The prefix that deleted keys must start with
+    ///
+    /// # Returns
+    /// * `Result<u64, errors::StorageError>` - The number of keys deleted
+    async fn delete_prefix(&self, prefix: String) -> Result<u64, errors::StorageError>;
+}
+
+/// Trait for renaming keys.
+#[async_trait]
+pub trait KeyRename {
+    /// Moves the value stored under one key to another key.
+    ///
+    /// Any value already stored under `to` is replaced. The value keeps its
+    /// remaining expiration.
+    ///
+    /// # Arguments
+    /// * `from` - The key whose value should be moved
+    /// * `to` - The key to move the value to
+    ///
+    /// # Returns
+    /// * `Result<(), errors::StorageError>` - `StorageError::NotFound` if `from`
+    ///   doesn't exist
+    async fn rename(
+        &self,
+        from: impl AsRef<str> + Send,
+        to: impl AsRef<str> + Send,
+    ) -> Result<(), errors::StorageError>;
+}
+
+/// Trait for reading many raw byte values at once.
+///
+/// The binary counterpart of the batched string loads, letting backends fetch
+/// a group of keys in a single round-trip.
+#[async_trait]
+pub trait BatchRawStorage {
+    /// Loads multiple raw byte values.
+    ///
+    /// # Arguments
+    /// * `keys` - The keys whose values should be loaded
+    ///
+    /// # Returns
+    /// * `Result<Vec<Option<Vec<u8>>>, errors::StorageError>` - The value of each key if
+    ///   it exists, in the same order as `keys`
+    async fn load_raw_many(
+        &self,
+        keys: Vec<String>,
+    ) -> Result<Vec<Option<Vec<u8>>>, errors::StorageError>;
+}
+
+/// Trait for replacing a string value while reading the previous one.
+#[async_trait]
+pub trait StringGetSet {
+    /// Stores a string value and returns the value it replaced, in one atomic step.
+    ///
+    /// The new value is stored without expiration, replacing any expiration
+    /// the key had.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The string value to store
+    ///
+    /// # Returns
+    /// * `Result<Option<String>, errors::StorageError>` - The previous value, or `None`
+    ///   if the key didn't exist
+    async fn get_set(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<Option<String>, errors::StorageError>;
+}
+
+/// Trait for incrementing integers that must stay within a range.
+#[async_trait]
+pub trait BoundedIncrement {
+    /// Increments an atomic integer value and clamps the result into `[min, max]`.
+    ///
+    /// The increment and the clamp happen in one atomic step. A missing key
+    /// counts as `0`, and a value already outside the range is clamped even
+    /// when `delta` is `0`. The value keeps its expiration.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be incremented
+    /// * `delta` - The integer value to increment by
+    /// * `min` - The lowest value the key may hold
+    /// * `max` - The highest value the key may hold
+    ///
+    /// # Returns
+    /// * `Result<i64, errors::StorageError>` - The new, clamped value,
+    ///   `StorageError::Overflow` if the unclamped sum is outside the `i64` range,
+    ///   or `StorageError::InvalidArgument` if `min` is greater than `max`
+    async fn atomic_increment_bounded(
+        &self,
+        key: impl AsRef<str> + Send,
+        delta: i64,
+        min: i64,
+        max: i64,
+    ) -> Result<i64, errors::StorageError>;
 }
@@ -0,0 +1,125 @@
+//! Time sources used for expiration.
+//!
+//! Backends that track expiry themselves read the current time through a
+//! [`Clock`], so tests can substitute a [`MockClock`] (with the `test-util`
+//! feature) and advance time manually instead of sleeping.
+
+use crate::errors::StorageError;
+
+/// Source of the current Unix time.
+pub trait Clock: Send + Sync {
+    /// Gets the current Unix timestamp in seconds.
+    ///
+    /// # Returns
+    /// * `Result<u64, StorageError>` - The current time, or `StorageError::Clock`
+    ///   if it cannot be determined
+    fn now_secs(&self) -> Result<u64, StorageError>;
+
+    /// Gets the current Unix timestamp in milliseconds.
+    ///
+    /// Defaults to [`Clock::now_secs`] scaled to milliseconds, so clocks that
+    /// only track whole seconds need not implement it.
+    ///
+    /// # Returns
+    /// * `Result<u64, StorageError>` - The current time, or `StorageError::Clock`
+    ///   if it cannot be determined
+    fn now_millis(&self) -> Result<u64, StorageError> {
+        Ok(self.now_secs()?.saturating_mul(1000))
+    }
+}
+
+/// [`Clock`] backed by the system's wall-clock time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    /// Gets the current Unix timestamp in seconds.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Current Unix timestamp in seconds
+    /// * `Err(StorageError::Clock)` - If the system time is set to before the Unix
+    ///   epoch (1970-01-01 00:00:00 UTC), e.g. because the system clock is
+    ///   incorrectly set or experiencing severe clock skew
+    fn now_secs(&self) -> Result<u64, StorageError> {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .map_err(|e| StorageError::Clock(e.to_string()))
+    }
+
+    /// Gets the current Unix timestamp in milliseconds.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Current Unix timestamp in milliseconds
+    /// * `Err(StorageError::Clock)` - If the system time is set to before the Unix epoch
+    fn now_millis(&self) -> Result<u64, StorageError> {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .map_err(|e| StorageError::Clock(e.to_string()))
+    }
+}
+
+/// [`Clock`] whose time only moves when told to.
+///
+/// Clones share the same time, so a test can keep one handle to advance time
+/// while the storage holds another.
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug, Clone, Default)]
+pub struct MockClock {
+    millis: std::sync::Arc<std::sync::atomic::AtomicU64>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl MockClock {
+    /// Creates a clock stopped at the given Unix timestamp.
+    ///
+    /// # Arguments
+    /// * `secs` - The initial Unix timestamp in seconds
+    pub fn new(secs: u64) -> Self {
+        Self {
+            millis: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(
+                secs.saturating_mul(1000),
+            )),
+        }
+    }
+
+    /// Moves the clock forward.
+    ///
+    /// # Arguments
+    /// * `secs` - The number of seconds to advance by
+    pub fn advance(&self, secs: u64) {
+        self.advance_millis(secs.saturating_mul(1000));
+    }
+
+    /// Moves the clock forward by a number of milliseconds.
+    ///
+    /// # Arguments
+    /// * `millis` - The number of milliseconds to advance by
+    pub fn advance_millis(&self, millis: u64) {
+        self.millis
+            .fetch_add(millis, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Sets the clock to a Unix timestamp.
+    ///
+    /// # Arguments
+    /// * `secs` - The Unix timestamp in seconds
+    pub fn set(&self, secs: u64) {
+        self.millis.store(
+            secs.saturating_mul(1000),
+            std::sync::atomic::Ordering::SeqCst,
+        );
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Clock for MockClock {
+    fn now_secs(&self) -> Result<u64, StorageError> {
+        Ok(self.now_millis()? / 1000)
+    }
+
+    fn now_millis(&self) -> Result<u64, StorageError> {
+        Ok(self.millis.load(std::sync::atomic::Ordering::SeqCst))
+    }
+}
@@ -0,0 +1,87 @@
+//! Serialization formats for typed storage.
+//!
+//! A [`Codec`] turns serde-serializable values into bytes and back. Every
+//! codec reports failures as `StorageError::DeserializationError`, with the
+//! name of the format in the message.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::errors::StorageError;
+
+/// Serialization format used to encode values as bytes.
+pub trait Codec: Send + Sync {
+    /// Encodes a value into bytes.
+    ///
+    /// # Arguments
+    /// * `value` - The value to encode
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>, StorageError>` - The encoded bytes or an error
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, StorageError>;
+
+    /// Decodes a value from bytes.
+    ///
+    /// # Arguments
+    /// * `bytes` - The bytes to decode
+    ///
+    /// # Returns
+    /// * `Result<T, StorageError>` - The decoded value or an error
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, StorageError>;
+}
+
+/// Create a codec error naming the format and the failed operation
+fn codec_error(format: &str, operation: &str, error: impl std::fmt::Display) -> StorageError {
+    StorageError::DeserializationError(format!("failed to {operation} {format}: {error}"))
+}
+
+/// [`Codec`] that encodes values as JSON.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, StorageError> {
+        serde_json::to_vec(value).map_err(|e| codec_error("JSON", "encode", e))
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, StorageError> {
+        serde_json::from_slice(bytes).map_err(|e| codec_error("JSON", "decode", e))
+    }
+}
+
+/// [`Codec`] that encodes values with bincode.
+///
+/// The encoding is compact but not self-describing, so values must be decoded
+/// into the same type they were encoded from.
+#[cfg(feature = "bincode")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BincodeCodec;
+
+#[cfg(feature = "bincode")]
+impl Codec for BincodeCodec {
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, StorageError> {
+        bincode::serialize(value).map_err(|e| codec_error("bincode", "encode", e))
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, StorageError> {
+        bincode::deserialize(bytes).map_err(|e| codec_error("bincode", "decode", e))
+    }
+}
+
+/// [`Codec`] that encodes values as MessagePack.
+///
+/// Structs are encoded as maps keyed by field name, so fields can be added
+/// without breaking previously stored values.
+#[cfg(feature = "messagepack")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessagePackCodec;
+
+#[cfg(feature = "messagepack")]
+impl Codec for MessagePackCodec {
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, StorageError> {
+        rmp_serde::to_vec_named(value).map_err(|e| codec_error("MessagePack", "encode", e))
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, StorageError> {
+        rmp_serde::from_slice(bytes).map_err(|e| codec_error("MessagePack", "decode", e))
+    }
+}
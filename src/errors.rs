@@ -6,4 +6,60 @@ pub enum StorageError {
     ConnectionError(String),
     #[error("Failed to deserialize value: {0}")]
     DeserializationError(String),
+    /// The key an operation requires does not exist.
+    ///
+    /// Loads report missing keys as `Ok(None)` instead; this variant is for
+    /// operations where an absent key is a failure.
+    #[error("Key not found: {0}")]
+    NotFound(String),
+    /// The system clock could not be read, e.g. because it is set before the Unix epoch.
+    #[error("Failed to read the system clock: {0}")]
+    Clock(String),
+    /// The operation did not complete in time.
+    #[error("Operation timed out: {0}")]
+    Timeout(String),
+    /// A stored value could not be decrypted, e.g. because it was encrypted
+    /// with a different key or has been tampered with.
+    #[error("Failed to decrypt value: {0}")]
+    Decryption(String),
+    /// An integer operation's result does not fit in an `i64`.
+    #[error("Integer overflow: {0}")]
+    Overflow(String),
+    /// Reading or writing a file failed, e.g. while persisting the in-memory store.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// An optimistic transaction failed on every attempt because a watched key
+    /// was modified concurrently; holds the number of attempts made.
+    #[error("Transaction aborted after {0} conflicting attempts")]
+    RetriesExhausted(u32),
+    /// A key was rejected by the configured `KeyValidator`.
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+    /// An argument is outside the values the operation accepts, e.g. an empty range.
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+}
+
+impl StorageError {
+    /// Checks whether the error is likely to be temporary.
+    ///
+    /// Transient errors are caused by the backend being unreachable or slow,
+    /// so retrying the operation later may succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::ConnectionError(_) | Self::Timeout(_))
+    }
+}
+
+#[cfg(feature = "redis")]
+impl From<fred::error::RedisError> for StorageError {
+    /// Maps `fred`'s error kind to the closest variant, keeping the message.
+    fn from(error: fred::error::RedisError) -> Self {
+        use fred::error::RedisErrorKind;
+
+        match error.kind() {
+            RedisErrorKind::Timeout => Self::Timeout(error.to_string()),
+            RedisErrorKind::Parse => Self::DeserializationError(error.to_string()),
+            _ => Self::ConnectionError(error.to_string()),
+        }
+    }
 }
@@ -0,0 +1,126 @@
+//! Key transformations shared across storage backends.
+//!
+//! Long keys (e.g. full URLs) can be replaced by a fixed-length hash so that
+//! memory usage per key stays bounded and keys remain within backend limits.
+
+use crate::errors::StorageError;
+
+/// Checks a caller-provided key before a backend operation uses it.
+///
+/// Configured through `IMCConfig::key_validator` or
+/// `RedisStorageConfig::key_validator`, it runs at the start of every
+/// operation taking a key, before any long key is hashed. A rejected key
+/// should be reported as `StorageError::InvalidKey`, which the operation then
+/// fails with.
+pub type KeyValidator = fn(&str) -> Result<(), StorageError>;
+
+/// Namespace prefixed to every key that has been replaced by its hash.
+pub const HASHED_KEY_PREFIX: &str = "tsot:h:";
+
+/// Length of a hex-encoded blake3 digest.
+const HASH_HEX_LEN: usize = 64;
+
+/// Replaces a key longer than `threshold` bytes with a stable hash.
+///
+/// The resulting key is [`HASHED_KEY_PREFIX`] followed by the hex-encoded
+/// blake3 digest of the original key. Keys that are already in hashed form are
+/// returned unchanged, so the transform can safely be applied more than once
+/// (e.g. to keys returned by a scan).
+///
+/// Two distinct keys map to the same hash with probability around 2^-256,
+/// which is negligible for any realistic keyspace.
+///
+/// # Arguments
+/// * `key` - The original key
+/// * `threshold` - Maximum key length left untouched, or `None` to disable hashing
+///
+/// # Returns
+/// * `String` - The key to use with the underlying store
+pub(crate) fn hash_long_key(key: &str, threshold: Option<usize>) -> String {
+    match threshold {
+        Some(limit) if key.len() > limit && !is_hashed(key) => format!(
+            "{}{}",
+            HASHED_KEY_PREFIX,
+            blake3::hash(key.as_bytes()).to_hex()
+        ),
+        _ => key.to_string(),
+    }
+}
+
+/// Checks whether a key has already been replaced by its hash.
+fn is_hashed(key: &str) -> bool {
+    key.len() == HASHED_KEY_PREFIX.len() + HASH_HEX_LEN && key.starts_with(HASHED_KEY_PREFIX)
+}
+
+/// Checks whether a key matches a glob pattern, as used by Redis' `SCAN ... MATCH`.
+///
+/// Supports `*`, `?`, character classes such as `[abc]`, `[^abc]` and
+/// `[a-z]`, and `\` to match the next character literally.
+#[cfg(all(feature = "imc", feature = "async"))]
+pub(crate) fn glob_match(pattern: &str, key: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let key: Vec<char> = key.chars().collect();
+    let (mut p, mut k) = (0, 0);
+    // Pattern position after the last `*` and the key position it was retried from
+    let mut retry = None;
+
+    while k < key.len() {
+        if pattern.get(p) == Some(&'*') {
+            p += 1;
+            retry = Some((p, k));
+            continue;
+        }
+        if let Some(next) = match_char(&pattern, p, key[k]) {
+            p = next;
+            k += 1;
+            continue;
+        }
+        // Let the last `*` absorb one more character and try again
+        match retry {
+            Some((star, from)) => {
+                p = star;
+                k = from + 1;
+                retry = Some((star, from + 1));
+            }
+            None => return false,
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Matches the pattern element starting at `p` against one character.
+///
+/// # Returns
+/// * `Option<usize>` - The position after the element if it matches
+#[cfg(all(feature = "imc", feature = "async"))]
+fn match_char(pattern: &[char], p: usize, c: char) -> Option<usize> {
+    match *pattern.get(p)? {
+        '?' => Some(p + 1),
+        '\\' if p + 1 < pattern.len() => (pattern[p + 1] == c).then_some(p + 2),
+        '[' => {
+            let mut i = p + 1;
+            let negated = pattern.get(i) == Some(&'^');
+            if negated {
+                i += 1;
+            }
+            let mut matched = false;
+            while i < pattern.len() && pattern[i] != ']' {
+                if pattern[i] == '\\' && i + 1 < pattern.len() {
+                    matched |= pattern[i + 1] == c;
+                    i += 2;
+                } else if i + 2 < pattern.len() && pattern[i + 1] == '-' && pattern[i + 2] != ']' {
+                    let (low, high) = (pattern[i], pattern[i + 2]);
+                    matched |= (low.min(high)..=low.max(high)).contains(&c);
+                    i += 3;
+                } else {
+                    matched |= pattern[i] == c;
+                    i += 1;
+                }
+            }
+            // An unterminated class extends to the end of the pattern
+            (matched != negated).then_some((i + 1).min(pattern.len()))
+        }
+        literal => (literal == c).then_some(p + 1),
+    }
+}
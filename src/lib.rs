@@ -1,7 +1,17 @@
 #[cfg(feature = "async")]
 pub mod asynchronous;
+pub mod clock;
+#[cfg(feature = "serde")]
+pub mod codec;
 pub mod errors;
+pub mod keys;
+#[cfg(feature = "async")]
+pub mod migrate;
 pub mod storage;
 #[cfg(feature = "sync")]
 pub mod sync;
+#[cfg(feature = "metrics")]
+pub mod telemetry;
 pub mod types;
+#[cfg(feature = "async")]
+pub mod wrappers;
@@ -0,0 +1,52 @@
+//! Helpers for moving data between storage backends.
+
+use crate::{
+    asynchronous::{RawExport, RawStorage},
+    errors,
+};
+
+/// Copies raw entries from one backend into another.
+///
+/// Scans the source for live keys (restricted to `prefix` if given) and writes
+/// each value into the destination with its remaining time-to-live. Keys that
+/// expire or disappear while the migration is running are skipped. Keys
+/// without a time-to-live stay permanent, even in a destination that applies a
+/// default expiry to writes without one.
+///
+/// # Arguments
+/// * `src` - The backend to copy entries from
+/// * `dst` - The backend to copy entries into
+/// * `prefix` - Optional prefix that migrated keys must start with
+///
+/// # Returns
+/// * `Result<usize, errors::StorageError>` - The number of entries copied
+pub async fn migrate<Src, Dst>(
+    src: &Src,
+    dst: &Dst,
+    prefix: Option<String>,
+) -> Result<usize, errors::StorageError>
+where
+    Src: RawStorage + RawExport + Sync,
+    Dst: RawStorage + Sync,
+{
+    let mut copied = 0;
+
+    for key in src.scan_raw_keys(prefix).await? {
+        match src.export_raw(key.clone()).await? {
+            // Expired between the scan and the export, or about to expire
+            None | Some((_, Some(0))) => continue,
+            Some((value, Some(expiry))) => {
+                dst.store_raw_with_expiry(key, value, Some(expiry)).await?;
+            }
+            // Keeping the TTL of an absent key stores the value without one,
+            // where a plain store would get the destination's default expiry
+            Some((value, None)) => {
+                dst.delete_raw(&key).await?;
+                dst.store_raw_keep_ttl(key, value).await?;
+            }
+        }
+        copied += 1;
+    }
+
+    Ok(copied)
+}
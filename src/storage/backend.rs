@@ -0,0 +1,1119 @@
+//! Runtime selection between the in-memory and Redis backends.
+//!
+//! [`Backend`] wraps either backend and implements every storage trait both of
+//! them support by dispatching to the active one, so code can pick a backend
+//! from configuration while using a single concrete type.
+
+use crate::{
+    errors::StorageError,
+    storage::{
+        imc::{IMCConfig, IMCModule},
+        redis::{RedisStorageConfig, RedisStorageModule},
+    },
+};
+
+/// Configuration selecting which backend a [`Backend`] connects to.
+pub enum BackendConfig {
+    /// Use an in-memory cache
+    Imc(IMCConfig),
+    /// Use a Redis server or cluster
+    Redis(RedisStorageConfig),
+}
+
+/// A storage backend chosen at runtime.
+#[derive(Clone)]
+pub enum Backend {
+    /// An in-memory cache
+    Imc(IMCModule),
+    /// A Redis connection
+    Redis(RedisStorageModule),
+}
+
+impl Backend {
+    /// Creates the backend described by a configuration.
+    ///
+    /// # Arguments
+    /// * `config` - Selects the backend and holds its settings
+    ///
+    /// # Returns
+    /// * `Ok(Backend)` - The configured backend
+    /// * `Err(StorageError::ConnectionError)` - If connecting to Redis failed
+    #[cfg(feature = "async")]
+    pub async fn new(config: BackendConfig) -> Result<Self, StorageError> {
+        Ok(match config {
+            BackendConfig::Imc(config) => Self::Imc(IMCModule::new(config)),
+            BackendConfig::Redis(config) => Self::Redis(RedisStorageModule::new(config).await?),
+        })
+    }
+
+    /// Creates the backend described by a configuration for use with the
+    /// synchronous traits.
+    ///
+    /// Redis backends are created with [`RedisStorageModule::new_blocking`].
+    ///
+    /// # Arguments
+    /// * `config` - Selects the backend and holds its settings
+    ///
+    /// # Returns
+    /// * `Ok(Backend)` - The configured backend
+    /// * `Err(StorageError::ConnectionError)` - If connecting to Redis failed
+    #[cfg(all(feature = "sync", feature = "async"))]
+    pub fn new_blocking(config: BackendConfig) -> Result<Self, StorageError> {
+        Ok(match config {
+            BackendConfig::Imc(config) => Self::Imc(IMCModule::new(config)),
+            BackendConfig::Redis(config) => Self::Redis(RedisStorageModule::new_blocking(config)?),
+        })
+    }
+}
+
+impl From<IMCModule> for Backend {
+    fn from(module: IMCModule) -> Self {
+        Self::Imc(module)
+    }
+}
+
+impl From<RedisStorageModule> for Backend {
+    fn from(module: RedisStorageModule) -> Self {
+        Self::Redis(module)
+    }
+}
+
+/// Evaluates `$call` with `$inner` bound to the active backend.
+macro_rules! dispatch {
+    ($self:ident, $inner:ident => $call:expr) => {
+        match $self {
+            Backend::Imc($inner) => $call,
+            Backend::Redis($inner) => $call,
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use async_trait::async_trait;
+
+    use super::Backend;
+    use crate::{
+        asynchronous::{
+            AtomicStorage, BatchRawStorage, BatchStringStorage, BoundedIncrement, BulkDelete,
+            ConditionalStringStorage, FloatAtomicStorage, HashStorage, HealthCheck, KeyExists,
+            KeyListing, KeyRename, ListStorage, MessageStream, PubSub, RateLimiter, RawExport,
+            RawStorage, RawStorageWithExpiry, SetStorage, StringAppend, StringExpiry, StringGetSet,
+            StringRange, StringStorage, StringStorageWithExpiry, VersionedStorage,
+        },
+        errors, types,
+    };
+
+    #[async_trait]
+    impl StringStorage for Backend {
+        async fn load_string(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => StringStorage::load_string(inner, key).await)
+        }
+
+        async fn delete_string(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => StringStorage::delete_string(inner, key).await)
+        }
+
+        async fn take_string(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => StringStorage::take_string(inner, key).await)
+        }
+    }
+
+    #[async_trait]
+    impl StringStorageWithExpiry for Backend {
+        async fn store_with_expiry(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: String,
+            expiry: Option<u64>,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => StringStorageWithExpiry::store_with_expiry(inner, key, value, expiry).await)
+        }
+
+        async fn store_keep_ttl(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: String,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => StringStorageWithExpiry::store_keep_ttl(inner, key, value).await)
+        }
+    }
+
+    #[async_trait]
+    impl RawStorage for Backend {
+        async fn load_raw(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<Vec<u8>>, errors::StorageError> {
+            dispatch!(self, inner => RawStorage::load_raw(inner, key).await)
+        }
+
+        async fn delete_raw(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => RawStorage::delete_raw(inner, key).await)
+        }
+    }
+
+    #[async_trait]
+    impl RawStorageWithExpiry for Backend {
+        async fn store_raw_with_expiry(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: Vec<u8>,
+            expiry: Option<u64>,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => RawStorageWithExpiry::store_raw_with_expiry(inner, key, value, expiry).await)
+        }
+
+        async fn store_raw_keep_ttl(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: Vec<u8>,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => RawStorageWithExpiry::store_raw_keep_ttl(inner, key, value).await)
+        }
+    }
+
+    #[async_trait]
+    impl AtomicStorage for Backend {
+        async fn atomic_store(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: i64,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_store(inner, key, value).await)
+        }
+
+        async fn atomic_load(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<i64>, errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_load(inner, key).await)
+        }
+
+        async fn atomic_delete(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_delete(inner, key).await)
+        }
+
+        async fn atomic_increment(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: i64,
+        ) -> Result<Option<i64>, errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_increment(inner, key, value).await)
+        }
+
+        async fn atomic_increment_or_init(
+            &self,
+            key: impl AsRef<str> + Send,
+            delta: i64,
+            init: i64,
+        ) -> Result<i64, errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_increment_or_init(inner, key, delta, init).await)
+        }
+
+        async fn atomic_compare_and_swap(
+            &self,
+            key: impl AsRef<str> + Send,
+            expected: i64,
+            new: i64,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_compare_and_swap(inner, key, expected, new).await)
+        }
+    }
+
+    #[async_trait]
+    impl RawExport for Backend {
+        async fn scan_raw_keys(
+            &self,
+            prefix: Option<String>,
+        ) -> Result<Vec<String>, errors::StorageError> {
+            dispatch!(self, inner => RawExport::scan_raw_keys(inner, prefix).await)
+        }
+
+        async fn export_raw(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<(Vec<u8>, Option<u64>)>, errors::StorageError> {
+            dispatch!(self, inner => RawExport::export_raw(inner, key).await)
+        }
+    }
+
+    #[async_trait]
+    impl VersionedStorage for Backend {
+        async fn load_versioned(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<(u64, String)>, errors::StorageError> {
+            dispatch!(self, inner => VersionedStorage::load_versioned(inner, key).await)
+        }
+
+        async fn compare_and_set_versioned(
+            &self,
+            key: impl AsRef<str> + Send,
+            expected_version: u64,
+            new_value: String,
+        ) -> Result<Option<u64>, errors::StorageError> {
+            dispatch!(self, inner => VersionedStorage::compare_and_set_versioned(inner, key, expected_version, new_value).await)
+        }
+    }
+
+    #[async_trait]
+    impl KeyExists for Backend {
+        async fn exists(&self, key: impl AsRef<str> + Send) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => KeyExists::exists(inner, key).await)
+        }
+
+        async fn exists_many(&self, keys: Vec<String>) -> Result<Vec<bool>, errors::StorageError> {
+            dispatch!(self, inner => KeyExists::exists_many(inner, keys).await)
+        }
+    }
+
+    #[async_trait]
+    impl StringExpiry for Backend {
+        async fn ttl(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<u64>, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::ttl(inner, key).await)
+        }
+
+        async fn expire(
+            &self,
+            key: impl AsRef<str> + Send,
+            expiry: u64,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::expire(inner, key, expiry).await)
+        }
+
+        async fn expire_if_longer(
+            &self,
+            key: impl AsRef<str> + Send,
+            expiry: u64,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::expire_if_longer(inner, key, expiry).await)
+        }
+
+        async fn persist(&self, key: impl AsRef<str> + Send) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::persist(inner, key).await)
+        }
+
+        async fn store_with_expiry_at(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: String,
+            at_unix_secs: u64,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::store_with_expiry_at(inner, key, value, at_unix_secs).await)
+        }
+
+        async fn store_with_expiry_ms(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: String,
+            expiry_ms: Option<u64>,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::store_with_expiry_ms(inner, key, value, expiry_ms).await)
+        }
+    }
+
+    #[async_trait]
+    impl ConditionalStringStorage for Backend {
+        async fn store_if_absent(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: String,
+            expiry: Option<u64>,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => ConditionalStringStorage::store_if_absent(inner, key, value, expiry).await)
+        }
+
+        async fn compare_and_swap(
+            &self,
+            key: impl AsRef<str> + Send,
+            expected: Option<String>,
+            new: String,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => ConditionalStringStorage::compare_and_swap(inner, key, expected, new).await)
+        }
+    }
+
+    #[async_trait]
+    impl FloatAtomicStorage for Backend {
+        async fn atomic_store_f64(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: f64,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => FloatAtomicStorage::atomic_store_f64(inner, key, value).await)
+        }
+
+        async fn atomic_load_f64(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<f64>, errors::StorageError> {
+            dispatch!(self, inner => FloatAtomicStorage::atomic_load_f64(inner, key).await)
+        }
+
+        async fn atomic_increment_f64(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: f64,
+        ) -> Result<f64, errors::StorageError> {
+            dispatch!(self, inner => FloatAtomicStorage::atomic_increment_f64(inner, key, value).await)
+        }
+    }
+
+    #[async_trait]
+    impl BatchStringStorage for Backend {
+        async fn store_many(
+            &self,
+            entries: Vec<(String, String)>,
+            expiry: Option<u64>,
+        ) -> Result<Vec<types::StoreState>, errors::StorageError> {
+            dispatch!(self, inner => BatchStringStorage::store_many(inner, entries, expiry).await)
+        }
+
+        async fn load_many(
+            &self,
+            keys: Vec<String>,
+        ) -> Result<Vec<Option<String>>, errors::StorageError> {
+            dispatch!(self, inner => BatchStringStorage::load_many(inner, keys).await)
+        }
+    }
+
+    #[async_trait]
+    impl KeyListing for Backend {
+        async fn keys_with_prefix(
+            &self,
+            prefix: String,
+        ) -> Result<Vec<String>, errors::StorageError> {
+            dispatch!(self, inner => KeyListing::keys_with_prefix(inner, prefix).await)
+        }
+    }
+
+    #[cfg(feature = "redis-admin")]
+    #[async_trait]
+    impl crate::asynchronous::AdminStorage for Backend {
+        async fn clear(&self) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => crate::asynchronous::AdminStorage::clear(inner).await)
+        }
+
+        async fn len(&self) -> Result<usize, errors::StorageError> {
+            dispatch!(self, inner => crate::asynchronous::AdminStorage::len(inner).await)
+        }
+    }
+
+    #[async_trait]
+    impl HealthCheck for Backend {
+        async fn ping(&self) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => HealthCheck::ping(inner).await)
+        }
+    }
+
+    #[async_trait]
+    impl RateLimiter for Backend {
+        async fn check_rate(
+            &self,
+            key: impl AsRef<str> + Send,
+            limit: i64,
+            window_secs: u64,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => RateLimiter::check_rate(inner, key, limit, window_secs).await)
+        }
+    }
+
+    #[async_trait]
+    impl StringAppend for Backend {
+        async fn append(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: String,
+        ) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => StringAppend::append(inner, key, value).await)
+        }
+    }
+
+    #[async_trait]
+    impl StringRange for Backend {
+        async fn get_range(
+            &self,
+            key: impl AsRef<str> + Send,
+            start: usize,
+            end: usize,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => StringRange::get_range(inner, key, start, end).await)
+        }
+    }
+
+    #[async_trait]
+    impl ListStorage for Backend {
+        async fn push_back(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: String,
+        ) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::push_back(inner, key, value).await)
+        }
+
+        async fn push_front(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: String,
+        ) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::push_front(inner, key, value).await)
+        }
+
+        async fn pop_back(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::pop_back(inner, key).await)
+        }
+
+        async fn pop_front(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::pop_front(inner, key).await)
+        }
+
+        async fn list_len(&self, key: impl AsRef<str> + Send) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::list_len(inner, key).await)
+        }
+
+        async fn list_range(
+            &self,
+            key: impl AsRef<str> + Send,
+            start: i64,
+            stop: i64,
+        ) -> Result<Vec<String>, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::list_range(inner, key, start, stop).await)
+        }
+    }
+
+    #[async_trait]
+    impl SetStorage for Backend {
+        async fn set_add(
+            &self,
+            key: impl AsRef<str> + Send,
+            member: String,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => SetStorage::set_add(inner, key, member).await)
+        }
+
+        async fn set_remove(
+            &self,
+            key: impl AsRef<str> + Send,
+            member: String,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => SetStorage::set_remove(inner, key, member).await)
+        }
+
+        async fn set_contains(
+            &self,
+            key: impl AsRef<str> + Send,
+            member: String,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => SetStorage::set_contains(inner, key, member).await)
+        }
+
+        async fn set_members(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Vec<String>, errors::StorageError> {
+            dispatch!(self, inner => SetStorage::set_members(inner, key).await)
+        }
+
+        async fn set_len(&self, key: impl AsRef<str> + Send) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => SetStorage::set_len(inner, key).await)
+        }
+    }
+
+    #[async_trait]
+    impl HashStorage for Backend {
+        async fn hash_set(
+            &self,
+            key: impl AsRef<str> + Send,
+            field: String,
+            value: String,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => HashStorage::hash_set(inner, key, field, value).await)
+        }
+
+        async fn hash_get(
+            &self,
+            key: impl AsRef<str> + Send,
+            field: String,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => HashStorage::hash_get(inner, key, field).await)
+        }
+
+        async fn hash_get_all(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<std::collections::HashMap<String, String>, errors::StorageError> {
+            dispatch!(self, inner => HashStorage::hash_get_all(inner, key).await)
+        }
+
+        async fn hash_delete(
+            &self,
+            key: impl AsRef<str> + Send,
+            field: String,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => HashStorage::hash_delete(inner, key, field).await)
+        }
+
+        async fn hash_len(&self, key: impl AsRef<str> + Send) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => HashStorage::hash_len(inner, key).await)
+        }
+    }
+
+    #[async_trait]
+    impl PubSub for Backend {
+        async fn publish(
+            &self,
+            channel: String,
+            message: String,
+        ) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => PubSub::publish(inner, channel, message).await)
+        }
+
+        async fn subscribe(&self, channel: String) -> Result<MessageStream, errors::StorageError> {
+            dispatch!(self, inner => PubSub::subscribe(inner, channel).await)
+        }
+    }
+
+    #[async_trait]
+    impl BulkDelete for Backend {
+        async fn delete_prefix(&self, prefix: String) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => BulkDelete::delete_prefix(inner, prefix).await)
+        }
+    }
+
+    #[async_trait]
+    impl KeyRename for Backend {
+        async fn rename(
+            &self,
+            from: impl AsRef<str> + Send,
+            to: impl AsRef<str> + Send,
+        ) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => KeyRename::rename(inner, from, to).await)
+        }
+    }
+
+    #[async_trait]
+    impl BatchRawStorage for Backend {
+        async fn load_raw_many(
+            &self,
+            keys: Vec<String>,
+        ) -> Result<Vec<Option<Vec<u8>>>, errors::StorageError> {
+            dispatch!(self, inner => BatchRawStorage::load_raw_many(inner, keys).await)
+        }
+    }
+
+    #[async_trait]
+    impl StringGetSet for Backend {
+        async fn get_set(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: String,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => StringGetSet::get_set(inner, key, value).await)
+        }
+    }
+
+    #[async_trait]
+    impl BoundedIncrement for Backend {
+        async fn atomic_increment_bounded(
+            &self,
+            key: impl AsRef<str> + Send,
+            delta: i64,
+            min: i64,
+            max: i64,
+        ) -> Result<i64, errors::StorageError> {
+            dispatch!(self, inner => BoundedIncrement::atomic_increment_bounded(inner, key, delta, min, max).await)
+        }
+    }
+}
+
+#[cfg(all(feature = "sync", feature = "async"))]
+mod sync_impl {
+    use super::Backend;
+    use crate::{
+        errors,
+        sync::{
+            AtomicStorage, BatchRawStorage, BatchStringStorage, BoundedIncrement, BulkDelete,
+            ConditionalStringStorage, FloatAtomicStorage, HashStorage, HealthCheck, KeyExists,
+            KeyListing, KeyRename, ListStorage, RateLimiter, RawStorage, RawStorageWithExpiry,
+            SetStorage, StringAppend, StringExpiry, StringGetSet, StringRange, StringStorage,
+            StringStorageWithExpiry, VersionedStorage,
+        },
+        types,
+    };
+
+    impl StringStorage for Backend {
+        fn load_string(
+            &self,
+            key: impl AsRef<str>,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => StringStorage::load_string(inner, key))
+        }
+
+        fn delete_string(&self, key: impl AsRef<str>) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => StringStorage::delete_string(inner, key))
+        }
+
+        fn take_string(
+            &self,
+            key: impl AsRef<str>,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => StringStorage::take_string(inner, key))
+        }
+    }
+
+    impl StringStorageWithExpiry for Backend {
+        fn store_with_expiry(
+            &self,
+            key: impl AsRef<str>,
+            value: String,
+            expiry: Option<u64>,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => StringStorageWithExpiry::store_with_expiry(inner, key, value, expiry))
+        }
+
+        fn store_keep_ttl(
+            &self,
+            key: impl AsRef<str>,
+            value: String,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => StringStorageWithExpiry::store_keep_ttl(inner, key, value))
+        }
+    }
+
+    impl RawStorage for Backend {
+        fn load_raw(&self, key: impl AsRef<str>) -> Result<Option<Vec<u8>>, errors::StorageError> {
+            dispatch!(self, inner => RawStorage::load_raw(inner, key))
+        }
+
+        fn delete_raw(&self, key: impl AsRef<str>) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => RawStorage::delete_raw(inner, key))
+        }
+    }
+
+    impl RawStorageWithExpiry for Backend {
+        fn store_raw_with_expiry(
+            &self,
+            key: impl AsRef<str>,
+            value: Vec<u8>,
+            expiry: Option<u64>,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => RawStorageWithExpiry::store_raw_with_expiry(inner, key, value, expiry))
+        }
+
+        fn store_raw_keep_ttl(
+            &self,
+            key: impl AsRef<str>,
+            value: Vec<u8>,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => RawStorageWithExpiry::store_raw_keep_ttl(inner, key, value))
+        }
+    }
+
+    impl AtomicStorage for Backend {
+        fn atomic_store(
+            &self,
+            key: impl AsRef<str>,
+            value: i64,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_store(inner, key, value))
+        }
+
+        fn atomic_load(&self, key: impl AsRef<str>) -> Result<Option<i64>, errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_load(inner, key))
+        }
+
+        fn atomic_delete(&self, key: impl AsRef<str>) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_delete(inner, key))
+        }
+
+        fn atomic_increment(
+            &self,
+            key: impl AsRef<str>,
+            value: i64,
+        ) -> Result<Option<i64>, errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_increment(inner, key, value))
+        }
+
+        fn atomic_increment_or_init(
+            &self,
+            key: impl AsRef<str>,
+            delta: i64,
+            init: i64,
+        ) -> Result<i64, errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_increment_or_init(inner, key, delta, init))
+        }
+
+        fn atomic_compare_and_swap(
+            &self,
+            key: impl AsRef<str>,
+            expected: i64,
+            new: i64,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => AtomicStorage::atomic_compare_and_swap(inner, key, expected, new))
+        }
+    }
+
+    impl VersionedStorage for Backend {
+        fn load_versioned(
+            &self,
+            key: impl AsRef<str>,
+        ) -> Result<Option<(u64, String)>, errors::StorageError> {
+            dispatch!(self, inner => VersionedStorage::load_versioned(inner, key))
+        }
+
+        fn compare_and_set_versioned(
+            &self,
+            key: impl AsRef<str>,
+            expected_version: u64,
+            new_value: String,
+        ) -> Result<Option<u64>, errors::StorageError> {
+            dispatch!(self, inner => VersionedStorage::compare_and_set_versioned(inner, key, expected_version, new_value))
+        }
+    }
+
+    impl KeyExists for Backend {
+        fn exists(&self, key: impl AsRef<str>) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => KeyExists::exists(inner, key))
+        }
+
+        fn exists_many(&self, keys: Vec<String>) -> Result<Vec<bool>, errors::StorageError> {
+            dispatch!(self, inner => KeyExists::exists_many(inner, keys))
+        }
+    }
+
+    impl StringExpiry for Backend {
+        fn ttl(&self, key: impl AsRef<str>) -> Result<Option<u64>, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::ttl(inner, key))
+        }
+
+        fn expire(&self, key: impl AsRef<str>, expiry: u64) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::expire(inner, key, expiry))
+        }
+
+        fn expire_if_longer(
+            &self,
+            key: impl AsRef<str>,
+            expiry: u64,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::expire_if_longer(inner, key, expiry))
+        }
+
+        fn persist(&self, key: impl AsRef<str>) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::persist(inner, key))
+        }
+
+        fn store_with_expiry_at(
+            &self,
+            key: impl AsRef<str>,
+            value: String,
+            at_unix_secs: u64,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::store_with_expiry_at(inner, key, value, at_unix_secs))
+        }
+
+        fn store_with_expiry_ms(
+            &self,
+            key: impl AsRef<str>,
+            value: String,
+            expiry_ms: Option<u64>,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => StringExpiry::store_with_expiry_ms(inner, key, value, expiry_ms))
+        }
+    }
+
+    impl ConditionalStringStorage for Backend {
+        fn store_if_absent(
+            &self,
+            key: impl AsRef<str>,
+            value: String,
+            expiry: Option<u64>,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => ConditionalStringStorage::store_if_absent(inner, key, value, expiry))
+        }
+
+        fn compare_and_swap(
+            &self,
+            key: impl AsRef<str>,
+            expected: Option<String>,
+            new: String,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => ConditionalStringStorage::compare_and_swap(inner, key, expected, new))
+        }
+    }
+
+    impl FloatAtomicStorage for Backend {
+        fn atomic_store_f64(
+            &self,
+            key: impl AsRef<str>,
+            value: f64,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => FloatAtomicStorage::atomic_store_f64(inner, key, value))
+        }
+
+        fn atomic_load_f64(
+            &self,
+            key: impl AsRef<str>,
+        ) -> Result<Option<f64>, errors::StorageError> {
+            dispatch!(self, inner => FloatAtomicStorage::atomic_load_f64(inner, key))
+        }
+
+        fn atomic_increment_f64(
+            &self,
+            key: impl AsRef<str>,
+            value: f64,
+        ) -> Result<f64, errors::StorageError> {
+            dispatch!(self, inner => FloatAtomicStorage::atomic_increment_f64(inner, key, value))
+        }
+    }
+
+    impl BatchStringStorage for Backend {
+        fn store_many(
+            &self,
+            entries: Vec<(String, String)>,
+            expiry: Option<u64>,
+        ) -> Result<Vec<types::StoreState>, errors::StorageError> {
+            dispatch!(self, inner => BatchStringStorage::store_many(inner, entries, expiry))
+        }
+
+        fn load_many(
+            &self,
+            keys: Vec<String>,
+        ) -> Result<Vec<Option<String>>, errors::StorageError> {
+            dispatch!(self, inner => BatchStringStorage::load_many(inner, keys))
+        }
+    }
+
+    impl KeyListing for Backend {
+        fn keys_with_prefix(&self, prefix: String) -> Result<Vec<String>, errors::StorageError> {
+            dispatch!(self, inner => KeyListing::keys_with_prefix(inner, prefix))
+        }
+    }
+
+    #[cfg(feature = "redis-admin")]
+    impl crate::sync::AdminStorage for Backend {
+        fn clear(&self) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => crate::sync::AdminStorage::clear(inner))
+        }
+
+        fn len(&self) -> Result<usize, errors::StorageError> {
+            dispatch!(self, inner => crate::sync::AdminStorage::len(inner))
+        }
+    }
+
+    impl HealthCheck for Backend {
+        fn ping(&self) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => HealthCheck::ping(inner))
+        }
+    }
+
+    impl RateLimiter for Backend {
+        fn check_rate(
+            &self,
+            key: impl AsRef<str>,
+            limit: i64,
+            window_secs: u64,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => RateLimiter::check_rate(inner, key, limit, window_secs))
+        }
+    }
+
+    impl StringAppend for Backend {
+        fn append(&self, key: impl AsRef<str>, value: String) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => StringAppend::append(inner, key, value))
+        }
+    }
+
+    impl StringRange for Backend {
+        fn get_range(
+            &self,
+            key: impl AsRef<str>,
+            start: usize,
+            end: usize,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => StringRange::get_range(inner, key, start, end))
+        }
+    }
+
+    impl ListStorage for Backend {
+        fn push_back(
+            &self,
+            key: impl AsRef<str>,
+            value: String,
+        ) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::push_back(inner, key, value))
+        }
+
+        fn push_front(
+            &self,
+            key: impl AsRef<str>,
+            value: String,
+        ) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::push_front(inner, key, value))
+        }
+
+        fn pop_back(&self, key: impl AsRef<str>) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::pop_back(inner, key))
+        }
+
+        fn pop_front(&self, key: impl AsRef<str>) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::pop_front(inner, key))
+        }
+
+        fn list_len(&self, key: impl AsRef<str>) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::list_len(inner, key))
+        }
+
+        fn list_range(
+            &self,
+            key: impl AsRef<str>,
+            start: i64,
+            stop: i64,
+        ) -> Result<Vec<String>, errors::StorageError> {
+            dispatch!(self, inner => ListStorage::list_range(inner, key, start, stop))
+        }
+    }
+
+    impl SetStorage for Backend {
+        fn set_add(
+            &self,
+            key: impl AsRef<str>,
+            member: String,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => SetStorage::set_add(inner, key, member))
+        }
+
+        fn set_remove(
+            &self,
+            key: impl AsRef<str>,
+            member: String,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => SetStorage::set_remove(inner, key, member))
+        }
+
+        fn set_contains(
+            &self,
+            key: impl AsRef<str>,
+            member: String,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => SetStorage::set_contains(inner, key, member))
+        }
+
+        fn set_members(&self, key: impl AsRef<str>) -> Result<Vec<String>, errors::StorageError> {
+            dispatch!(self, inner => SetStorage::set_members(inner, key))
+        }
+
+        fn set_len(&self, key: impl AsRef<str>) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => SetStorage::set_len(inner, key))
+        }
+    }
+
+    impl HashStorage for Backend {
+        fn hash_set(
+            &self,
+            key: impl AsRef<str>,
+            field: String,
+            value: String,
+        ) -> Result<types::StoreState, errors::StorageError> {
+            dispatch!(self, inner => HashStorage::hash_set(inner, key, field, value))
+        }
+
+        fn hash_get(
+            &self,
+            key: impl AsRef<str>,
+            field: String,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => HashStorage::hash_get(inner, key, field))
+        }
+
+        fn hash_get_all(
+            &self,
+            key: impl AsRef<str>,
+        ) -> Result<std::collections::HashMap<String, String>, errors::StorageError> {
+            dispatch!(self, inner => HashStorage::hash_get_all(inner, key))
+        }
+
+        fn hash_delete(
+            &self,
+            key: impl AsRef<str>,
+            field: String,
+        ) -> Result<bool, errors::StorageError> {
+            dispatch!(self, inner => HashStorage::hash_delete(inner, key, field))
+        }
+
+        fn hash_len(&self, key: impl AsRef<str>) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => HashStorage::hash_len(inner, key))
+        }
+    }
+
+    impl BulkDelete for Backend {
+        fn delete_prefix(&self, prefix: String) -> Result<u64, errors::StorageError> {
+            dispatch!(self, inner => BulkDelete::delete_prefix(inner, prefix))
+        }
+    }
+
+    impl KeyRename for Backend {
+        fn rename(
+            &self,
+            from: impl AsRef<str>,
+            to: impl AsRef<str>,
+        ) -> Result<(), errors::StorageError> {
+            dispatch!(self, inner => KeyRename::rename(inner, from, to))
+        }
+    }
+
+    impl BatchRawStorage for Backend {
+        fn load_raw_many(
+            &self,
+            keys: Vec<String>,
+        ) -> Result<Vec<Option<Vec<u8>>>, errors::StorageError> {
+            dispatch!(self, inner => BatchRawStorage::load_raw_many(inner, keys))
+        }
+    }
+
+    impl StringGetSet for Backend {
+        fn get_set(
+            &self,
+            key: impl AsRef<str>,
+            value: String,
+        ) -> Result<Option<String>, errors::StorageError> {
+            dispatch!(self, inner => StringGetSet::get_set(inner, key, value))
+        }
+    }
+
+    impl BoundedIncrement for Backend {
+        fn atomic_increment_bounded(
+            &self,
+            key: impl AsRef<str>,
+            delta: i64,
+            min: i64,
+            max: i64,
+        ) -> Result<i64, errors::StorageError> {
+            dispatch!(self, inner => BoundedIncrement::atomic_increment_bounded(inner, key, delta, min, max))
+        }
+    }
+}
@@ -4,12 +4,24 @@
 //! as the underlying concurrent hash map. It supports storing string values with
 //! optional expiration times and implements the [`StringStorage`] and
 //! [`StringStorageWithExpiry`] traits.
+//!
+//! Operations on a single key run directly on the calling task: they only
+//! hold a DashMap shard lock briefly and finish in constant time, so moving
+//! them to another thread would cost more than it saves. Operations that visit
+//! every entry of a store (key listings and clearing) run on Tokio's blocking
+//! thread pool via `spawn_blocking`, so large caches don't stall the executor.
 
-use core::sync::atomic::AtomicI64;
+use core::sync::atomic::{AtomicI64, AtomicU64};
 
-use super::{now, IMCModule};
+use super::{
+    bounded_increment, checked_increment, is_expired, millis_to_secs, secs_to_millis, IMCModule,
+};
 use crate::asynchronous::{
-    AtomicStorage, RawStorage, RawStorageWithExpiry, StringStorage, StringStorageWithExpiry,
+    AdminStorage, AtomicStorage, BatchRawStorage, BatchStringStorage, BoundedIncrement, BulkDelete,
+    ConditionalStringStorage, FloatAtomicStorage, HashStorage, HealthCheck, KeyExists, KeyListing,
+    KeyRename, ListStorage, MessageStream, PubSub, RateLimiter, RawExport, RawStorage,
+    RawStorageWithExpiry, SetStorage, StringAppend, StringExpiry, StringGetSet, StringRange,
+    StringStorage, StringStorageWithExpiry, VersionedStorage,
 };
 use async_trait::async_trait;
 
@@ -28,25 +40,48 @@ impl StringStorageWithExpiry for IMCModule {
     /// # Returns
     /// * `Ok(StoreState::New)` - If the key did not exist
     /// * `Ok(StoreState::Updated)` - If the key existed and was updated
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
     async fn store_with_expiry(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: String,
         expiry: Option<u64>,
     ) -> Result<crate::types::StoreState, crate::errors::StorageError> {
-        // Using tokio::task::spawn_blocking for potentially lengthy operations
-        let self = self.clone();
-        tokio::task::spawn_blocking(move || {
-            let current_time = expiry.map(|e| now() + e);
-            let output = self.string_store.insert(key, (value, current_time));
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "store_with_expiry");
+        let key = self.key(key)?;
+        let expires_at = self.expires_at(expiry)?;
+        Ok(self.string_store_until(key, value, expires_at))
+    }
 
-            match output {
-                None => Ok(crate::types::StoreState::New),
-                Some(_) => Ok(crate::types::StoreState::Updated),
-            }
-        })
-        .await
-        .unwrap_or_else(|e| Err(crate::errors::StorageError::JoinError(e)))
+    /// Stores a string value while preserving the key's existing expiration.
+    ///
+    /// If the key doesn't exist or has expired, the value is stored without
+    /// expiration.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The string value to store
+    ///
+    /// # Returns
+    /// * `Ok(StoreState::New)` - If the key did not exist
+    /// * `Ok(StoreState::Updated)` - If the key existed and was updated
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
+    async fn store_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<crate::types::StoreState, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "store_keep_ttl");
+        let key = self.key(key)?;
+        self.string_keep_ttl(key, value)
     }
 }
 
@@ -60,33 +95,27 @@ impl StringStorage for IMCModule {
     /// * `key` - The key whose value should be loaded
     ///
     /// # Returns
-    /// * `Ok(Some(String))` - If the key exists and hasn't expired
+    /// * `Ok(Some(String)` - If the key exists and hasn't expired
     /// * `Ok(None)` - If the key doesn't exist or has expired
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
     async fn load_string(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
     ) -> Result<Option<String>, crate::errors::StorageError> {
-        let self = self.clone();
-        // Using tokio::task::spawn_blocking since DashMap operations might be CPU-intensive
-        tokio::task::spawn_blocking(move || {
-            match self.string_store.get(&key) {
-                Some(value) => {
-                    let (inner_value, expiry) = value.value();
-                    match expiry {
-                        Some(expiry) if expiry < &now() => {
-                            // Note: This is now potentially problematic as it's a recursive async call
-                            // We should handle this differently in a real implementation
-                            self.string_store.remove(&key);
-                            Ok(None)
-                        }
-                        _ => Ok(Some(inner_value.clone())),
-                    }
-                }
-                None => Ok(None), // Changed from todo!() to returning None
-            }
-        })
-        .await
-        .unwrap_or_else(|e| Err(crate::errors::StorageError::JoinError(e)))
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "load_string");
+        let key = self.key(key)?;
+        let output = self.load_live_string(&key, self.now_millis()?);
+        let output = output.map(|value| value.to_string());
+
+        self.record_load(output.is_some());
+        if output.is_some() {
+            self.touch_string(&key);
+        }
+        Ok(output)
     }
 
     /// Deletes a string value.
@@ -99,14 +128,44 @@ impl StringStorage for IMCModule {
     ///
     /// # Returns
     /// * `Ok(())` - The operation was successful (whether or not the key existed)
-    async fn delete_string(&self, key: String) -> Result<(), crate::errors::StorageError> {
-        let self = self.clone();
-        tokio::task::spawn_blocking(move || {
-            self.string_store.remove(&key);
-            Ok(())
-        })
-        .await
-        .unwrap_or_else(|e| Err(crate::errors::StorageError::JoinError(e)))
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
+    async fn delete_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<(), crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "delete_string");
+        let key = self.key(key)?;
+        self.string_delete(&key);
+        Ok(())
+    }
+
+    /// Loads and deletes a string value in a single step.
+    ///
+    /// The value is removed atomically, so concurrent callers cannot both
+    /// receive it. An expired value is removed but not returned.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be taken
+    ///
+    /// # Returns
+    /// * `Ok(Some(String))` - If the key existed and hadn't expired
+    /// * `Ok(None)` - If the key doesn't exist or had expired
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
+    async fn take_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "take_string");
+        let key = self.key(key)?;
+        self.string_take(&key)
     }
 }
 
@@ -127,19 +186,48 @@ impl RawStorageWithExpiry for IMCModule {
     /// * `Ok(StoreState::Updated)` - If the key existed and was updated
     /// * `Err(StorageError)` - If an error occurred during storage
     ///
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
     async fn store_raw_with_expiry(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: Vec<u8>,
         expiry: Option<u64>,
     ) -> Result<crate::types::StoreState, crate::errors::StorageError> {
-        let current_time = expiry.map(|e| now() + e);
-        let output = self.data_store.insert(key, (value, current_time));
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "store_raw_with_expiry");
+        let key = self.key(key)?;
+        let expires_at = self.expires_at(expiry)?;
+        Ok(self.raw_store_until(key, value, expires_at))
+    }
 
-        match output {
-            None => Ok(crate::types::StoreState::New),
-            Some(_) => Ok(crate::types::StoreState::Updated),
-        }
+    /// Stores a binary value while preserving the key's existing expiration.
+    ///
+    /// If the key doesn't exist or has expired, the value is stored without
+    /// expiration.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The binary value to store
+    ///
+    /// # Returns
+    /// * `Ok(StoreState::New)` - If the key did not exist
+    /// * `Ok(StoreState::Updated)` - If the key existed and was updated
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
+    async fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+    ) -> Result<crate::types::StoreState, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "store_raw_keep_ttl");
+        let key = self.key(key)?;
+        self.raw_keep_ttl(key, value)
     }
 }
 
@@ -153,21 +241,25 @@ impl RawStorage for IMCModule {
     /// * `key` - The key whose value should be loaded
     ///
     /// # Returns
-    /// * `Ok(Some(Vec<u8>))` - If the key exists and hasn't expired
-    async fn load_raw(&self, key: String) -> Result<Option<Vec<u8>>, crate::errors::StorageError> {
-        match self.data_store.get(&key) {
-            Some(value) => {
-                let (inner_value, expiry) = value.value();
-                match expiry {
-                    Some(expiry) if expiry < &now() => {
-                        self.delete_raw(key.clone()).await?;
-                        Ok(None)
-                    }
-                    _ => Ok(Some(inner_value.clone())),
-                }
-            }
-            None => Ok(None),
+    /// * `Ok(Some(Vec<u8>)` - If the key exists and hasn't expired
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
+    async fn load_raw(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<Vec<u8>>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "load_raw");
+        let key = self.key(key)?;
+        let output = self.load_live_raw(&key, self.now_millis()?);
+
+        self.record_load(output.is_some());
+        if output.is_some() {
+            self.touch_raw(&key);
         }
+        Ok(output)
     }
 
     /// Deletes a binary value.
@@ -183,8 +275,18 @@ impl RawStorage for IMCModule {
     /// * `Err(StorageError)` - If an error occurred during deletion
     /// * `Err(JoinError)` - If an error occurred while joining the async task
     ///
-    async fn delete_raw(&self, key: String) -> Result<(), crate::errors::StorageError> {
-        self.data_store.remove(&key);
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
+    async fn delete_raw(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<(), crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "delete_raw");
+        let key = self.key(key)?;
+        self.raw_delete(&key);
         Ok(())
     }
 }
@@ -201,11 +303,18 @@ impl AtomicStorage for IMCModule {
     /// # Returns
     /// * `Ok(StoreState::New)` - If the key did not exist
     /// * `Ok(StoreState::Updated)` - If the key existed and was updated
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
     async fn atomic_store(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: i64,
     ) -> Result<crate::types::StoreState, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "atomic_store");
+        let key = self.key(key)?;
         let output = self.atomic_store.insert(key, AtomicI64::new(value));
 
         match output {
@@ -223,7 +332,17 @@ impl AtomicStorage for IMCModule {
     /// * `Ok(Some(i64))` - If the key exists
     /// * `Ok(None)` - If the key doesn't exist
     /// * `Err(StorageError)` - If an error occurred during loading
-    async fn atomic_load(&self, key: String) -> Result<Option<i64>, crate::errors::StorageError> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
+    async fn atomic_load(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<i64>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "atomic_load");
+        let key = self.key(key)?;
         match self.atomic_store.get(&key) {
             Some(value) => Ok(Some(value.load(std::sync::atomic::Ordering::SeqCst))),
             None => Ok(None),
@@ -242,15 +361,25 @@ impl AtomicStorage for IMCModule {
     /// * `Ok(())` - The operation was successful (whether or not the key existed)
     /// * `Err(StorageError)` - If an error occurred during deletion
     ///
-    async fn atomic_delete(&self, key: String) -> Result<(), crate::errors::StorageError> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
+    async fn atomic_delete(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<(), crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "atomic_delete");
+        let key = self.key(key)?;
         self.atomic_store.remove(&key);
         Ok(())
     }
 
     /// Increments an atomic integer value.
     ///
-    /// If the key already exists, the value is updated.
-    /// The function returns the previous value before the increment.
+    /// A missing key is treated as `0` and created by the increment.
+    /// The function returns the new value after the increment.
     ///
     /// # Arguments
     ///
@@ -259,20 +388,1248 @@ impl AtomicStorage for IMCModule {
     ///
     /// # Returns
     ///
-    /// * `Ok(Some(i64))` - The previous value before the increment
-    /// * `Ok(None)` - If the key doesn't exist
+    /// * `Ok(Some(i64))` - The new value after the increment
+    /// * `Err(StorageError::Overflow)` - If the result is outside the `i64` range
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
     async fn atomic_increment(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: i64,
     ) -> Result<Option<i64>, crate::errors::StorageError> {
-        let output = self.atomic_store.get(&key);
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "atomic_increment");
+        let key = self.key(key)?;
+        let atomic = self.atomic_store.entry(key).or_insert(AtomicI64::new(0));
 
-        match output {
-            Some(atomic) => Ok(Some(
-                atomic.fetch_add(value, std::sync::atomic::Ordering::SeqCst),
-            )),
+        checked_increment(&atomic, value).map(Some)
+    }
+
+    /// Increments an atomic integer value, creating it if it doesn't exist.
+    ///
+    /// A missing key is initialized to `init` before `delta` is applied.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be incremented
+    /// * `delta` - The integer value to increment by
+    /// * `init` - The value to initialize a missing key with
+    ///
+    /// # Returns
+    /// * `Ok(i64)` - The new value after the increment
+    /// * `Err(StorageError::Overflow)` - If the result is outside the `i64` range
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
+    async fn atomic_increment_or_init(
+        &self,
+        key: impl AsRef<str> + Send,
+        delta: i64,
+        init: i64,
+    ) -> Result<i64, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "atomic_increment_or_init");
+        let key = self.key(key)?;
+        let atomic = self
+            .atomic_store
+            .entry(key)
+            .or_insert_with(|| AtomicI64::new(init));
+
+        checked_increment(&atomic, delta)
+    }
+
+    /// Replaces an atomic integer value if it currently equals `expected`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key whose value should be swapped
+    /// * `expected` - The value the key must currently hold
+    /// * `new` - The integer value to store
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - If the value matched and was replaced
+    /// * `Ok(false)` - If the value didn't match or the key doesn't exist
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(backend = "imc", key = %key.as_ref()))
+    )]
+    async fn atomic_compare_and_swap(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected: i64,
+        new: i64,
+    ) -> Result<bool, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "atomic_compare_and_swap");
+        let key = self.key(key)?;
+
+        match self.atomic_store.get(&key) {
+            Some(atomic) => Ok(atomic
+                .compare_exchange(
+                    expected,
+                    new,
+                    std::sync::atomic::Ordering::SeqCst,
+                    std::sync::atomic::Ordering::SeqCst,
+                )
+                .is_ok()),
+            None => Ok(false),
+        }
+    }
+}
+
+#[async_trait]
+impl RawExport for IMCModule {
+    /// Lists the keys of all binary values that haven't expired.
+    ///
+    /// # Arguments
+    /// * `prefix` - Optional prefix that returned keys must start with
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - The matching keys, in no particular order
+    async fn scan_raw_keys(
+        &self,
+        prefix: Option<String>,
+    ) -> Result<Vec<String>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "scan_raw_keys");
+        let current_time = self.now_millis()?;
+        let self = self.clone();
+        tokio::task::spawn_blocking(move || {
+            Ok(self
+                .data_store
+                .iter()
+                .filter(|entry| match &prefix {
+                    Some(prefix) => entry.key().starts_with(prefix),
+                    None => true,
+                })
+                .filter(|entry| match entry.value().1 {
+                    Some(expiry) => !is_expired(expiry, current_time),
+                    None => true,
+                })
+                .map(|entry| entry.key().clone())
+                .collect())
+        })
+        .await
+        .unwrap_or_else(|e| Err(crate::errors::StorageError::JoinError(e)))
+    }
+
+    /// Exports a binary value and its remaining time-to-live.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be exported
+    ///
+    /// # Returns
+    /// * `Ok(Some((Vec<u8>, Option<u64>)))` - The value and remaining seconds until expiry
+    /// * `Ok(None)` - If the key doesn't exist or has expired
+    async fn export_raw(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<(Vec<u8>, Option<u64>)>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "export_raw");
+        let key = self.key(key)?;
+        let current_time = self.now_millis()?;
+
+        match self.data_store.get(&key) {
+            Some(value) => {
+                let (inner_value, expiry) = value.value();
+                match expiry {
+                    Some(expiry) if is_expired(*expiry, current_time) => Ok(None),
+                    _ => Ok(Some((
+                        inner_value.clone(),
+                        expiry.map(|e| millis_to_secs(e - current_time)),
+                    ))),
+                }
+            }
             None => Ok(None),
         }
     }
 }
+
+#[async_trait]
+impl VersionedStorage for IMCModule {
+    /// Loads a versioned record if it exists.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose record should be loaded
+    ///
+    /// # Returns
+    /// * `Ok(Some((u64, String)))` - The current version and value
+    /// * `Ok(None)` - If the record doesn't exist
+    async fn load_versioned(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<(u64, String)>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "load_versioned");
+        let key = self.key(key)?;
+        Ok(self
+            .versioned_store
+            .get(&key)
+            .map(|record| record.value().clone()))
+    }
+
+    /// Replaces a record's value if its version matches, bumping the version.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose record should be updated
+    /// * `expected_version` - The version the record must currently be at
+    /// * `new_value` - The value to store
+    ///
+    /// # Returns
+    /// * `Ok(Some(u64))` - The new version if the update was applied
+    /// * `Ok(None)` - If the current version didn't match
+    async fn compare_and_set_versioned(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected_version: u64,
+        new_value: String,
+    ) -> Result<Option<u64>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "compare_and_set_versioned");
+        let key = self.key(key)?;
+        Ok(self.compare_and_set_versioned_entry(key, expected_version, new_value))
+    }
+}
+
+#[async_trait]
+impl KeyExists for IMCModule {
+    /// Checks whether a key exists in any of the stores.
+    ///
+    /// Uses the same expiry check as [`StringStorage::load_string`] and
+    /// [`RawStorage::load_raw`], evicting the entry if it has expired.
+    ///
+    /// # Arguments
+    /// * `key` - The key to check
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the key exists and hasn't expired
+    /// * `Ok(false)` - If the key doesn't exist or has expired
+    async fn exists(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<bool, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "exists");
+        let key = self.key(key)?;
+        Ok(self.key_exists(&key, self.now_millis()?))
+    }
+
+    /// Checks which of several keys exist in any of the stores.
+    ///
+    /// Every key is checked against the same current time, evicting expired
+    /// entries like [`KeyExists::exists`].
+    ///
+    /// # Arguments
+    /// * `keys` - The keys to check
+    ///
+    /// # Returns
+    /// * `Ok(Vec<bool>)` - Whether each key exists and hasn't expired, in the same order as `keys`
+    async fn exists_many(
+        &self,
+        keys: Vec<String>,
+    ) -> Result<Vec<bool>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "exists_many");
+        let keys = keys
+            .into_iter()
+            .map(|key| self.key(key))
+            .collect::<Result<Vec<_>, _>>()?;
+        let current_time = self.now_millis()?;
+        Ok(keys
+            .iter()
+            .map(|key| self.key_exists(key, current_time))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl StringExpiry for IMCModule {
+    /// Gets the remaining time-to-live of a string value.
+    ///
+    /// If the value has expired, it is automatically deleted and reported as missing.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose expiration should be inspected
+    ///
+    /// # Returns
+    /// * `Ok(Some(u64))` - The remaining seconds until the value expires
+    /// * `Ok(None)` - If the key has no expiration, doesn't exist or has expired
+    async fn ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<u64>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "ttl");
+        let key = self.key(key)?;
+        self.string_ttl(&key)
+    }
+
+    /// Sets the expiration of a string value without rewriting it.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose expiration should be set
+    /// * `expiry` - Expiration duration in seconds from now
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the key exists and its expiration was updated
+    /// * `Ok(false)` - If the key doesn't exist or has expired
+    async fn expire(
+        &self,
+        key: impl AsRef<str> + Send,
+        expiry: u64,
+    ) -> Result<bool, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "expire");
+        let key = self.key(key)?;
+        self.string_expire(&key, expiry)
+    }
+
+    /// Sets the expiration of a string value only if it extends it.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose expiration should be extended
+    /// * `expiry` - Expiration duration in seconds from now
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the new expiration is later than the current one and was set
+    /// * `Ok(false)` - If the key doesn't exist, has expired, has no expiration or
+    ///   already expires later
+    async fn expire_if_longer(
+        &self,
+        key: impl AsRef<str> + Send,
+        expiry: u64,
+    ) -> Result<bool, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "expire_if_longer");
+        let key = self.key(key)?;
+        self.string_expire_if_longer(&key, expiry)
+    }
+
+    /// Removes the expiration of a string value.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose expiration should be removed
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the key existed and its expiration was removed
+    /// * `Ok(false)` - If the key doesn't exist, has expired or had no expiration
+    async fn persist(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<bool, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "persist");
+        let key = self.key(key)?;
+        self.string_persist(&key)
+    }
+
+    /// Stores a string value that expires at an absolute point in time.
+    ///
+    /// The timestamp is stored without consulting the clock.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The string value to store
+    /// * `at_unix_secs` - The Unix timestamp in seconds at which the value expires
+    ///
+    /// # Returns
+    /// * `Ok(StoreState::New)` - If the key did not exist
+    /// * `Ok(StoreState::Updated)` - If the key existed and was updated
+    async fn store_with_expiry_at(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        at_unix_secs: u64,
+    ) -> Result<crate::types::StoreState, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "store_with_expiry_at");
+        let key = self.key(key)?;
+        Ok(self.string_store_until(key, value, Some(secs_to_millis(at_unix_secs))))
+    }
+
+    /// Stores a string value with an optional expiration in milliseconds.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The string value to store
+    /// * `expiry_ms` - Optional expiration duration in milliseconds from now
+    ///
+    /// # Returns
+    /// * `Ok(StoreState::New)` - If the key did not exist
+    /// * `Ok(StoreState::Updated)` - If the key existed and was updated
+    async fn store_with_expiry_ms(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry_ms: Option<u64>,
+    ) -> Result<crate::types::StoreState, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "store_with_expiry_ms");
+        let key = self.key(key)?;
+        let expires_at = self.expires_at_ms(expiry_ms)?;
+        Ok(self.string_store_until(key, value, expires_at))
+    }
+}
+
+#[async_trait]
+impl ConditionalStringStorage for IMCModule {
+    /// Stores a string value only if the key doesn't exist or has expired.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The string value to store
+    /// * `expiry` - Optional expiration duration in seconds from now
+    ///
+    /// # Returns
+    /// * `Ok(StoreState::New)` - If the value was written
+    /// * `Ok(StoreState::Unchanged)` - If a live value already exists for the key
+    async fn store_if_absent(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<crate::types::StoreState, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "store_if_absent");
+        let key = self.key(key)?;
+        self.string_store_if_absent(key, value, expiry)
+    }
+
+    /// Replaces a string value only if it currently matches `expected`.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be swapped
+    /// * `expected` - The value the key must currently hold, or `None` if it must be absent
+    /// * `new` - The string value to store
+    ///
+    /// # Returns
+    /// * `Ok(StoreState::Updated)` - If the value matched and was replaced
+    /// * `Ok(StoreState::New)` - If `expected` was `None` and the value was written
+    /// * `Ok(StoreState::Unchanged)` - If the current value didn't match
+    async fn compare_and_swap(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected: Option<String>,
+        new: String,
+    ) -> Result<crate::types::StoreState, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "compare_and_swap");
+        let key = self.key(key)?;
+        self.string_compare_and_swap(key, expected, new)
+    }
+}
+
+#[async_trait]
+impl FloatAtomicStorage for IMCModule {
+    /// Stores a floating-point value.
+    ///
+    /// If the key already exists, the value is updated.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The floating-point value to store
+    ///
+    /// # Returns
+    /// * `Ok(StoreState::New)` - If the key did not exist
+    /// * `Ok(StoreState::Updated)` - If the key existed and was updated
+    async fn atomic_store_f64(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: f64,
+    ) -> Result<crate::types::StoreState, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "atomic_store_f64");
+        let key = self.key(key)?;
+        let output = self
+            .float_store
+            .insert(key, AtomicU64::new(value.to_bits()));
+
+        match output {
+            None => Ok(crate::types::StoreState::New),
+            Some(_) => Ok(crate::types::StoreState::Updated),
+        }
+    }
+
+    /// Loads a floating-point value if it exists.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be loaded
+    ///
+    /// # Returns
+    /// * `Ok(Some(f64))` - If the key exists
+    /// * `Ok(None)` - If the key doesn't exist
+    async fn atomic_load_f64(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<f64>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "atomic_load_f64");
+        let key = self.key(key)?;
+        Ok(self
+            .float_store
+            .get(&key)
+            .map(|value| f64::from_bits(value.load(std::sync::atomic::Ordering::SeqCst))))
+    }
+
+    /// Increments a floating-point value.
+    ///
+    /// A missing key is treated as `0.0` and created by the increment.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be incremented
+    /// * `value` - The floating-point value to increment by
+    ///
+    /// # Returns
+    /// * `Ok(f64)` - The new value after the increment
+    async fn atomic_increment_f64(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: f64,
+    ) -> Result<f64, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "atomic_increment_f64");
+        let key = self.key(key)?;
+        Ok(self.float_increment(key, value))
+    }
+}
+
+#[async_trait]
+impl BatchStringStorage for IMCModule {
+    /// Stores multiple string values with an optional expiration time.
+    ///
+    /// Each entry is stored as by `store_with_expiry`, in order, so a key that
+    /// appears more than once ends up holding its last value.
+    ///
+    /// # Arguments
+    /// * `entries` - The (key, value) pairs to store
+    /// * `expiry` - Optional expiration duration in seconds from now
+    ///
+    /// # Returns
+    /// * `Ok(Vec<StoreState>)` - Whether each entry was new or updated, in input order
+    async fn store_many(
+        &self,
+        entries: Vec<(String, String)>,
+        expiry: Option<u64>,
+    ) -> Result<Vec<crate::types::StoreState>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "store_many");
+        let mut output = Vec::with_capacity(entries.len());
+        for (key, value) in entries {
+            output.push(self.store_with_expiry(key, value, expiry).await?);
+        }
+        Ok(output)
+    }
+
+    /// Loads multiple string values that exist and haven't expired.
+    ///
+    /// # Arguments
+    /// * `keys` - The keys whose values should be loaded
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Option<String>>)` - The value of each key, in input order
+    async fn load_many(
+        &self,
+        keys: Vec<String>,
+    ) -> Result<Vec<Option<String>>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "load_many");
+        let mut output = Vec::with_capacity(keys.len());
+        for key in keys {
+            output.push(self.load_string(key).await?);
+        }
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl KeyListing for IMCModule {
+    /// Lists the keys of all string values that haven't expired.
+    ///
+    /// # Arguments
+    /// * `prefix` - The prefix that returned keys must start with
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - The matching keys, in no particular order
+    async fn keys_with_prefix(
+        &self,
+        prefix: String,
+    ) -> Result<Vec<String>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "keys_with_prefix");
+        let self = self.clone();
+        tokio::task::spawn_blocking(move || self.string_keys_with_prefix(&prefix))
+            .await
+            .unwrap_or_else(|e| Err(crate::errors::StorageError::JoinError(e)))
+    }
+}
+
+#[async_trait]
+impl AdminStorage for IMCModule {
+    /// Removes every entry from all of the module's stores.
+    ///
+    /// The hit, miss and eviction counters reported by `stats` are kept.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The stores were cleared
+    async fn clear(&self) -> Result<(), crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "clear");
+        let self = self.clone();
+        tokio::task::spawn_blocking(move || {
+            self.clear_all();
+            Ok(())
+        })
+        .await
+        .unwrap_or_else(|e| Err(crate::errors::StorageError::JoinError(e)))
+    }
+
+    /// Counts the string, binary and integer entries across the stores.
+    ///
+    /// Expired entries are only removed when accessed or swept, so until the
+    /// next sweep (see `IMCConfig::eviction_interval`) they are still counted.
+    /// A key present in several stores is counted once per store.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The number of entries held
+    async fn len(&self) -> Result<usize, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "len");
+        Ok(self.string_store.len() + self.data_store.len() + self.atomic_store.len())
+    }
+}
+
+#[async_trait]
+impl HealthCheck for IMCModule {
+    /// Checks that the cache is reachable.
+    ///
+    /// The cache lives in process memory, so this always succeeds.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Always
+    async fn ping(&self) -> Result<(), crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "ping");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RateLimiter for IMCModule {
+    /// Counts a request against a key's limit for the current window.
+    ///
+    /// # Arguments
+    /// * `key` - The key identifying the rate-limited subject
+    /// * `limit` - The number of requests allowed per window
+    /// * `window_secs` - The length of a window in seconds
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the request is within the limit
+    /// * `Ok(false)` - If the limit for the current window has been exceeded
+    async fn check_rate(
+        &self,
+        key: impl AsRef<str> + Send,
+        limit: i64,
+        window_secs: u64,
+    ) -> Result<bool, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "check_rate");
+        let key = self.key(key)?;
+        self.rate_check(key, limit, window_secs)
+    }
+}
+
+#[async_trait]
+impl StringAppend for IMCModule {
+    /// Appends to a string value, creating it if it doesn't exist.
+    ///
+    /// The concatenation happens under the entry's shard lock, so concurrent
+    /// appends are never lost. An existing expiration is kept; an expired value
+    /// is replaced as if the key didn't exist.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be appended to
+    /// * `value` - The string to append
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The length of the value after appending, in bytes
+    async fn append(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<u64, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "append");
+        let key = self.key(key)?;
+        self.string_append(key, value)
+    }
+}
+
+#[async_trait]
+impl StringRange for IMCModule {
+    /// Loads the bytes `start..end` of a string value if it hasn't expired.
+    ///
+    /// If the value has expired, it is automatically deleted and None is returned.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be read
+    /// * `start` - The offset of the first byte to return
+    /// * `end` - The offset one past the last byte to return
+    ///
+    /// # Returns
+    /// * `Ok(Some(String))` - The requested portion, clamped to the value's length
+    /// * `Ok(None)` - If the key doesn't exist or has expired
+    async fn get_range(
+        &self,
+        key: impl AsRef<str> + Send,
+        start: usize,
+        end: usize,
+    ) -> Result<Option<String>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "get_range");
+        let key = self.key(key)?;
+        self.string_range(&key, start, end)
+    }
+}
+
+#[async_trait]
+impl ListStorage for IMCModule {
+    /// Appends a value to the end of a list, creating it if missing.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    /// * `value` - The value to append
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The length of the list after the push
+    async fn push_back(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<u64, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "push_back");
+        let key = self.key(key)?;
+        Ok(self.list_push(key, value, false))
+    }
+
+    /// Prepends a value to the start of a list, creating it if missing.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    /// * `value` - The value to prepend
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The length of the list after the push
+    async fn push_front(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<u64, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "push_front");
+        let key = self.key(key)?;
+        Ok(self.list_push(key, value, true))
+    }
+
+    /// Removes and returns the last value of a list.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    ///
+    /// # Returns
+    /// * `Ok(Some(String))` - The removed value
+    /// * `Ok(None)` - If the list is empty or doesn't exist
+    async fn pop_back(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "pop_back");
+        let key = self.key(key)?;
+        Ok(self.list_pop(key, false))
+    }
+
+    /// Removes and returns the first value of a list.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    ///
+    /// # Returns
+    /// * `Ok(Some(String))` - The removed value
+    /// * `Ok(None)` - If the list is empty or doesn't exist
+    async fn pop_front(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "pop_front");
+        let key = self.key(key)?;
+        Ok(self.list_pop(key, true))
+    }
+
+    /// Gets the number of values in a list.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The length of the list, `0` if it doesn't exist
+    async fn list_len(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<u64, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "list_len");
+        let key = self.key(key)?;
+        Ok(self
+            .list_store
+            .get(&key)
+            .map_or(0, |list| list.len() as u64))
+    }
+
+    /// Gets the values of a list between two indices, inclusive.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the list
+    /// * `start` - The index of the first value, negative to count from the end
+    /// * `stop` - The index of the last value, negative to count from the end
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - The values in the range, in list order
+    async fn list_range(
+        &self,
+        key: impl AsRef<str> + Send,
+        start: i64,
+        stop: i64,
+    ) -> Result<Vec<String>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "list_range");
+        let key = self.key(key)?;
+        Ok(self.list_range(&key, start, stop))
+    }
+}
+
+#[async_trait]
+impl SetStorage for IMCModule {
+    /// Adds a member to a set, creating it if missing.
+    ///
+    /// The set is updated under its shard lock, so concurrent adds of the same
+    /// member report it as new exactly once.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the set
+    /// * `member` - The member to add
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the member was newly added
+    /// * `Ok(false)` - If the member was already present
+    async fn set_add(
+        &self,
+        key: impl AsRef<str> + Send,
+        member: String,
+    ) -> Result<bool, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "set_add");
+        let key = self.key(key)?;
+        Ok(self.set_store.entry(key).or_default().insert(member))
+    }
+
+    /// Removes a member from a set, removing the set once empty.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the set
+    /// * `member` - The member to remove
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the member was present
+    /// * `Ok(false)` - If the member or the set didn't exist
+    async fn set_remove(
+        &self,
+        key: impl AsRef<str> + Send,
+        member: String,
+    ) -> Result<bool, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "set_remove");
+        let key = self.key(key)?;
+        Ok(self.set_remove_member(key, &member))
+    }
+
+    /// Checks whether a set contains a member.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the set
+    /// * `member` - The member to look for
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Whether the member is present
+    async fn set_contains(
+        &self,
+        key: impl AsRef<str> + Send,
+        member: String,
+    ) -> Result<bool, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "set_contains");
+        let key = self.key(key)?;
+        Ok(self
+            .set_store
+            .get(&key)
+            .is_some_and(|set| set.contains(&member)))
+    }
+
+    /// Gets every member of a set.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the set
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - The members in no particular order, empty if the set doesn't exist
+    async fn set_members(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Vec<String>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "set_members");
+        let key = self.key(key)?;
+        Ok(self
+            .set_store
+            .get(&key)
+            .map(|set| set.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    /// Gets the number of members in a set.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the set
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The size of the set, `0` if it doesn't exist
+    async fn set_len(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<u64, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "set_len");
+        let key = self.key(key)?;
+        Ok(self.set_store.get(&key).map_or(0, |set| set.len() as u64))
+    }
+}
+
+#[async_trait]
+impl HashStorage for IMCModule {
+    /// Sets a field of a map, creating the map if missing.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the map
+    /// * `field` - The field to set
+    /// * `value` - The value to store in the field
+    ///
+    /// # Returns
+    /// * `Ok(StoreState::New)` - If the field did not exist
+    /// * `Ok(StoreState::Updated)` - If the field existed and was updated
+    async fn hash_set(
+        &self,
+        key: impl AsRef<str> + Send,
+        field: String,
+        value: String,
+    ) -> Result<crate::types::StoreState, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "hash_set");
+        let key = self.key(key)?;
+        let output = self.hash_store.entry(key).or_default().insert(field, value);
+
+        match output {
+            None => Ok(crate::types::StoreState::New),
+            Some(_) => Ok(crate::types::StoreState::Updated),
+        }
+    }
+
+    /// Gets a field of a map.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the map
+    /// * `field` - The field to get
+    ///
+    /// # Returns
+    /// * `Ok(Some(String))` - If the field exists
+    /// * `Ok(None)` - If the field or the map doesn't exist
+    async fn hash_get(
+        &self,
+        key: impl AsRef<str> + Send,
+        field: String,
+    ) -> Result<Option<String>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "hash_get");
+        let key = self.key(key)?;
+        Ok(self
+            .hash_store
+            .get(&key)
+            .and_then(|map| map.get(&field).cloned()))
+    }
+
+    /// Gets every field of a map.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the map
+    ///
+    /// # Returns
+    /// * `Ok(HashMap<String, String>)` - The fields and their values, empty if the map doesn't exist
+    async fn hash_get_all(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<std::collections::HashMap<String, String>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "hash_get_all");
+        let key = self.key(key)?;
+        Ok(self
+            .hash_store
+            .get(&key)
+            .map(|map| map.clone())
+            .unwrap_or_default())
+    }
+
+    /// Deletes a field of a map, removing the map once empty.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the map
+    /// * `field` - The field to delete
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the field existed
+    /// * `Ok(false)` - If the field or the map didn't exist
+    async fn hash_delete(
+        &self,
+        key: impl AsRef<str> + Send,
+        field: String,
+    ) -> Result<bool, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "hash_delete");
+        let key = self.key(key)?;
+        Ok(self.hash_remove_field(key, &field))
+    }
+
+    /// Gets the number of fields in a map.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the map
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The number of fields, `0` if the map doesn't exist
+    async fn hash_len(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<u64, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "hash_len");
+        let key = self.key(key)?;
+        Ok(self.hash_store.get(&key).map_or(0, |map| map.len() as u64))
+    }
+}
+
+/// Number of messages a lagging subscriber may fall behind before it skips ahead
+const CHANNEL_CAPACITY: usize = 1024;
+
+#[async_trait]
+impl PubSub for IMCModule {
+    /// Publishes a message to the subscribers of this module and its clones.
+    ///
+    /// # Arguments
+    /// * `channel` - The channel to publish to
+    /// * `message` - The message to publish
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The number of subscriptions the message was delivered to
+    async fn publish(
+        &self,
+        channel: String,
+        message: String,
+    ) -> Result<u64, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "publish");
+        let delivered = self
+            .channels
+            .get(&channel)
+            .and_then(|sender| sender.send(message).ok())
+            .unwrap_or(0);
+
+        // Drop channels whose subscribers have all gone away
+        if delivered == 0 {
+            self.channels
+                .remove_if(&channel, |_, sender| sender.receiver_count() == 0);
+        }
+
+        Ok(delivered as u64)
+    }
+
+    /// Subscribes to a channel within this process.
+    ///
+    /// A subscriber that falls more than [`CHANNEL_CAPACITY`] messages behind
+    /// skips the oldest ones instead of blocking publishers.
+    ///
+    /// # Arguments
+    /// * `channel` - The channel to subscribe to
+    ///
+    /// # Returns
+    /// * `Ok(MessageStream)` - The messages published to the channel from now on
+    async fn subscribe(
+        &self,
+        channel: String,
+    ) -> Result<MessageStream, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "subscribe");
+        let receiver = self
+            .channels
+            .entry(channel)
+            .or_insert_with(|| tokio::sync::broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe();
+
+        Ok(Box::pin(futures::stream::unfold(
+            receiver,
+            |mut receiver| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(message) => return Some((message, receiver)),
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            },
+        )))
+    }
+}
+
+#[async_trait]
+impl BulkDelete for IMCModule {
+    /// Deletes every entry whose key starts with a prefix, from all stores.
+    ///
+    /// # Arguments
+    /// * `prefix` - The prefix that deleted keys must start with
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The number of entries deleted, not counting expired ones
+    async fn delete_prefix(&self, prefix: String) -> Result<u64, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "delete_prefix");
+        let self = self.clone();
+        tokio::task::spawn_blocking(move || self.remove_prefix(&prefix))
+            .await
+            .unwrap_or_else(|e| Err(crate::errors::StorageError::JoinError(e)))
+    }
+}
+
+#[async_trait]
+impl KeyRename for IMCModule {
+    /// Moves the entries stored under one key to another key, in every store.
+    ///
+    /// The old entries are removed and reinserted under the new key with their
+    /// expiration. The move is not atomic: a concurrent reader may briefly find
+    /// neither key.
+    ///
+    /// # Arguments
+    /// * `from` - The key whose entries should be moved
+    /// * `to` - The key to move the entries to; its existing entries are replaced
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the entries were moved
+    /// * `Err(StorageError::NotFound)` - If `from` has no live entry
+    async fn rename(
+        &self,
+        from: impl AsRef<str> + Send,
+        to: impl AsRef<str> + Send,
+    ) -> Result<(), crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "rename");
+        self.rename_key(self.key(from)?, self.key(to)?)
+    }
+}
+
+#[async_trait]
+impl BatchRawStorage for IMCModule {
+    /// Loads multiple raw byte values that exist and haven't expired.
+    ///
+    /// # Arguments
+    /// * `keys` - The keys whose values should be loaded
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Option<Vec<u8>>>)` - The value of each key, in input order
+    async fn load_raw_many(
+        &self,
+        keys: Vec<String>,
+    ) -> Result<Vec<Option<Vec<u8>>>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "load_raw_many");
+        let mut output = Vec::with_capacity(keys.len());
+        for key in keys {
+            output.push(self.load_raw(key).await?);
+        }
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl StringGetSet for IMCModule {
+    /// Stores a string value and returns the value it replaced.
+    ///
+    /// The swap happens under the entry's shard lock. An expired previous
+    /// value is reported as missing.
+    ///
+    /// # Arguments
+    /// * `key` - The key under which to store the value
+    /// * `value` - The string value to store
+    ///
+    /// # Returns
+    /// * `Ok(Some(String))` - The previous value, if it existed and hadn't expired
+    /// * `Ok(None)` - If the key didn't exist or had expired
+    async fn get_set(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<Option<String>, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "get_set");
+        let key = self.key(key)?;
+        self.string_get_set(key, value)
+    }
+}
+
+#[async_trait]
+impl BoundedIncrement for IMCModule {
+    /// Increments an atomic integer value and clamps the result into `[min, max]`.
+    ///
+    /// The addition and the clamp are applied with a single compare-exchange
+    /// loop. A missing key counts as `0`.
+    ///
+    /// # Arguments
+    /// * `key` - The key whose value should be incremented
+    /// * `delta` - The integer value to increment by
+    /// * `min` - The lowest value the key may hold
+    /// * `max` - The highest value the key may hold
+    ///
+    /// # Returns
+    /// * `Ok(i64)` - The new, clamped value
+    /// * `Err(StorageError::Overflow)` - If the unclamped sum is outside the `i64` range
+    /// * `Err(StorageError::InvalidArgument)` - If `min` is greater than `max`
+    async fn atomic_increment_bounded(
+        &self,
+        key: impl AsRef<str> + Send,
+        delta: i64,
+        min: i64,
+        max: i64,
+    ) -> Result<i64, crate::errors::StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("imc", "atomic_increment_bounded");
+        if min > max {
+            return Err(crate::errors::StorageError::InvalidArgument(format!(
+                "min ({min}) is greater than max ({max})"
+            )));
+        }
+        let key = self.key(key)?;
+        let atomic = self.atomic_store.entry(key).or_insert(AtomicI64::new(0));
+
+        bounded_increment(&atomic, delta, min, max)
+    }
+}
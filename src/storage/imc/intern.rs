@@ -0,0 +1,70 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+use dashmap::DashMap;
+use std::sync::Arc;
+
+/// Pool size below which the interner is never pruned.
+const MIN_PRUNE_AT: usize = 64;
+
+/// Pool of shared string values, used when `intern_values` is enabled.
+///
+/// Values removed or replaced in the store stay pooled until the next prune,
+/// which drops every value no entry refers to any more. A prune runs whenever
+/// the pool has doubled in size since the last one, so at most about half of
+/// the pooled values are unused at any time.
+pub(super) struct Interner {
+    pool: DashMap<Arc<str>, ()>,
+    /// Pool size at which the next prune runs
+    prune_at: AtomicUsize,
+}
+
+impl Interner {
+    pub(super) fn new() -> Self {
+        Self {
+            pool: DashMap::new(),
+            prune_at: AtomicUsize::new(MIN_PRUNE_AT),
+        }
+    }
+
+    /// Returns the pooled [`Arc<str>`] for `value`, adding it to the pool if missing.
+    pub(super) fn intern(&self, value: String) -> Arc<str> {
+        if let Some(existing) = self.pool.get(value.as_str()) {
+            return existing.key().clone();
+        }
+
+        // Another writer may have pooled the same value since the lookup, in
+        // which case the entry hands back its copy
+        let interned = self
+            .pool
+            .entry(Arc::from(value))
+            .or_insert(())
+            .key()
+            .clone();
+
+        if self.pool.len() >= self.prune_at.load(Ordering::Relaxed) {
+            self.prune();
+        }
+        interned
+    }
+
+    /// Returns the number of pooled values.
+    #[cfg(all(test, feature = "async"))]
+    pub(super) fn len(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// Drops every pooled value.
+    pub(super) fn clear(&self) {
+        self.pool.clear();
+        self.prune_at.store(MIN_PRUNE_AT, Ordering::Relaxed);
+    }
+
+    /// Drops pooled values that are referenced by nothing but the pool.
+    ///
+    /// A value can only be handed out again through the pool, whose shard is
+    /// locked while the count is checked, so a dropped value is never in use.
+    fn prune(&self) {
+        self.pool.retain(|value, _| Arc::strong_count(value) > 1);
+        let prune_at = self.pool.len().saturating_mul(2).max(MIN_PRUNE_AT);
+        self.prune_at.store(prune_at, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,198 @@
+use core::sync::atomic::{AtomicI64, Ordering};
+use dashmap::DashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use super::{released, ValueBytes};
+
+/// Least-recently-used tracking for a store bounded in entries or bytes.
+///
+/// Keys are ordered by a monotonically increasing access tick. The tracker
+/// may hold keys that have since been removed from the store (e.g. expired
+/// entries evicted lazily); these are skipped when making room and pruned
+/// once they outnumber the live entries.
+pub(super) struct Lru {
+    /// Maximum number of entries the store may hold, unbounded if `None`
+    capacity: Option<usize>,
+    state: Mutex<LruState>,
+}
+
+#[derive(Default)]
+struct LruState {
+    /// Next access tick to hand out
+    tick: u64,
+    /// Last access tick of each tracked key
+    ticks: HashMap<String, u64>,
+    /// Tracked keys ordered from least to most recently used
+    order: BTreeMap<u64, String>,
+}
+
+impl Lru {
+    pub(super) fn new(capacity: Option<usize>) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    /// Makes room for `key` in `store` and marks it as most recently used.
+    ///
+    /// If `key` is not already present and the store is at capacity, the
+    /// least recently used entries are removed until it is below capacity.
+    /// Must not be called while holding a guard into `store`.
+    ///
+    /// # Returns
+    /// * `u64` - The number of entries evicted from `store`
+    pub(super) fn admit<V: ValueBytes>(
+        &self,
+        store: &DashMap<String, (V, Option<u64>)>,
+        bytes: &AtomicI64,
+        key: &str,
+    ) -> u64 {
+        let mut state = self.lock();
+        let mut evicted = 0;
+
+        if let (Some(capacity), false) = (self.capacity, store.contains_key(key)) {
+            while store.len() >= capacity {
+                let Some((_, oldest)) = state.order.pop_first() else {
+                    break;
+                };
+                state.ticks.remove(&oldest);
+                if released(bytes, store.remove(&oldest)).is_some() {
+                    evicted += 1;
+                }
+            }
+        }
+
+        if state.ticks.len() > store.len().saturating_mul(2) {
+            state.prune(store);
+        }
+
+        state.touch(key);
+        evicted
+    }
+
+    /// Evicts least recently used entries until `bytes` is within `budget`.
+    ///
+    /// `key`, the entry just written, is never evicted, so a value larger
+    /// than the budget is kept alone. Must not be called while holding a
+    /// guard into `store`.
+    ///
+    /// # Returns
+    /// * `u64` - The number of entries evicted from `store`
+    pub(super) fn shrink<V: ValueBytes>(
+        &self,
+        store: &DashMap<String, (V, Option<u64>)>,
+        bytes: &AtomicI64,
+        budget: usize,
+        key: &str,
+    ) -> u64 {
+        let budget = i64::try_from(budget).unwrap_or(i64::MAX);
+        let mut state = self.lock();
+        let mut evicted = 0;
+        let mut kept = None;
+
+        while bytes.load(Ordering::Relaxed) > budget {
+            let Some((tick, oldest)) = state.order.pop_first() else {
+                break;
+            };
+            if oldest == key {
+                kept = Some((tick, oldest));
+                continue;
+            }
+            state.ticks.remove(&oldest);
+            if released(bytes, store.remove(&oldest)).is_some() {
+                evicted += 1;
+            }
+        }
+
+        if let Some((tick, key)) = kept {
+            state.order.insert(tick, key);
+        }
+        evicted
+    }
+
+    /// Marks `key` as most recently used.
+    pub(super) fn touch(&self, key: &str) {
+        self.lock().touch(key);
+    }
+
+    /// Stops tracking `key` after it has been removed from the store.
+    pub(super) fn forget(&self, key: &str) {
+        let mut state = self.lock();
+        if let Some(tick) = state.ticks.remove(key) {
+            state.order.remove(&tick);
+        }
+    }
+
+    /// Returns the tick the next access will be marked with.
+    #[cfg(feature = "async")]
+    pub(super) fn tick(&self) -> u64 {
+        self.lock().tick
+    }
+
+    /// Stops tracking keys removed by a sweep that started at tick `since`.
+    ///
+    /// A key touched since then, or back in `store`, has been written again
+    /// after the sweep removed it and stays tracked. Must not be called while
+    /// holding a guard into `store`.
+    #[cfg(feature = "async")]
+    pub(super) fn forget_swept<V>(&self, store: &DashMap<String, V>, keys: &[String], since: u64) {
+        let mut state = self.lock();
+        for key in keys {
+            let Some(&tick) = state.ticks.get(key) else {
+                continue;
+            };
+            if tick < since && !store.contains_key(key) {
+                state.ticks.remove(key);
+                state.order.remove(&tick);
+            }
+        }
+    }
+
+    /// Returns the number of tracked keys.
+    #[cfg(all(test, feature = "async"))]
+    pub(super) fn len(&self) -> usize {
+        self.lock().ticks.len()
+    }
+
+    /// Stops tracking every key after the store has been cleared.
+    pub(super) fn clear(&self) {
+        *self.lock() = LruState::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruState> {
+        // The state is always left consistent, so a poisoned lock is safe to reuse
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl LruState {
+    fn touch(&mut self, key: &str) {
+        let tick = self.tick;
+        self.tick += 1;
+
+        match self.ticks.get_mut(key) {
+            Some(previous) => {
+                self.order.remove(previous);
+                *previous = tick;
+            }
+            None => {
+                self.ticks.insert(key.to_string(), tick);
+            }
+        }
+        self.order.insert(tick, key.to_string());
+    }
+
+    /// Drops tracked keys that are no longer present in `store`.
+    fn prune<V>(&mut self, store: &DashMap<String, V>) {
+        let Self { ticks, order, .. } = self;
+        ticks.retain(|key, tick| {
+            let live = store.contains_key(key);
+            if !live {
+                order.remove(tick);
+            }
+            live
+        });
+    }
+}
@@ -1,10 +1,29 @@
 #[cfg(feature = "async")]
 mod async_impl;
+mod intern;
+mod lru;
+#[cfg(feature = "persistence")]
+mod persistence;
+#[cfg(feature = "test-util")]
+mod recording;
 #[cfg(feature = "sync")]
 mod sync_impl;
+#[cfg(all(test, feature = "async"))]
+mod tests;
+#[cfg(feature = "persistence")]
+mod wal;
 
-use core::sync::atomic::AtomicI64;
-use dashmap::DashMap;
+#[cfg(feature = "test-util")]
+pub use recording::{Call, RecordingStorage};
+
+use core::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use dashmap::{mapref::entry::Entry, DashMap};
+use intern::Interner;
+use lru::Lru;
+
+use crate::clock::{Clock, SystemClock};
+use crate::types::StoreState;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
 type ArcDashMap<K, V> = Arc<DashMap<K, V>>;
@@ -15,59 +34,1853 @@ type ArcDashMap<K, V> = Arc<DashMap<K, V>>;
 /// expiration times. Uses [`DashMap`] as the underlying concurrent hash map
 /// wrapped in an [`Arc`] for safe sharing across threads.
 ///
-/// The stored values are tuples of (String, Option<u64>) where:
-/// - The String is the stored value
-/// - The Option<u64> is the optional expiration time in Unix timestamp seconds
+/// The stored values are tuples of (Arc<str>, Option<u64>) where:
+/// - The Arc<str> is the stored value, possibly shared with other entries
+/// - The Option<u64> is the optional expiration time in Unix timestamp milliseconds
 #[derive(Clone)]
 pub struct IMCModule {
     /// Thread-safe storage for string values and their expiration times
-    string_store: Arc<DashMap<String, (String, Option<u64>)>>,
+    string_store: ArcDashMap<String, (Arc<str>, Option<u64>)>,
     /// Thread-safe storage for binary data values and their expiration times
     data_store: ArcDashMap<String, (Vec<u8>, Option<u64>)>,
 
     atomic_store: Arc<DashMap<String, AtomicI64>>,
+    /// Thread-safe storage for floating-point values, kept as their bit patterns
+    float_store: Arc<DashMap<String, AtomicU64>>,
+    /// Thread-safe storage for versioned records as (version, value) pairs
+    versioned_store: ArcDashMap<String, (u64, String)>,
+    /// Thread-safe storage for lists, removed once they become empty
+    list_store: ArcDashMap<String, VecDeque<String>>,
+    /// Thread-safe storage for sets, removed once they become empty
+    set_store: ArcDashMap<String, HashSet<String>>,
+    /// Thread-safe storage for field-value maps, removed once they become empty
+    hash_store: ArcDashMap<String, HashMap<String, String>>,
+    /// Broadcast channels for in-process pub/sub, keyed by channel name
+    #[cfg(feature = "async")]
+    channels: ArcDashMap<String, tokio::sync::broadcast::Sender<String>>,
+    /// Rate limit counters as (count, window end in Unix timestamp milliseconds) pairs
+    rate_store: ArcDashMap<String, (i64, u64)>,
+    /// Pool of interned string values, present only when interning is enabled
+    interner: Option<Arc<Interner>>,
+    /// Keys longer than this many bytes are replaced by their hash
+    hash_keys_over: Option<usize>,
+    /// Check applied to every caller-provided key
+    key_validator: Option<crate::keys::KeyValidator>,
+    /// Time of the last write to each string or binary key, present only when tracked
+    last_modified: Option<ArcDashMap<String, u64>>,
+    /// Approximate byte budget of the string and binary stores, each
+    max_bytes: Option<usize>,
+    /// Recency tracking for the string store, present only when it is bounded
+    string_lru: Option<Arc<Lru>>,
+    /// Recency tracking for the binary data store, present only when it is bounded
+    data_lru: Option<Arc<Lru>>,
+    /// Hit, miss, eviction and size counters reported by [`IMCModule::stats`]
+    counters: Arc<Counters>,
+    /// Time source for expiration
+    clock: Arc<dyn Clock>,
+    /// Write-ahead log of string and binary writes, present only when `wal_path` is set
+    #[cfg(feature = "persistence")]
+    wal: Option<wal::Wal>,
+    /// Background task evicting expired entries, stopped once every clone is dropped
+    #[cfg(feature = "async")]
+    _sweeper: Option<Arc<Sweeper>>,
+}
+
+/// Snapshot of cache activity counters for an [`IMCModule`].
+///
+/// Counters are cumulative since the module was created and are shared by
+/// all of its clones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Loads of string or binary values that found a live value
+    pub hits: u64,
+    /// Loads of string or binary values that found no value or an expired one
+    pub misses: u64,
+    /// Entries removed by the cache itself: expired entries found on load or
+    /// by the background sweeper, and entries evicted to honor `max_entries`
+    /// or `max_bytes`
+    pub evictions: u64,
+    /// Number of entries currently held in the string and binary stores,
+    /// including expired entries that have not been evicted yet
+    pub len: usize,
+    /// Approximate bytes held in the string and binary stores, counted as the
+    /// key length plus the value length of each entry, including expired
+    /// entries that have not been evicted yet
+    pub bytes: usize,
+}
+
+/// Counters backing [`CacheStats`].
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    /// Approximate bytes held by the string store
+    ///
+    /// Signed, since concurrent writers may apply their adjustments out of
+    /// order and briefly take the counter below zero.
+    string_bytes: AtomicI64,
+    /// Approximate bytes held by the binary data store
+    data_bytes: AtomicI64,
 }
 
 /// Configuration struct for IMCModule.
 ///
-/// Currently empty but provides extensibility for future configuration options
-/// such as default expiration times, maximum cache size, etc.
-pub struct IMCConfig {}
+/// Provides extensibility for future configuration options such as default
+/// expiration times, maximum cache size, etc. `IMCConfig::default()` disables
+/// every optional behavior.
+#[derive(Default, Clone)]
+pub struct IMCConfig {
+    /// Deduplicate identical string values behind a shared [`Arc<str>`].
+    ///
+    /// When enabled, every write to the string store hashes the value and looks
+    /// it up in an intern pool, so entries holding the same value share a single
+    /// allocation. This saves memory for caches with many repeated values, at the
+    /// cost of an extra hash and pool lookup on each write. Values that are
+    /// removed or replaced are not released right away: the pool is pruned of
+    /// values no entry uses whenever it has doubled in size, so it may hold up
+    /// to about twice as many values as the store uses.
+    pub intern_values: bool,
+    /// Replace keys longer than this many bytes with a stable hash.
+    ///
+    /// Hashed keys are [`crate::keys::HASHED_KEY_PREFIX`] followed by the hex
+    /// blake3 digest of the original key, which bounds per-key memory for
+    /// caches keyed by long strings such as URLs. The transform is applied on
+    /// every read and write; key listings return the hashed form.
+    pub hash_keys_over: Option<usize>,
+    /// Periodically evict expired entries in the background.
+    ///
+    /// Expired entries are otherwise only removed when they are next accessed,
+    /// so keys that are written once and never read again stay in memory. When
+    /// set, a Tokio task sweeps the string and binary stores at this interval,
+    /// scanning them on the blocking thread pool so async workers aren't stalled.
+    /// The task is stopped when the last clone of the module is dropped.
+    /// `None` disables the sweeper.
+    #[cfg(feature = "async")]
+    pub eviction_interval: Option<std::time::Duration>,
+    /// Bound the string and binary stores to this many entries each.
+    ///
+    /// When a write would add a new key to a store that is already full, the
+    /// least recently used entries of that store are evicted first. Loads and
+    /// writes both count as uses. The bound is approximate under concurrent
+    /// writes, and recency tracking adds a lock shared by all accesses to the
+    /// bounded stores. `None` leaves the stores unbounded.
+    pub max_entries: Option<usize>,
+    /// Bound the approximate memory of the string and binary stores to this
+    /// many bytes each.
+    ///
+    /// An entry counts as the length of its key plus the length of its value;
+    /// allocator overhead is ignored, and an interned value is counted for
+    /// every entry holding it. When a write leaves a store over budget, the
+    /// least recently used entries of that store are evicted until it fits,
+    /// with the same recency tracking as `max_entries`. A value larger than
+    /// the budget evicts every other entry but is still stored. `None` leaves
+    /// the stores unbounded.
+    pub max_bytes: Option<usize>,
+    /// Append string and binary stores and deletes to a log at this path.
+    ///
+    /// The log is replayed on construction, restoring the writes of previous
+    /// runs, and then appended to by a background thread so writes don't wait
+    /// on the disk. Records still buffered when the process crashes are lost.
+    /// The log is never compacted, so it grows with every write.
+    ///
+    /// Logged are the writes of the `StringStorage` and `RawStorage` traits
+    /// (stores, keep-TTL stores, deletes and takes), absolute and millisecond
+    /// expiry stores, get-sets and clears. Other operations, such as appends,
+    /// conditional writes, expiration changes and renames, are not logged and
+    /// are lost on restart. `None` disables the log.
+    #[cfg(feature = "persistence")]
+    pub wal_path: Option<std::path::PathBuf>,
+    /// Number of shards each underlying [`DashMap`] is split into.
+    ///
+    /// More shards reduce lock contention between concurrent accesses at the
+    /// cost of some memory per map. Must be a power of two greater than 1.
+    /// `None` uses DashMap's default, which scales with the number of CPUs.
+    pub shard_amount: Option<usize>,
+    /// Record when each string or binary key was last written.
+    ///
+    /// Meant for debugging races: stores, deletes, takes, appends, successful
+    /// conditional writes, renames and prefix deletes note the current time,
+    /// which [`IMCModule::last_modified`] reports. Expiration changes and
+    /// evictions are not writes. Times are kept after their key is removed,
+    /// until the module is cleared, so memory grows with the number of
+    /// distinct keys written. `false` skips the bookkeeping.
+    pub track_last_modified: bool,
+    /// Check every key before it is used, failing the operation on rejection.
+    ///
+    /// `None` accepts every key, including the empty one.
+    pub key_validator: Option<crate::keys::KeyValidator>,
+}
 
 impl IMCModule {
     /// Creates a new instance of IMCModule.
     ///
     /// # Arguments
-    /// * `_config` - Configuration options for the cache (currently unused)
+    /// * `config` - Configuration options for the cache
+    ///
+    /// # Returns
+    /// * `Self` - A new instance of IMCModule with an empty cache
+    ///
+    /// # Panics
+    /// Panics if `eviction_interval` is set and this is called outside of a
+    /// Tokio runtime, if `shard_amount` is not a power of two greater than 1,
+    /// or if `wal_path` is set and the log cannot be replayed or opened.
+    pub fn new(config: IMCConfig) -> Self {
+        Self::with_capacity(config, 0)
+    }
+
+    /// Creates a new instance of IMCModule with pre-sized stores.
+    ///
+    /// The string, binary and atomic stores are allocated to hold at least
+    /// `capacity` entries each without reallocating, which avoids repeated
+    /// rehashing when bulk-loading a known number of keys.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration options for the cache
+    /// * `capacity` - The number of entries each of these stores can hold before growing
     ///
     /// # Returns
     /// * `Self` - A new instance of IMCModule with an empty cache
-    pub fn new(_config: IMCConfig) -> Self {
-        Self {
-            string_store: Arc::new(DashMap::new()),
-            data_store: Arc::new(DashMap::new()),
-            atomic_store: Arc::new(DashMap::new()),
+    ///
+    /// # Panics
+    /// Panics if `eviction_interval` is set and this is called outside of a
+    /// Tokio runtime, if `shard_amount` is not a power of two greater than 1,
+    /// or if `wal_path` is set and the log cannot be replayed or opened.
+    pub fn with_capacity(config: IMCConfig, capacity: usize) -> Self {
+        Self::build(config, capacity, Arc::new(SystemClock))
+    }
+
+    /// Creates a new instance of IMCModule that reads time from `clock`.
+    ///
+    /// All expiration checks, including those of the background sweeper, use
+    /// the given clock, e.g. a `MockClock` to test expiry without sleeping.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration options for the cache
+    /// * `clock` - Source of the current time
+    ///
+    /// # Returns
+    /// * `Self` - A new instance of IMCModule with an empty cache
+    ///
+    /// # Panics
+    /// Panics if `eviction_interval` is set and this is called outside of a
+    /// Tokio runtime, if `shard_amount` is not a power of two greater than 1,
+    /// or if `wal_path` is set and the log cannot be replayed or opened.
+    pub fn with_clock(config: IMCConfig, clock: impl Clock + 'static) -> Self {
+        Self::build(config, 0, Arc::new(clock))
+    }
+
+    /// Shared constructor behind `with_capacity` and `with_clock`.
+    fn build(config: IMCConfig, capacity: usize, clock: Arc<dyn Clock>) -> Self {
+        let shards = config.shard_amount;
+        let string_store: ArcDashMap<String, (Arc<str>, Option<u64>)> = new_map(shards, capacity);
+        let data_store: ArcDashMap<String, (Vec<u8>, Option<u64>)> = new_map(shards, capacity);
+        let counters = Arc::new(Counters::default());
+        let bounded = config.max_entries.is_some() || config.max_bytes.is_some();
+        let string_lru = bounded.then(|| Arc::new(Lru::new(config.max_entries)));
+        let data_lru = bounded.then(|| Arc::new(Lru::new(config.max_entries)));
+
+        #[cfg(feature = "async")]
+        let sweeper = config.eviction_interval.map(|interval| {
+            Arc::new(Sweeper::spawn(
+                interval,
+                string_store.clone(),
+                data_store.clone(),
+                string_lru.clone(),
+                data_lru.clone(),
+                counters.clone(),
+                clock.clone(),
+            ))
+        });
+
+        let module = Self {
+            string_store,
+            data_store,
+            atomic_store: new_map(shards, capacity),
+            float_store: new_map(shards, 0),
+            versioned_store: new_map(shards, 0),
+            list_store: new_map(shards, 0),
+            set_store: new_map(shards, 0),
+            hash_store: new_map(shards, 0),
+            #[cfg(feature = "async")]
+            channels: new_map(shards, 0),
+            rate_store: new_map(shards, 0),
+            interner: config.intern_values.then(|| Arc::new(Interner::new())),
+            hash_keys_over: config.hash_keys_over,
+            key_validator: config.key_validator,
+            last_modified: config.track_last_modified.then(|| new_map(shards, 0)),
+            max_bytes: config.max_bytes,
+            string_lru,
+            data_lru,
+            counters,
+            clock,
+            #[cfg(feature = "persistence")]
+            wal: None,
+            #[cfg(feature = "async")]
+            _sweeper: sweeper,
+        };
+
+        #[cfg(feature = "persistence")]
+        let module = match &config.wal_path {
+            Some(path) => module.attach_wal(path),
+            None => module,
+        };
+        module
+    }
+
+    /// Closes the cache, releasing its contents.
+    ///
+    /// Mirrors `RedisStorageModule::close`. Every store is cleared, which also
+    /// affects clones of this module since they share the same stores. The
+    /// background sweeper stops once the last clone is dropped.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Always
+    pub fn close(self) -> Result<(), crate::errors::StorageError> {
+        self.clear_all();
+        Ok(())
+    }
+
+    /// Returns a snapshot of the cache's hit, miss and eviction counters and size.
+    ///
+    /// # Returns
+    /// * `CacheStats` - The current counters and number of stored entries
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+            evictions: self.counters.evictions.load(Ordering::Relaxed),
+            len: self.string_store.len() + self.data_store.len(),
+            bytes: byte_count(&self.counters.string_bytes) + byte_count(&self.counters.data_bytes),
+        }
+    }
+
+    /// Returns when a string or binary key was last written.
+    ///
+    /// Requires `track_last_modified`; see there for what counts as a write.
+    /// A write whose time couldn't be read from the clock isn't recorded.
+    ///
+    /// # Arguments
+    /// * `key` - The key to look up, as passed to the storage traits
+    ///
+    /// # Returns
+    /// * `Option<u64>` - The time of the last write in Unix timestamp milliseconds,
+    ///   or `None` if the key was never written, tracking is disabled or the
+    ///   key is rejected by the configured validator
+    pub fn last_modified(&self, key: &str) -> Option<u64> {
+        let last_modified = self.last_modified.as_ref()?;
+        let key = self.key(key).ok()?;
+        last_modified.get(&key).map(|entry| *entry.value())
+    }
+
+    /// Evicts every expired string and binary entry now.
+    ///
+    /// Performs the same sweep as the background task configured by
+    /// `eviction_interval`, e.g. for callers that prefer to schedule cleanup
+    /// themselves. The stores are scanned on a blocking thread, since each
+    /// shard is locked while it is checked. Evicted entries are counted in
+    /// [`CacheStats::evictions`].
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The number of entries evicted
+    /// * `Err(StorageError::Clock)` - If the current time cannot be determined
+    #[cfg(feature = "async")]
+    pub async fn clear_expired(&self) -> Result<usize, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        let this = self.clone();
+        let evicted = tokio::task::spawn_blocking(move || {
+            evict_expired(
+                &this.string_store,
+                &this.counters.string_bytes,
+                this.string_lru.as_deref(),
+                current_time,
+            ) + evict_expired(
+                &this.data_store,
+                &this.counters.data_bytes,
+                this.data_lru.as_deref(),
+                current_time,
+            )
+        })
+        .await
+        .map_err(crate::errors::StorageError::JoinError)?;
+
+        self.record_evictions(evicted);
+        Ok(evicted as usize)
+    }
+
+    /// Returns the number of entries in the string store.
+    ///
+    /// Expired entries that haven't been evicted yet are included.
+    pub fn string_len(&self) -> usize {
+        self.string_store.len()
+    }
+
+    /// Returns the number of entries in the binary data store.
+    ///
+    /// Expired entries that haven't been evicted yet are included.
+    pub fn data_len(&self) -> usize {
+        self.data_store.len()
+    }
+
+    /// Returns the number of entries in the atomic integer store.
+    pub fn atomic_len(&self) -> usize {
+        self.atomic_store.len()
+    }
+
+    /// Checks whether the string, binary data and atomic integer stores are all empty.
+    ///
+    /// Like the per-store lengths, this doesn't look at expiration, so a store
+    /// holding only expired entries is not empty until they are evicted.
+    pub fn is_empty(&self) -> bool {
+        self.string_store.is_empty() && self.data_store.is_empty() && self.atomic_store.is_empty()
+    }
+
+    /// Copies every live entry of the string store.
+    ///
+    /// This is a point-in-time copy rather than a live view: writes made while
+    /// the store is being iterated may or may not be included, and later writes
+    /// are never reflected. Expired entries are skipped but not evicted. Keys
+    /// are returned as stored, i.e. already hashed if they exceeded
+    /// `hash_keys_over`.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(String, String, Option<u64>)>)` - The key, value and absolute expiration
+    ///   time in Unix timestamp milliseconds of each entry, in no particular order
+    /// * `Err(StorageError::Clock)` - If the current time cannot be determined
+    pub fn snapshot_strings(
+        &self,
+    ) -> Result<Vec<(String, String, Option<u64>)>, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+
+        Ok(self
+            .string_store
+            .iter()
+            .filter(|entry| !entry.value().1.is_some_and(|e| is_expired(e, current_time)))
+            .map(|entry| {
+                let (value, expiry) = entry.value();
+                (entry.key().clone(), value.to_string(), *expiry)
+            })
+            .collect())
+    }
+
+    /// Replaces a versioned record's value if its version matches.
+    ///
+    /// The comparison and write happen while holding the entry guard, so
+    /// concurrent updates of the same key cannot interleave.
+    fn compare_and_set_versioned_entry(
+        &self,
+        key: String,
+        expected_version: u64,
+        new_value: String,
+    ) -> Option<u64> {
+        match self.versioned_store.entry(key) {
+            Entry::Occupied(mut entry) if entry.get().0 == expected_version => {
+                let version = expected_version + 1;
+                entry.insert((version, new_value));
+                Some(version)
+            }
+            Entry::Vacant(entry) if expected_version == 0 => {
+                entry.insert((1, new_value));
+                Some(1)
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the remaining time-to-live of a string value.
+    ///
+    /// An entry that has already expired is evicted and reported as missing.
+    fn string_ttl(&self, key: &str) -> Result<Option<u64>, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        let Some(expiry) = self.string_store.get(key).and_then(|entry| entry.value().1) else {
+            return Ok(None);
+        };
+
+        if is_expired(expiry, current_time) {
+            self.evict_string_if_expired(key, current_time);
+            return Ok(None);
+        }
+
+        Ok(Some(millis_to_secs(expiry - current_time)))
+    }
+
+    /// Sets the expiration of a string value in place.
+    ///
+    /// An entry that has already expired is evicted and treated as missing.
+    fn string_expire(&self, key: &str, expiry: u64) -> Result<bool, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        let Some(mut entry) = self.string_store.get_mut(key) else {
+            return Ok(false);
+        };
+
+        match entry.1 {
+            Some(current) if is_expired(current, current_time) => {
+                drop(entry);
+                self.evict_string_if_expired(key, current_time);
+                Ok(false)
+            }
+            _ => {
+                entry.1 = Some(current_time.saturating_add(secs_to_millis(expiry)));
+                Ok(true)
+            }
+        }
+    }
+
+    /// Pushes the expiration of a string value forward in place.
+    ///
+    /// The comparison and update happen under the entry guard. An entry that
+    /// has already expired is evicted and treated as missing; one without
+    /// expiration is left unchanged.
+    fn string_expire_if_longer(
+        &self,
+        key: &str,
+        expiry: u64,
+    ) -> Result<bool, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        let expires_at = current_time.saturating_add(secs_to_millis(expiry));
+        let Some(mut entry) = self.string_store.get_mut(key) else {
+            return Ok(false);
+        };
+
+        match entry.1 {
+            Some(current) if is_expired(current, current_time) => {
+                drop(entry);
+                self.evict_string_if_expired(key, current_time);
+                Ok(false)
+            }
+            Some(current) if expires_at > current => {
+                entry.1 = Some(expires_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Removes the expiration of a string value in place.
+    ///
+    /// An entry that has already expired is evicted and treated as missing.
+    fn string_persist(&self, key: &str) -> Result<bool, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        let Some(mut entry) = self.string_store.get_mut(key) else {
+            return Ok(false);
+        };
+
+        match entry.1 {
+            Some(current) if is_expired(current, current_time) => {
+                drop(entry);
+                self.evict_string_if_expired(key, current_time);
+                Ok(false)
+            }
+            Some(_) => {
+                entry.1 = None;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Stores a string value with an optional absolute expiration time.
+    ///
+    /// # Arguments
+    /// * `expires_at` - The expiration time in Unix timestamp milliseconds, if any
+    fn string_store_until(
+        &self,
+        key: String,
+        value: String,
+        expires_at: Option<u64>,
+    ) -> StoreState {
+        #[cfg(feature = "persistence")]
+        self.log(|| wal::WalRecord::StoreString {
+            key: key.clone(),
+            value: value.clone(),
+            expiry: expires_at,
+        });
+        self.admit_string(&key);
+        self.mark_modified(&key);
+        let previous = insert_sized(
+            &self.string_store,
+            &self.counters.string_bytes,
+            key.clone(),
+            (self.intern(value), expires_at),
+        );
+        self.shrink_strings(&key);
+        match previous {
+            None => StoreState::New,
+            Some(_) => StoreState::Updated,
+        }
+    }
+
+    /// Stores a string value while preserving the key's existing expiration.
+    fn string_keep_ttl(
+        &self,
+        key: String,
+        value: String,
+    ) -> Result<StoreState, crate::errors::StorageError> {
+        #[cfg(feature = "persistence")]
+        self.log(|| wal::WalRecord::StoreStringKeepTtl {
+            key: key.clone(),
+            value: value.clone(),
+        });
+        self.admit_string(&key);
+        self.mark_modified(&key);
+        let state = insert_keep_ttl(
+            &self.string_store,
+            &self.counters.string_bytes,
+            key.clone(),
+            self.intern(value),
+            self.now_millis()?,
+        );
+        self.shrink_strings(&key);
+        Ok(state)
+    }
+
+    /// Replaces a string value, returning the previous one if it hadn't expired.
+    ///
+    /// The new value is stored without expiration.
+    fn string_get_set(
+        &self,
+        key: String,
+        value: String,
+    ) -> Result<Option<String>, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        #[cfg(feature = "persistence")]
+        self.log(|| wal::WalRecord::StoreString {
+            key: key.clone(),
+            value: value.clone(),
+            expiry: None,
+        });
+        self.admit_string(&key);
+        self.mark_modified(&key);
+        let previous = insert_sized(
+            &self.string_store,
+            &self.counters.string_bytes,
+            key.clone(),
+            (self.intern(value), None),
+        );
+        self.shrink_strings(&key);
+
+        Ok(match previous {
+            Some((_, Some(expiry))) if is_expired(expiry, current_time) => None,
+            Some((previous, _)) => Some(previous.to_string()),
+            None => None,
+        })
+    }
+
+    /// Removes a string value.
+    fn string_delete(&self, key: &str) {
+        #[cfg(feature = "persistence")]
+        self.log(|| wal::WalRecord::DeleteString {
+            key: key.to_string(),
+        });
+        self.forget_string(key);
+        self.mark_modified(key);
+        released(&self.counters.string_bytes, self.string_store.remove(key));
+    }
+
+    /// Stores a binary value with an optional absolute expiration time.
+    ///
+    /// # Arguments
+    /// * `expires_at` - The expiration time in Unix timestamp milliseconds, if any
+    fn raw_store_until(&self, key: String, value: Vec<u8>, expires_at: Option<u64>) -> StoreState {
+        #[cfg(feature = "persistence")]
+        self.log(|| wal::WalRecord::StoreRaw {
+            key: key.clone(),
+            value: value.clone(),
+            expiry: expires_at,
+        });
+        self.admit_raw(&key);
+        self.mark_modified(&key);
+        let previous = insert_sized(
+            &self.data_store,
+            &self.counters.data_bytes,
+            key.clone(),
+            (value, expires_at),
+        );
+        self.shrink_raw(&key);
+        match previous {
+            None => StoreState::New,
+            Some(_) => StoreState::Updated,
+        }
+    }
+
+    /// Stores a binary value while preserving the key's existing expiration.
+    fn raw_keep_ttl(
+        &self,
+        key: String,
+        value: Vec<u8>,
+    ) -> Result<StoreState, crate::errors::StorageError> {
+        #[cfg(feature = "persistence")]
+        self.log(|| wal::WalRecord::StoreRawKeepTtl {
+            key: key.clone(),
+            value: value.clone(),
+        });
+        self.admit_raw(&key);
+        self.mark_modified(&key);
+        let state = insert_keep_ttl(
+            &self.data_store,
+            &self.counters.data_bytes,
+            key.clone(),
+            value,
+            self.now_millis()?,
+        );
+        self.shrink_raw(&key);
+        Ok(state)
+    }
+
+    /// Removes a binary value.
+    fn raw_delete(&self, key: &str) {
+        #[cfg(feature = "persistence")]
+        self.log(|| wal::WalRecord::DeleteRaw {
+            key: key.to_string(),
+        });
+        self.forget_raw(key);
+        self.mark_modified(key);
+        released(&self.counters.data_bytes, self.data_store.remove(key));
+    }
+
+    /// Stores a string value only if no live entry exists for the key.
+    ///
+    /// The check and insert happen under the entry guard, so concurrent
+    /// callers cannot both succeed. An expired entry counts as absent.
+    fn string_store_if_absent(
+        &self,
+        key: String,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        let expiry = expiry.map(|e| current_time.saturating_add(secs_to_millis(e)));
+        self.admit_string(&key);
+        let size = key.len() + value.len();
+
+        let state = match self.string_store.entry(key.clone()) {
+            Entry::Occupied(mut entry) => match entry.get().1 {
+                Some(current) if is_expired(current, current_time) => {
+                    let (previous, _) = entry.insert((self.intern(value), expiry));
+                    adjust_bytes(
+                        &self.counters.string_bytes,
+                        key.len() + previous.len(),
+                        size,
+                    );
+                    StoreState::New
+                }
+                _ => StoreState::Unchanged,
+            },
+            Entry::Vacant(entry) => {
+                entry.insert((self.intern(value), expiry));
+                adjust_bytes(&self.counters.string_bytes, 0, size);
+                StoreState::New
+            }
+        };
+        if state != StoreState::Unchanged {
+            self.mark_modified(&key);
+        }
+        self.shrink_strings(&key);
+        Ok(state)
+    }
+
+    /// Replaces a string value only if it currently matches `expected`.
+    ///
+    /// The comparison and write happen under the entry guard, so they cannot
+    /// interleave with other writers. An expired entry counts as absent.
+    fn string_compare_and_swap(
+        &self,
+        key: String,
+        expected: Option<String>,
+        new: String,
+    ) -> Result<StoreState, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        self.admit_string(&key);
+        let bytes = &self.counters.string_bytes;
+        let size = key.len() + new.len();
+
+        let state = match (self.string_store.entry(key.clone()), expected) {
+            (Entry::Occupied(mut entry), expected) => {
+                let (current, expiry) = entry.get();
+                let live = !matches!(expiry, Some(expiry) if is_expired(*expiry, current_time));
+                let freed = key.len() + current.len();
+
+                match expected {
+                    Some(expected) if live && **current == *expected => {
+                        let expiry = *expiry;
+                        entry.insert((self.intern(new), expiry));
+                        adjust_bytes(bytes, freed, size);
+                        StoreState::Updated
+                    }
+                    None if !live => {
+                        entry.insert((self.intern(new), None));
+                        adjust_bytes(bytes, freed, size);
+                        StoreState::New
+                    }
+                    _ => StoreState::Unchanged,
+                }
+            }
+            (Entry::Vacant(entry), None) => {
+                entry.insert((self.intern(new), None));
+                adjust_bytes(bytes, 0, size);
+                StoreState::New
+            }
+            (Entry::Vacant(_), Some(_)) => StoreState::Unchanged,
+        };
+        if state != StoreState::Unchanged {
+            self.mark_modified(&key);
+        }
+        self.shrink_strings(&key);
+        Ok(state)
+    }
+
+    /// Removes every entry from all stores.
+    ///
+    /// Interned values and recency tracking are released as well, and the
+    /// byte counts reset; the hit, miss and eviction counters are kept.
+    fn clear_all(&self) {
+        #[cfg(feature = "persistence")]
+        self.log(|| wal::WalRecord::Clear);
+        self.string_store.clear();
+        self.data_store.clear();
+        // Writes racing with the clear may leave the counters slightly off
+        self.counters.string_bytes.store(0, Ordering::Relaxed);
+        self.counters.data_bytes.store(0, Ordering::Relaxed);
+        self.atomic_store.clear();
+        self.float_store.clear();
+        self.versioned_store.clear();
+        self.list_store.clear();
+        self.set_store.clear();
+        self.hash_store.clear();
+        self.rate_store.clear();
+
+        if let Some(interner) = &self.interner {
+            interner.clear();
+        }
+        if let Some(last_modified) = &self.last_modified {
+            last_modified.clear();
+        }
+        for lru in [&self.string_lru, &self.data_lru].into_iter().flatten() {
+            lru.clear();
+        }
+    }
+
+    /// Lists the keys of all live string values that start with `prefix`.
+    fn string_keys_with_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<String>, crate::errors::StorageError> {
+        self.live_string_keys(|key| key.starts_with(prefix))
+    }
+
+    /// Streams the keys of all live string values matching a glob pattern.
+    ///
+    /// The IMC counterpart of `RedisStorageModule::scan_iter`, supporting the
+    /// same pattern syntax. Only the string store is scanned, as with
+    /// `keys_with_prefix`. The matching keys are collected before the first
+    /// one is yielded: holding the map's shard locks while the stream is
+    /// consumed would block writers, and could deadlock a consumer that writes
+    /// to the module. Keys are returned as stored, so long keys appear hashed
+    /// when `hash_keys_over` is set.
+    ///
+    /// # Arguments
+    /// * `pattern` - The glob pattern keys must match, e.g. `user:*`
+    ///
+    /// # Returns
+    /// * `impl Stream` - The matching keys, in no particular order
+    #[cfg(feature = "async")]
+    pub fn scan_iter(
+        &self,
+        pattern: String,
+    ) -> impl futures::Stream<Item = Result<String, crate::errors::StorageError>> + Send {
+        use futures::StreamExt;
+
+        let this = self.clone();
+        let keys = async move {
+            tokio::task::spawn_blocking(move || {
+                this.live_string_keys(|key| crate::keys::glob_match(&pattern, key))
+            })
+            .await
+            .unwrap_or_else(|e| Err(crate::errors::StorageError::JoinError(e)))
+        };
+
+        futures::stream::once(keys).flat_map(|keys| match keys {
+            Ok(keys) => futures::stream::iter(keys.into_iter().map(Ok)).left_stream(),
+            Err(e) => futures::stream::iter(std::iter::once(Err(e))).right_stream(),
+        })
+    }
+
+    /// Lists the keys of all live string values accepted by `matches`.
+    fn live_string_keys(
+        &self,
+        matches: impl Fn(&str) -> bool,
+    ) -> Result<Vec<String>, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+
+        Ok(self
+            .string_store
+            .iter()
+            .filter(|entry| matches(entry.key()))
+            .filter(|entry| match entry.value().1 {
+                Some(expiry) => !is_expired(expiry, current_time),
+                None => true,
+            })
+            .map(|entry| entry.key().clone())
+            .collect())
+    }
+
+    /// Checks whether a key exists in any store.
+    ///
+    /// Expired string and binary entries are evicted and reported as absent.
+    fn key_exists(&self, key: &str, current_time: u64) -> bool {
+        self.evict_string_if_expired(key, current_time);
+        self.evict_raw_if_expired(key, current_time);
+        self.string_store.contains_key(key)
+            || self.data_store.contains_key(key)
+            || self.atomic_store.contains_key(key)
+            || self.float_store.contains_key(key)
+            || self.versioned_store.contains_key(key)
+            || self.list_store.contains_key(key)
+            || self.set_store.contains_key(key)
+            || self.hash_store.contains_key(key)
+            || self.rate_store.contains_key(key)
+    }
+
+    /// Removes every entry whose key starts with `prefix` from all stores.
+    ///
+    /// Expired string and binary entries are removed as well, but not counted.
+    fn remove_prefix(&self, prefix: &str) -> Result<u64, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        let live = |expiry: &Option<u64>| !expiry.is_some_and(|e| is_expired(e, current_time));
+
+        let strings = retain_unprefixed(&self.string_store, prefix, |key, entry| {
+            release_bytes(&self.counters.string_bytes, key, entry);
+            live(&entry.1)
+        });
+        let data = retain_unprefixed(&self.data_store, prefix, |key, entry| {
+            release_bytes(&self.counters.data_bytes, key, entry);
+            live(&entry.1)
+        });
+        // Recency tracking is updated only after the stores' shard locks are released
+        for key in &strings {
+            self.forget_string(key);
+            self.mark_modified(key);
+        }
+        for key in &data {
+            self.forget_raw(key);
+            self.mark_modified(key);
+        }
+
+        let others = [
+            retain_unprefixed(&self.atomic_store, prefix, |_, _| true),
+            retain_unprefixed(&self.float_store, prefix, |_, _| true),
+            retain_unprefixed(&self.versioned_store, prefix, |_, _| true),
+            retain_unprefixed(&self.list_store, prefix, |_, _| true),
+            retain_unprefixed(&self.set_store, prefix, |_, _| true),
+            retain_unprefixed(&self.hash_store, prefix, |_, _| true),
+            retain_unprefixed(&self.rate_store, prefix, |_, _| true),
+        ];
+
+        Ok((strings.len() + data.len() + others.iter().map(Vec::len).sum::<usize>()) as u64)
+    }
+
+    /// Moves every entry stored under `from` to `to`.
+    ///
+    /// Entries under `to` are replaced, including those in stores where `from`
+    /// has no entry, so afterwards `to` holds exactly what `from` held. Values
+    /// keep their expiration. Fails with `StorageError::NotFound` if `from` has
+    /// no live entry in any store.
+    fn rename_key(&self, from: String, to: String) -> Result<(), crate::errors::StorageError> {
+        if !self.key_exists(&from, self.now_millis()?) {
+            return Err(crate::errors::StorageError::NotFound(from));
+        }
+        if from == to {
+            return Ok(());
+        }
+
+        self.mark_modified(&from);
+        self.mark_modified(&to);
+        self.forget_string(&from);
+        self.forget_string(&to);
+        if move_sized_entry(&self.string_store, &self.counters.string_bytes, &from, &to) {
+            self.touch_string(&to);
+            self.shrink_strings(&to);
+        }
+        self.forget_raw(&from);
+        self.forget_raw(&to);
+        if move_sized_entry(&self.data_store, &self.counters.data_bytes, &from, &to) {
+            self.touch_raw(&to);
+            self.shrink_raw(&to);
+        }
+        move_entry(&self.atomic_store, &from, &to);
+        move_entry(&self.float_store, &from, &to);
+        move_entry(&self.versioned_store, &from, &to);
+        move_entry(&self.list_store, &from, &to);
+        move_entry(&self.set_store, &from, &to);
+        move_entry(&self.hash_store, &from, &to);
+        move_entry(&self.rate_store, &from, &to);
+
+        Ok(())
+    }
+
+    /// Removes a string value, returning it if it hadn't expired.
+    fn string_take(&self, key: &str) -> Result<Option<String>, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        #[cfg(feature = "persistence")]
+        self.log(|| wal::WalRecord::DeleteString {
+            key: key.to_string(),
+        });
+        self.forget_string(key);
+        self.mark_modified(key);
+        let Some((_, (value, expiry))) =
+            released(&self.counters.string_bytes, self.string_store.remove(key))
+        else {
+            return Ok(None);
+        };
+
+        Ok(match expiry {
+            Some(expiry) if is_expired(expiry, current_time) => None,
+            _ => Some(value.to_string()),
+        })
+    }
+
+    /// Loads the bytes `start..end` of a string value if it hasn't expired.
+    ///
+    /// The range is clamped to the value's length. An expired value is evicted
+    /// and counted as a miss, like a load.
+    fn string_range(
+        &self,
+        key: &str,
+        start: usize,
+        end: usize,
+    ) -> Result<Option<String>, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        let output = match self.string_store.get(key) {
+            Some(entry) => match entry.value() {
+                (_, Some(expiry)) if is_expired(*expiry, current_time) => {
+                    drop(entry);
+                    self.evict_string_if_expired(key, current_time);
+                    None
+                }
+                (value, _) => {
+                    let bytes = value.as_bytes();
+                    let end = end.min(bytes.len());
+                    let start = start.min(end);
+                    Some(String::from_utf8_lossy(&bytes[start..end]).into_owned())
+                }
+            },
+            None => None,
+        };
+
+        self.record_load(output.is_some());
+        if output.is_some() {
+            self.touch_string(key);
+        }
+        Ok(output)
+    }
+
+    /// Appends to a string value, creating it if missing or expired.
+    ///
+    /// The existing expiration is preserved; an expired value is replaced as if
+    /// absent, without expiration.
+    fn string_append(
+        &self,
+        key: String,
+        value: String,
+    ) -> Result<u64, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        self.admit_string(&key);
+        self.mark_modified(&key);
+        let mut freed = 0;
+
+        let entry = self
+            .string_store
+            .entry(key.clone())
+            .and_modify(|(current, expiry)| {
+                freed = key.len() + current.len();
+                let mut appended = match expiry {
+                    Some(at) if is_expired(*at, current_time) => {
+                        *expiry = None;
+                        String::new()
+                    }
+                    _ => current.to_string(),
+                };
+                appended.push_str(&value);
+                *current = self.intern(appended);
+            })
+            .or_insert_with(|| (self.intern(value.clone()), None));
+        let len = entry.value().0.len();
+        drop(entry);
+
+        adjust_bytes(&self.counters.string_bytes, freed, key.len() + len);
+        self.shrink_strings(&key);
+        Ok(len as u64)
+    }
+
+    /// Adds to a floating-point value, creating it at `0.0` if missing.
+    ///
+    /// Values are stored as [`f64::to_bits`] in an [`AtomicU64`], so the
+    /// addition is applied with a compare-exchange loop. NaN and infinities
+    /// follow IEEE 754 arithmetic: once a value becomes NaN it stays NaN.
+    fn float_increment(&self, key: String, value: f64) -> f64 {
+        let atomic = self
+            .float_store
+            .entry(key)
+            .or_insert_with(|| AtomicU64::new(0f64.to_bits()));
+
+        let mut current = atomic.load(std::sync::atomic::Ordering::SeqCst);
+        loop {
+            let updated = f64::from_bits(current) + value;
+            match atomic.compare_exchange_weak(
+                current,
+                updated.to_bits(),
+                std::sync::atomic::Ordering::SeqCst,
+                std::sync::atomic::Ordering::SeqCst,
+            ) {
+                Ok(_) => return updated,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Counts a request in the key's current rate limit window.
+    ///
+    /// Starts a new window when none exists or the previous one has ended.
+    /// Ended windows are only replaced when their key is checked again.
+    fn rate_check(
+        &self,
+        key: String,
+        limit: i64,
+        window_secs: u64,
+    ) -> Result<bool, crate::errors::StorageError> {
+        let current_time = self.now_millis()?;
+        let window_end = current_time.saturating_add(secs_to_millis(window_secs));
+
+        let mut entry = self.rate_store.entry(key).or_insert((0, window_end));
+        let (count, end) = entry.value_mut();
+        if is_expired(*end, current_time) {
+            *count = 0;
+            *end = window_end;
+        }
+        *count = count.saturating_add(1);
+
+        Ok(*count <= limit)
+    }
+
+    /// Pushes a value onto either end of a list, creating it if missing.
+    ///
+    /// # Returns
+    /// * `u64` - The length of the list after the push
+    fn list_push(&self, key: String, value: String, front: bool) -> u64 {
+        let mut list = self.list_store.entry(key).or_default();
+        if front {
+            list.push_front(value);
+        } else {
+            list.push_back(value);
+        }
+        list.len() as u64
+    }
+
+    /// Pops a value from either end of a list, removing the list once empty.
+    fn list_pop(&self, key: String, front: bool) -> Option<String> {
+        let Entry::Occupied(mut entry) = self.list_store.entry(key) else {
+            return None;
+        };
+
+        let list = entry.get_mut();
+        let value = if front {
+            list.pop_front()
+        } else {
+            list.pop_back()
+        };
+        if list.is_empty() {
+            entry.remove();
+        }
+        value
+    }
+
+    /// Lists the elements of a list between `start` and `stop`, inclusive.
+    ///
+    /// Negative indices count from the end of the list, as in Redis `LRANGE`.
+    fn list_range(&self, key: &str, start: i64, stop: i64) -> Vec<String> {
+        let Some(list) = self.list_store.get(key) else {
+            return Vec::new();
+        };
+
+        let len = list.len() as i64;
+        let start = if start < 0 {
+            (len + start).max(0)
+        } else {
+            start
+        };
+        let stop = if stop < 0 {
+            len + stop
+        } else {
+            stop.min(len - 1)
+        };
+        if start > stop {
+            return Vec::new();
+        }
+
+        list.range(start as usize..=stop as usize)
+            .cloned()
+            .collect()
+    }
+
+    /// Removes a member from a set, removing the set once empty.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the member was present
+    fn set_remove_member(&self, key: String, member: &str) -> bool {
+        let Entry::Occupied(mut entry) = self.set_store.entry(key) else {
+            return false;
+        };
+
+        let removed = entry.get_mut().remove(member);
+        if entry.get().is_empty() {
+            entry.remove();
+        }
+        removed
+    }
+
+    /// Removes a field from a map, removing the map once empty.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the field was present
+    fn hash_remove_field(&self, key: String, field: &str) -> bool {
+        let Entry::Occupied(mut entry) = self.hash_store.entry(key) else {
+            return false;
+        };
+
+        let removed = entry.get_mut().remove(field).is_some();
+        if entry.get().is_empty() {
+            entry.remove();
+        }
+        removed
+    }
+
+    /// Makes room for a string entry before it is written.
+    fn admit_string(&self, key: &str) {
+        if let Some(lru) = &self.string_lru {
+            self.record_evictions(lru.admit(&self.string_store, &self.counters.string_bytes, key));
+        }
+    }
+
+    /// Evicts string entries other than `key` until the store fits `max_bytes`.
+    ///
+    /// Must be called after the write, once its entry guard is released.
+    fn shrink_strings(&self, key: &str) {
+        if let (Some(lru), Some(max_bytes)) = (&self.string_lru, self.max_bytes) {
+            self.record_evictions(lru.shrink(
+                &self.string_store,
+                &self.counters.string_bytes,
+                max_bytes,
+                key,
+            ));
+        }
+    }
+
+    /// Records a use of a string entry.
+    fn touch_string(&self, key: &str) {
+        if let Some(lru) = &self.string_lru {
+            lru.touch(key);
+        }
+    }
+
+    /// Stops tracking a string entry that has been removed.
+    fn forget_string(&self, key: &str) {
+        if let Some(lru) = &self.string_lru {
+            lru.forget(key);
+        }
+    }
+
+    /// Loads a live string value, evicting it if it has expired.
+    fn load_live_string(&self, key: &str, current_time: u64) -> Option<Arc<str>> {
+        let (value, expired) = load_live(&self.string_store, key, current_time);
+        if expired {
+            self.evict_string_if_expired(key, current_time);
+        }
+        value
+    }
+
+    /// Evicts a string entry if it has expired.
+    ///
+    /// Every eviction of a single expired string goes through here, so the
+    /// entry's bytes, its LRU tracking and the eviction count stay in step.
+    /// Must not be called while holding a guard into the string store.
+    ///
+    /// # Returns
+    /// * `bool` - Whether an entry was evicted
+    fn evict_string_if_expired(&self, key: &str, current_time: u64) -> bool {
+        let evicted = evict_if_expired(
+            &self.string_store,
+            &self.counters.string_bytes,
+            key,
+            current_time,
+        );
+        if evicted {
+            self.forget_string(key);
+            self.record_evictions(1);
+        }
+        evicted
+    }
+
+    /// Makes room for a binary entry before it is written.
+    fn admit_raw(&self, key: &str) {
+        if let Some(lru) = &self.data_lru {
+            self.record_evictions(lru.admit(&self.data_store, &self.counters.data_bytes, key));
+        }
+    }
+
+    /// Evicts binary entries other than `key` until the store fits `max_bytes`.
+    ///
+    /// Must be called after the write, once its entry guard is released.
+    fn shrink_raw(&self, key: &str) {
+        if let (Some(lru), Some(max_bytes)) = (&self.data_lru, self.max_bytes) {
+            self.record_evictions(lru.shrink(
+                &self.data_store,
+                &self.counters.data_bytes,
+                max_bytes,
+                key,
+            ));
+        }
+    }
+
+    /// Records a use of a binary entry.
+    fn touch_raw(&self, key: &str) {
+        if let Some(lru) = &self.data_lru {
+            lru.touch(key);
+        }
+    }
+
+    /// Stops tracking a binary entry that has been removed.
+    fn forget_raw(&self, key: &str) {
+        if let Some(lru) = &self.data_lru {
+            lru.forget(key);
+        }
+    }
+
+    /// Loads a live binary value, evicting it if it has expired.
+    fn load_live_raw(&self, key: &str, current_time: u64) -> Option<Vec<u8>> {
+        let (value, expired) = load_live(&self.data_store, key, current_time);
+        if expired {
+            self.evict_raw_if_expired(key, current_time);
+        }
+        value
+    }
+
+    /// Evicts a binary entry if it has expired.
+    ///
+    /// The binary counterpart of [`IMCModule::evict_string_if_expired`].
+    ///
+    /// # Returns
+    /// * `bool` - Whether an entry was evicted
+    fn evict_raw_if_expired(&self, key: &str, current_time: u64) -> bool {
+        let evicted = evict_if_expired(
+            &self.data_store,
+            &self.counters.data_bytes,
+            key,
+            current_time,
+        );
+        if evicted {
+            self.forget_raw(key);
+            self.record_evictions(1);
+        }
+        evicted
+    }
+
+    /// Notes a write to a string or binary key, if tracking is enabled.
+    fn mark_modified(&self, key: &str) {
+        let Some(last_modified) = &self.last_modified else {
+            return;
+        };
+        if let Ok(now) = self.now_millis() {
+            last_modified.insert(key.to_string(), now);
+        }
+    }
+
+    /// Counts a load of a string or binary value as a hit or a miss.
+    fn record_load(&self, hit: bool) {
+        let counter = if hit {
+            &self.counters.hits
+        } else {
+            &self.counters.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts entries removed by the cache itself.
+    fn record_evictions(&self, count: u64) {
+        self.counters.evictions.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Gets the current Unix timestamp in milliseconds from the module's clock.
+    ///
+    /// All expiration times are kept in milliseconds, so that second and
+    /// millisecond expiries can be mixed freely.
+    fn now_millis(&self) -> Result<u64, crate::errors::StorageError> {
+        self.clock.now_millis()
+    }
+
+    /// Converts an expiry relative to now into an absolute expiration time.
+    ///
+    /// # Arguments
+    /// * `expiry` - Optional expiration duration in seconds from now
+    ///
+    /// # Returns
+    /// * `Ok(Option<u64>)` - The absolute expiration time in Unix timestamp milliseconds, if any
+    fn expires_at(&self, expiry: Option<u64>) -> Result<Option<u64>, crate::errors::StorageError> {
+        self.expires_at_ms(expiry.map(secs_to_millis))
+    }
+
+    /// Converts an expiry in milliseconds from now into an absolute expiration time.
+    ///
+    /// # Arguments
+    /// * `expiry_ms` - Optional expiration duration in milliseconds from now
+    ///
+    /// # Returns
+    /// * `Ok(Option<u64>)` - The absolute expiration time in Unix timestamp milliseconds, if any
+    fn expires_at_ms(
+        &self,
+        expiry_ms: Option<u64>,
+    ) -> Result<Option<u64>, crate::errors::StorageError> {
+        expiry_ms
+            .map(|e| Ok(self.now_millis()?.saturating_add(e)))
+            .transpose()
+    }
+
+    /// Maps a caller-provided key to the key used in the underlying stores.
+    ///
+    /// Fails with the configured validator's error if it rejects the key.
+    fn key(&self, key: impl AsRef<str>) -> Result<String, crate::errors::StorageError> {
+        let key = key.as_ref();
+        if let Some(validate) = self.key_validator {
+            validate(key)?;
+        }
+        Ok(crate::keys::hash_long_key(key, self.hash_keys_over))
+    }
+
+    /// Converts a string value into its stored representation.
+    ///
+    /// If interning is enabled, returns the pooled [`Arc<str>`] for an identical
+    /// value when one exists, adding the value to the pool otherwise.
+    fn intern(&self, value: String) -> Arc<str> {
+        match &self.interner {
+            Some(interner) => interner.intern(value),
+            None => Arc::from(value),
         }
     }
 }
 
-/// Gets the current Unix timestamp in seconds.
+/// Inserts a value while preserving the expiration of an existing entry.
+///
+/// An existing entry that has already expired is treated as absent, so the
+/// value is stored without expiration.
 ///
-/// Helper function that returns the current time as seconds since the Unix epoch.
-/// Used for calculating and checking expiration times.
+/// # Arguments
+/// * `store` - The store to insert into
+/// * `bytes` - The store's byte counter
+/// * `key` - The key under which to store the value
+/// * `value` - The value to store
+/// * `current_time` - The current Unix timestamp in milliseconds
 ///
 /// # Returns
-/// * `u64` - Current Unix timestamp in seconds
-///
-/// # Panics
-/// Panics if the system time is set to before the Unix epoch (1970-01-01 00:00:00 UTC).
-/// This could happen if:
-/// - The system clock is incorrectly set
-/// - The function is running in a time machine
-/// - The system is experiencing severe clock skew
-fn now() -> u64 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .expect("Time went backwards")
-        .as_secs()
+/// * `StoreState::New` - If the key did not exist or had expired
+/// * `StoreState::Updated` - If the key existed and was updated
+fn insert_keep_ttl<V: ValueBytes>(
+    store: &DashMap<String, (V, Option<u64>)>,
+    bytes: &AtomicI64,
+    key: String,
+    value: V,
+    current_time: u64,
+) -> crate::types::StoreState {
+    let size = key.len() + value.value_bytes();
+    match store.entry(key) {
+        Entry::Occupied(mut entry) => {
+            let (state, expiry) = match entry.get().1 {
+                Some(expiry) if is_expired(expiry, current_time) => {
+                    (crate::types::StoreState::New, None)
+                }
+                expiry => (crate::types::StoreState::Updated, expiry),
+            };
+            let (previous, _) = entry.insert((value, expiry));
+            adjust_bytes(bytes, entry.key().len() + previous.value_bytes(), size);
+            state
+        }
+        Entry::Vacant(entry) => {
+            entry.insert((value, None));
+            adjust_bytes(bytes, 0, size);
+            crate::types::StoreState::New
+        }
+    }
+}
+
+/// Removes an entry if it has expired, re-checking the expiry under the write lock.
+///
+/// # Arguments
+/// * `store` - The store to remove the entry from
+/// * `bytes` - The store's byte counter
+/// * `key` - The key to check
+/// * `current_time` - The current Unix timestamp in milliseconds
+///
+/// # Returns
+/// * `bool` - Whether an expired entry was removed
+fn evict_if_expired<V: ValueBytes>(
+    store: &DashMap<String, (V, Option<u64>)>,
+    bytes: &AtomicI64,
+    key: &str,
+    current_time: u64,
+) -> bool {
+    released(
+        bytes,
+        store.remove_if(
+            key,
+            |_, (_, expiry)| matches!(expiry, Some(expiry) if is_expired(*expiry, current_time)),
+        ),
+    )
+    .is_some()
+}
+
+/// Loads a value unless it has expired.
+///
+/// The value is cloned out and the read guard released before returning, so
+/// the caller can evict an expired entry; removing while still holding the
+/// guard would deadlock on the shard lock.
+///
+/// # Arguments
+/// * `store` - The store to load from
+/// * `key` - The key whose value should be loaded
+/// * `current_time` - The current Unix timestamp in milliseconds
+///
+/// # Returns
+/// * `(Option<V>, bool)` - The value if it is live, and whether the entry has expired
+fn load_live<V: Clone>(
+    store: &DashMap<String, (V, Option<u64>)>,
+    key: &str,
+    current_time: u64,
+) -> (Option<V>, bool) {
+    match store.get(key) {
+        None => (None, false),
+        Some(entry) => match entry.value() {
+            (_, Some(expiry)) if is_expired(*expiry, current_time) => (None, true),
+            (value, _) => (Some(value.clone()), false),
+        },
+    }
+}
+
+/// Checks whether an absolute expiration time has been reached.
+///
+/// An entry is expired from the millisecond its expiration time is reached,
+/// so a value stored with an expiry of `0` is never returned.
+///
+/// Every expiry check in this module goes through this function so that
+/// loads, existence checks and listings agree on when an entry is expired.
+///
+/// # Arguments
+/// * `expiry` - The absolute expiration time in Unix timestamp milliseconds
+/// * `current_time` - The current Unix timestamp in milliseconds
+///
+/// # Returns
+/// * `bool` - Whether the entry has expired
+fn is_expired(expiry: u64, current_time: u64) -> bool {
+    expiry <= current_time
+}
+
+/// Converts a duration in seconds to milliseconds, saturating on overflow.
+fn secs_to_millis(secs: u64) -> u64 {
+    secs.saturating_mul(1000)
+}
+
+/// Converts a remaining duration in milliseconds to seconds, rounding up.
+///
+/// Rounding up keeps a value that hasn't expired yet from reporting `0`
+/// seconds left.
+fn millis_to_secs(millis: u64) -> u64 {
+    millis.saturating_add(999) / 1000
+}
+
+/// Adds `delta` to an atomic integer unless the result would overflow.
+///
+/// The addition is applied with a compare-exchange loop, so on overflow the
+/// stored value is left unchanged instead of wrapping around.
+///
+/// # Returns
+/// * `Ok(i64)` - The new value after the addition
+/// * `Err(StorageError::Overflow)` - If the result is outside the `i64` range
+fn checked_increment(atomic: &AtomicI64, delta: i64) -> Result<i64, crate::errors::StorageError> {
+    let mut current = atomic.load(Ordering::SeqCst);
+    loop {
+        let updated = current
+            .checked_add(delta)
+            .ok_or_else(|| crate::errors::StorageError::Overflow(format!("{current} + {delta}")))?;
+        match atomic.compare_exchange_weak(current, updated, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(_) => return Ok(updated),
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+/// Adds `delta` to an atomic integer and clamps the result into `[min, max]`.
+///
+/// Like [`checked_increment`], the update is a compare-exchange loop, so the
+/// clamp is applied atomically with the addition.
+///
+/// # Returns
+/// * `Ok(i64)` - The new, clamped value
+/// * `Err(StorageError::Overflow)` - If the unclamped sum is outside the `i64` range
+fn bounded_increment(
+    atomic: &AtomicI64,
+    delta: i64,
+    min: i64,
+    max: i64,
+) -> Result<i64, crate::errors::StorageError> {
+    let mut current = atomic.load(Ordering::SeqCst);
+    loop {
+        let updated = current
+            .checked_add(delta)
+            .ok_or_else(|| crate::errors::StorageError::Overflow(format!("{current} + {delta}")))?
+            .clamp(min, max);
+        match atomic.compare_exchange_weak(current, updated, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(_) => return Ok(updated),
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+/// Replaces the entry under `to` with the one under `from`, if any.
+///
+/// An existing entry under `to` is removed even if `from` has no entry.
+///
+/// # Returns
+/// * `bool` - Whether an entry was moved
+fn move_entry<V>(store: &DashMap<String, V>, from: &str, to: &str) -> bool {
+    store.remove(to);
+    match store.remove(from) {
+        Some((_, value)) => {
+            store.insert(to.to_string(), value);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Like [`move_entry`], for a string or binary store whose size is tracked.
+fn move_sized_entry<V: ValueBytes>(
+    store: &DashMap<String, (V, Option<u64>)>,
+    bytes: &AtomicI64,
+    from: &str,
+    to: &str,
+) -> bool {
+    released(bytes, store.remove(to));
+    match released(bytes, store.remove(from)) {
+        Some((_, value)) => {
+            insert_sized(store, bytes, to.to_string(), value);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Removes every entry whose key starts with `prefix`.
+///
+/// # Arguments
+/// * `store` - The store to remove entries from
+/// * `prefix` - The prefix that removed keys must start with
+/// * `counted` - Called with each removed entry; whether it should be reported,
+///   e.g. because it hadn't expired
+///
+/// # Returns
+/// * `Vec<String>` - The keys of the removed entries that were counted
+fn retain_unprefixed<V>(
+    store: &DashMap<String, V>,
+    prefix: &str,
+    mut counted: impl FnMut(&str, &V) -> bool,
+) -> Vec<String> {
+    let mut removed = Vec::new();
+    store.retain(|key, value| {
+        if !key.starts_with(prefix) {
+            return true;
+        }
+        if counted(key, value) {
+            removed.push(key.clone());
+        }
+        false
+    });
+    removed
+}
+
+/// Size of a string or binary value, as counted against `max_bytes`.
+trait ValueBytes {
+    fn value_bytes(&self) -> usize;
+}
+
+impl ValueBytes for Arc<str> {
+    fn value_bytes(&self) -> usize {
+        self.len()
+    }
+}
+
+impl ValueBytes for Vec<u8> {
+    fn value_bytes(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Inserts an entry into a string or binary store, updating its byte counter.
+///
+/// Replacing an entry only adds the difference in size.
+///
+/// # Returns
+/// * `Option<(V, Option<u64>)>` - The replaced value and expiration, if any
+fn insert_sized<V: ValueBytes>(
+    store: &DashMap<String, (V, Option<u64>)>,
+    bytes: &AtomicI64,
+    key: String,
+    value: (V, Option<u64>),
+) -> Option<(V, Option<u64>)> {
+    let key_len = key.len();
+    let size = key_len + value.0.value_bytes();
+    let previous = store.insert(key, value);
+    let freed = previous
+        .as_ref()
+        .map_or(0, |(previous, _)| key_len + previous.value_bytes());
+    adjust_bytes(bytes, freed, size);
+    previous
+}
+
+/// Subtracts an entry removed from a string or binary store from its byte counter.
+///
+/// Passes the removal's result through, so it can wrap the removing call.
+fn released<V: ValueBytes>(
+    bytes: &AtomicI64,
+    removed: Option<(String, (V, Option<u64>))>,
+) -> Option<(String, (V, Option<u64>))> {
+    if let Some((key, entry)) = &removed {
+        release_bytes(bytes, key, entry);
+    }
+    removed
+}
+
+/// Subtracts a removed entry from a byte counter.
+fn release_bytes<V: ValueBytes>(bytes: &AtomicI64, key: &str, entry: &(V, Option<u64>)) {
+    adjust_bytes(bytes, key.len() + entry.0.value_bytes(), 0);
+}
+
+/// Updates a byte counter after an entry of `freed` bytes became one of `used` bytes.
+fn adjust_bytes(bytes: &AtomicI64, freed: usize, used: usize) {
+    bytes.fetch_add(used as i64 - freed as i64, Ordering::Relaxed);
+}
+
+/// Reads a byte counter, hiding transient negative values.
+fn byte_count(bytes: &AtomicI64) -> usize {
+    bytes.load(Ordering::Relaxed).max(0) as usize
+}
+
+/// Creates an empty shared map with the configured number of shards.
+///
+/// # Arguments
+/// * `shard_amount` - The number of shards, or `None` for DashMap's default
+/// * `capacity` - The number of entries the map can hold before growing
+///
+/// # Returns
+/// * `ArcDashMap<K, V>` - The new map
+fn new_map<K: Eq + std::hash::Hash, V>(
+    shard_amount: Option<usize>,
+    capacity: usize,
+) -> ArcDashMap<K, V> {
+    Arc::new(match shard_amount {
+        Some(amount) => DashMap::with_capacity_and_shard_amount(capacity, amount),
+        None => DashMap::with_capacity(capacity),
+    })
+}
+
+/// Removes every entry whose expiration time has passed.
+///
+/// Removed keys are also dropped from the store's LRU tracker, if any, unless
+/// they were written again during the sweep. Must not be called while holding
+/// a guard into `store`.
+///
+/// # Returns
+/// * `u64` - The number of entries removed
+#[cfg(feature = "async")]
+fn evict_expired<V: ValueBytes>(
+    store: &DashMap<String, (V, Option<u64>)>,
+    bytes: &AtomicI64,
+    lru: Option<&Lru>,
+    current_time: u64,
+) -> u64 {
+    let since = lru.map(Lru::tick);
+    let mut evicted = Vec::new();
+    store.retain(|key, entry| match entry.1 {
+        Some(expiry) if is_expired(expiry, current_time) => {
+            release_bytes(bytes, key, entry);
+            evicted.push(key.clone());
+            false
+        }
+        _ => true,
+    });
+    // The LRU is only touched once retain has released its shard locks, so a
+    // key may have been written again in between and must stay tracked
+    if let (Some(lru), Some(since)) = (lru, since) {
+        lru.forget_swept(store, &evicted, since);
+    }
+    evicted.len() as u64
+}
+
+/// Handle to the background task that evicts expired entries.
+///
+/// Aborts the task when dropped.
+#[cfg(feature = "async")]
+struct Sweeper(tokio::task::JoinHandle<()>);
+
+#[cfg(feature = "async")]
+impl Sweeper {
+    /// Spawns a task sweeping both stores every `interval`.
+    ///
+    /// Each sweep runs on Tokio's blocking thread pool.
+    fn spawn(
+        interval: std::time::Duration,
+        string_store: ArcDashMap<String, (Arc<str>, Option<u64>)>,
+        data_store: ArcDashMap<String, (Vec<u8>, Option<u64>)>,
+        string_lru: Option<Arc<Lru>>,
+        data_lru: Option<Arc<Lru>>,
+        counters: Arc<Counters>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                // Skip this sweep if the clock can't be read; entries are still checked on access
+                let Ok(current_time) = clock.now_millis() else {
+                    continue;
+                };
+                let (string_store, data_store) = (string_store.clone(), data_store.clone());
+                let (string_lru, data_lru) = (string_lru.clone(), data_lru.clone());
+                let counters = counters.clone();
+                // Each shard is locked while it is checked, so keep the scan off the async workers
+                let sweep = tokio::task::spawn_blocking(move || {
+                    let evicted = evict_expired(
+                        &string_store,
+                        &counters.string_bytes,
+                        string_lru.as_deref(),
+                        current_time,
+                    ) + evict_expired(
+                        &data_store,
+                        &counters.data_bytes,
+                        data_lru.as_deref(),
+                        current_time,
+                    );
+                    counters.evictions.fetch_add(evicted, Ordering::Relaxed);
+                });
+                // A panicking sweep is retried on the next tick
+                let _ = sweep.await;
+            }
+        }))
+    }
+}
+
+#[cfg(feature = "async")]
+impl Drop for Sweeper {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
 }
@@ -0,0 +1,127 @@
+//! Saving the string and binary stores to disk and restoring them.
+//!
+//! Snapshots are encoded with bincode and keep each entry's absolute
+//! expiration time, so entries that expire while the process is down are
+//! dropped when the snapshot is loaded again.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{insert_sized, is_expired, IMCConfig, IMCModule};
+use crate::codec::{BincodeCodec, Codec};
+use crate::errors::StorageError;
+
+/// Version of the snapshot format, bumped on incompatible changes.
+const FORMAT_VERSION: u32 = 1;
+
+/// Contents of a snapshot file.
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    /// The format version the snapshot was written with
+    version: u32,
+    /// String entries as (key, value, expiration time in Unix timestamp milliseconds)
+    strings: Vec<(String, String, Option<u64>)>,
+    /// Binary entries as (key, value, expiration time in Unix timestamp milliseconds)
+    data: Vec<(String, Vec<u8>, Option<u64>)>,
+}
+
+impl IMCModule {
+    /// Writes the live string and binary entries to a file.
+    ///
+    /// The snapshot is written to a temporary file next to `path` and then
+    /// renamed over it, so an interrupted save never leaves a truncated file
+    /// behind. Other stores, such as atomic integers and lists, are not saved.
+    ///
+    /// # Arguments
+    /// * `path` - The file to write the snapshot to
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the snapshot was written
+    /// * `Err(StorageError::Io)` - If the file cannot be written
+    #[cfg(feature = "async")]
+    pub async fn save_to_file(&self, path: &Path) -> Result<(), StorageError> {
+        let this = self.clone();
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || this.write_snapshot(&path))
+            .await
+            .unwrap_or_else(|e| Err(StorageError::JoinError(e)))
+    }
+
+    /// Creates a module from a snapshot written by [`IMCModule::save_to_file`].
+    ///
+    /// Entries whose expiration time has already passed are skipped. Keys are
+    /// restored as stored, so `hash_keys_over` should match the configuration
+    /// the snapshot was saved with.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration for the new module
+    /// * `path` - The snapshot file to read
+    ///
+    /// # Returns
+    /// * `Ok(Self)` - The module holding the snapshot's live entries
+    /// * `Err(StorageError::Io)` - If the file cannot be read
+    /// * `Err(StorageError::DeserializationError)` - If the file isn't a valid snapshot
+    pub fn load_from_file(config: IMCConfig, path: &Path) -> Result<Self, StorageError> {
+        let bytes = std::fs::read(path)?;
+        let snapshot: Snapshot = BincodeCodec.decode(&bytes)?;
+        if snapshot.version != FORMAT_VERSION {
+            return Err(StorageError::DeserializationError(format!(
+                "unsupported snapshot version {}",
+                snapshot.version
+            )));
+        }
+
+        let module = Self::new(config);
+        let current_time = module.now_millis()?;
+        let live = |expiry: &Option<u64>| !expiry.is_some_and(|e| is_expired(e, current_time));
+
+        for (key, value, expiry) in snapshot.strings {
+            if live(&expiry) {
+                module.string_store_until(key, value, expiry);
+            }
+        }
+        for (key, value, expiry) in snapshot.data {
+            if live(&expiry) {
+                module.admit_raw(&key);
+                insert_sized(
+                    &module.data_store,
+                    &module.counters.data_bytes,
+                    key.clone(),
+                    (value, expiry),
+                );
+                module.shrink_raw(&key);
+            }
+        }
+
+        Ok(module)
+    }
+
+    /// Encodes the live entries and atomically replaces the file at `path`.
+    #[cfg(feature = "async")]
+    fn write_snapshot(&self, path: &Path) -> Result<(), StorageError> {
+        let current_time = self.now_millis()?;
+        let data = self
+            .data_store
+            .iter()
+            .filter(|entry| !entry.value().1.is_some_and(|e| is_expired(e, current_time)))
+            .map(|entry| {
+                let (value, expiry) = entry.value();
+                (entry.key().clone(), value.clone(), *expiry)
+            })
+            .collect();
+
+        let snapshot = Snapshot {
+            version: FORMAT_VERSION,
+            strings: self.snapshot_strings()?,
+            data,
+        };
+        let bytes = BincodeCodec.encode(&snapshot)?;
+
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        std::fs::write(&temp, bytes)?;
+        std::fs::rename(&temp, path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,393 @@
+//! An in-memory backend that records the operations performed on it.
+//!
+//! [`RecordingStorage`] behaves exactly like the [`IMCModule`] it wraps, and
+//! additionally logs every call of the string, raw and atomic storage traits,
+//! so tests can assert e.g. that a key was stored with a 60 second TTL.
+
+use std::sync::{Arc, Mutex};
+
+use super::IMCModule;
+
+/// A single operation performed on a [`RecordingStorage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    /// The trait method that was called, e.g. `"store_with_expiry"`
+    pub op: &'static str,
+    /// The key as passed by the caller
+    pub key: String,
+    /// The expiry passed to the call, in seconds; `None` for calls without one
+    pub expiry: Option<u64>,
+}
+
+/// [`IMCModule`] wrapper that records every storage call it receives.
+///
+/// Clones share the same call log, so a test can keep one handle for
+/// assertions while the code under test uses another.
+#[derive(Clone)]
+pub struct RecordingStorage {
+    /// The backend every call is delegated to
+    inner: IMCModule,
+    /// Calls received so far, oldest first
+    calls: Arc<Mutex<Vec<Call>>>,
+}
+
+impl RecordingStorage {
+    /// Wraps an in-memory backend.
+    ///
+    /// # Arguments
+    /// * `inner` - The backend to delegate to
+    ///
+    /// # Returns
+    /// * `Self` - A new recorder with an empty call log
+    pub fn new(inner: IMCModule) -> Self {
+        Self {
+            inner,
+            calls: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Returns a reference to the wrapped backend.
+    ///
+    /// Calls made directly on it are not recorded.
+    pub fn inner(&self) -> &IMCModule {
+        &self.inner
+    }
+
+    /// Returns the calls received so far, oldest first.
+    pub fn calls(&self) -> Vec<Call> {
+        self.lock().clone()
+    }
+
+    /// Empties the call log.
+    pub fn clear_calls(&self) {
+        self.lock().clear();
+    }
+
+    fn record(&self, op: &'static str, key: &str, expiry: Option<u64>) {
+        self.lock().push(Call {
+            op,
+            key: key.to_string(),
+            expiry,
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Call>> {
+        // A panicking test may poison the lock; the log itself stays valid
+        self.calls.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use async_trait::async_trait;
+
+    use super::RecordingStorage;
+    use crate::{
+        asynchronous::{
+            AtomicStorage, RawStorage, RawStorageWithExpiry, StringStorage, StringStorageWithExpiry,
+        },
+        errors::StorageError,
+        types::StoreState,
+    };
+
+    #[async_trait]
+    impl StringStorageWithExpiry for RecordingStorage {
+        async fn store_with_expiry(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: String,
+            expiry: Option<u64>,
+        ) -> Result<StoreState, StorageError> {
+            let key = key.as_ref();
+            self.record("store_with_expiry", key, expiry);
+            self.inner.store_with_expiry(key, value, expiry).await
+        }
+
+        async fn store_keep_ttl(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: String,
+        ) -> Result<StoreState, StorageError> {
+            let key = key.as_ref();
+            self.record("store_keep_ttl", key, None);
+            self.inner.store_keep_ttl(key, value).await
+        }
+    }
+
+    #[async_trait]
+    impl StringStorage for RecordingStorage {
+        async fn load_string(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<String>, StorageError> {
+            let key = key.as_ref();
+            self.record("load_string", key, None);
+            self.inner.load_string(key).await
+        }
+
+        async fn delete_string(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+            let key = key.as_ref();
+            self.record("delete_string", key, None);
+            self.inner.delete_string(key).await
+        }
+
+        async fn take_string(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<String>, StorageError> {
+            let key = key.as_ref();
+            self.record("take_string", key, None);
+            self.inner.take_string(key).await
+        }
+    }
+
+    #[async_trait]
+    impl RawStorageWithExpiry for RecordingStorage {
+        async fn store_raw_with_expiry(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: Vec<u8>,
+            expiry: Option<u64>,
+        ) -> Result<StoreState, StorageError> {
+            let key = key.as_ref();
+            self.record("store_raw_with_expiry", key, expiry);
+            self.inner.store_raw_with_expiry(key, value, expiry).await
+        }
+
+        async fn store_raw_keep_ttl(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: Vec<u8>,
+        ) -> Result<StoreState, StorageError> {
+            let key = key.as_ref();
+            self.record("store_raw_keep_ttl", key, None);
+            self.inner.store_raw_keep_ttl(key, value).await
+        }
+    }
+
+    #[async_trait]
+    impl RawStorage for RecordingStorage {
+        async fn load_raw(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<Vec<u8>>, StorageError> {
+            let key = key.as_ref();
+            self.record("load_raw", key, None);
+            self.inner.load_raw(key).await
+        }
+
+        async fn delete_raw(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+            let key = key.as_ref();
+            self.record("delete_raw", key, None);
+            self.inner.delete_raw(key).await
+        }
+    }
+
+    #[async_trait]
+    impl AtomicStorage for RecordingStorage {
+        async fn atomic_store(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: i64,
+        ) -> Result<StoreState, StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_store", key, None);
+            self.inner.atomic_store(key, value).await
+        }
+
+        async fn atomic_load(
+            &self,
+            key: impl AsRef<str> + Send,
+        ) -> Result<Option<i64>, StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_load", key, None);
+            self.inner.atomic_load(key).await
+        }
+
+        async fn atomic_delete(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_delete", key, None);
+            self.inner.atomic_delete(key).await
+        }
+
+        async fn atomic_increment(
+            &self,
+            key: impl AsRef<str> + Send,
+            value: i64,
+        ) -> Result<Option<i64>, StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_increment", key, None);
+            self.inner.atomic_increment(key, value).await
+        }
+
+        async fn atomic_increment_or_init(
+            &self,
+            key: impl AsRef<str> + Send,
+            delta: i64,
+            init: i64,
+        ) -> Result<i64, StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_increment_or_init", key, None);
+            self.inner.atomic_increment_or_init(key, delta, init).await
+        }
+
+        async fn atomic_compare_and_swap(
+            &self,
+            key: impl AsRef<str> + Send,
+            expected: i64,
+            new: i64,
+        ) -> Result<bool, StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_compare_and_swap", key, None);
+            self.inner.atomic_compare_and_swap(key, expected, new).await
+        }
+    }
+}
+
+#[cfg(feature = "sync")]
+mod sync_impl {
+    use super::RecordingStorage;
+    use crate::{
+        errors::StorageError,
+        sync::{
+            AtomicStorage, RawStorage, RawStorageWithExpiry, StringStorage, StringStorageWithExpiry,
+        },
+        types::StoreState,
+    };
+
+    impl StringStorageWithExpiry for RecordingStorage {
+        fn store_with_expiry(
+            &self,
+            key: impl AsRef<str>,
+            value: String,
+            expiry: Option<u64>,
+        ) -> Result<StoreState, StorageError> {
+            let key = key.as_ref();
+            self.record("store_with_expiry", key, expiry);
+            self.inner.store_with_expiry(key, value, expiry)
+        }
+
+        fn store_keep_ttl(
+            &self,
+            key: impl AsRef<str>,
+            value: String,
+        ) -> Result<StoreState, StorageError> {
+            let key = key.as_ref();
+            self.record("store_keep_ttl", key, None);
+            self.inner.store_keep_ttl(key, value)
+        }
+    }
+
+    impl StringStorage for RecordingStorage {
+        fn load_string(&self, key: impl AsRef<str>) -> Result<Option<String>, StorageError> {
+            let key = key.as_ref();
+            self.record("load_string", key, None);
+            self.inner.load_string(key)
+        }
+
+        fn delete_string(&self, key: impl AsRef<str>) -> Result<(), StorageError> {
+            let key = key.as_ref();
+            self.record("delete_string", key, None);
+            self.inner.delete_string(key)
+        }
+
+        fn take_string(&self, key: impl AsRef<str>) -> Result<Option<String>, StorageError> {
+            let key = key.as_ref();
+            self.record("take_string", key, None);
+            self.inner.take_string(key)
+        }
+    }
+
+    impl RawStorageWithExpiry for RecordingStorage {
+        fn store_raw_with_expiry(
+            &self,
+            key: impl AsRef<str>,
+            value: Vec<u8>,
+            expiry: Option<u64>,
+        ) -> Result<StoreState, StorageError> {
+            let key = key.as_ref();
+            self.record("store_raw_with_expiry", key, expiry);
+            self.inner.store_raw_with_expiry(key, value, expiry)
+        }
+
+        fn store_raw_keep_ttl(
+            &self,
+            key: impl AsRef<str>,
+            value: Vec<u8>,
+        ) -> Result<StoreState, StorageError> {
+            let key = key.as_ref();
+            self.record("store_raw_keep_ttl", key, None);
+            self.inner.store_raw_keep_ttl(key, value)
+        }
+    }
+
+    impl RawStorage for RecordingStorage {
+        fn load_raw(&self, key: impl AsRef<str>) -> Result<Option<Vec<u8>>, StorageError> {
+            let key = key.as_ref();
+            self.record("load_raw", key, None);
+            self.inner.load_raw(key)
+        }
+
+        fn delete_raw(&self, key: impl AsRef<str>) -> Result<(), StorageError> {
+            let key = key.as_ref();
+            self.record("delete_raw", key, None);
+            self.inner.delete_raw(key)
+        }
+    }
+
+    impl AtomicStorage for RecordingStorage {
+        fn atomic_store(
+            &self,
+            key: impl AsRef<str>,
+            value: i64,
+        ) -> Result<StoreState, StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_store", key, None);
+            self.inner.atomic_store(key, value)
+        }
+
+        fn atomic_load(&self, key: impl AsRef<str>) -> Result<Option<i64>, StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_load", key, None);
+            self.inner.atomic_load(key)
+        }
+
+        fn atomic_delete(&self, key: impl AsRef<str>) -> Result<(), StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_delete", key, None);
+            self.inner.atomic_delete(key)
+        }
+
+        fn atomic_increment(
+            &self,
+            key: impl AsRef<str>,
+            value: i64,
+        ) -> Result<Option<i64>, StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_increment", key, None);
+            self.inner.atomic_increment(key, value)
+        }
+
+        fn atomic_increment_or_init(
+            &self,
+            key: impl AsRef<str>,
+            delta: i64,
+            init: i64,
+        ) -> Result<i64, StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_increment_or_init", key, None);
+            self.inner.atomic_increment_or_init(key, delta, init)
+        }
+
+        fn atomic_compare_and_swap(
+            &self,
+            key: impl AsRef<str>,
+            expected: i64,
+            new: i64,
+        ) -> Result<bool, StorageError> {
+            let key = key.as_ref();
+            self.record("atomic_compare_and_swap", key, None);
+            self.inner.atomic_compare_and_swap(key, expected, new)
+        }
+    }
+}
@@ -0,0 +1,565 @@
+use std::sync::Arc;
+
+use super::{is_expired, IMCConfig, IMCModule};
+use crate::asynchronous::{
+    AdminStorage, AtomicStorage, FloatAtomicStorage, HashStorage, KeyExists, KeyRename,
+    ListStorage, RawStorage, RawStorageWithExpiry, SetStorage, StringExpiry, StringStorage,
+    StringStorageWithExpiry,
+};
+use crate::clock::MockClock;
+use crate::errors::StorageError;
+
+/// Creates a module whose clock only moves when the returned handle is advanced.
+fn module(config: IMCConfig) -> (IMCModule, MockClock) {
+    let clock = MockClock::new(1_000_000);
+    (IMCModule::with_clock(config, clock.clone()), clock)
+}
+
+#[tokio::test]
+async fn interned_values_share_one_allocation() {
+    let storage = IMCModule::new(IMCConfig {
+        intern_values: true,
+        ..IMCConfig::default()
+    });
+
+    for (key, value) in [("a", "active"), ("b", "active"), ("c", "inactive")] {
+        storage.store_string(key, value.to_string()).await.unwrap();
+    }
+
+    let value = |key: &str| storage.string_store.get(key).unwrap().0.clone();
+    assert!(Arc::ptr_eq(&value("a"), &value("b")));
+    assert!(!Arc::ptr_eq(&value("a"), &value("c")));
+    assert_eq!(
+        storage.load_string("b").await.unwrap().as_deref(),
+        Some("active")
+    );
+}
+
+#[tokio::test]
+async fn values_are_not_shared_without_interning() {
+    let storage = IMCModule::new(IMCConfig::default());
+
+    for key in ["a", "b"] {
+        storage
+            .store_string(key, "active".to_string())
+            .await
+            .unwrap();
+    }
+
+    let value = |key: &str| storage.string_store.get(key).unwrap().0.clone();
+    assert!(!Arc::ptr_eq(&value("a"), &value("b")));
+}
+
+#[tokio::test]
+async fn intern_pool_drops_replaced_values() {
+    let storage = IMCModule::new(IMCConfig {
+        intern_values: true,
+        ..IMCConfig::default()
+    });
+
+    // Every write replaces the previous value, which leaves it unused in the pool
+    for i in 0..1_000 {
+        storage
+            .store_string("key", format!("value-{i}"))
+            .await
+            .unwrap();
+    }
+
+    let pooled = storage.interner.as_ref().unwrap().len();
+    assert!(pooled <= 128, "pool kept {pooled} values for one key");
+}
+
+#[test]
+fn entries_expire_at_their_expiration_time() {
+    assert!(!is_expired(1_001, 1_000));
+    assert!(is_expired(1_000, 1_000));
+    assert!(is_expired(999, 1_000));
+}
+
+#[tokio::test]
+async fn zero_or_past_expiry_is_never_returned() {
+    let (storage, clock) = module(IMCConfig::default());
+    storage
+        .store_with_expiry("zero", "a".to_string(), Some(0))
+        .await
+        .unwrap();
+    storage
+        .store_with_expiry("past", "b".to_string(), Some(1))
+        .await
+        .unwrap();
+    clock.advance(1);
+
+    for key in ["zero", "past"] {
+        assert_eq!(storage.load_string(key).await.unwrap(), None);
+        // The load evicts the expired entry
+        assert!(!storage.string_store.contains_key(key));
+    }
+}
+
+#[tokio::test]
+async fn persist_removes_the_expiration() {
+    let storage = IMCModule::new(IMCConfig::default());
+    storage
+        .store_with_expiry("token", "abc".to_string(), Some(60))
+        .await
+        .unwrap();
+    assert!(storage.ttl("token").await.unwrap().is_some());
+
+    assert!(storage.persist("token").await.unwrap());
+    assert_eq!(storage.ttl("token").await.unwrap(), None);
+    assert_eq!(
+        storage.load_string("token").await.unwrap().as_deref(),
+        Some("abc")
+    );
+}
+
+#[tokio::test]
+async fn persist_reports_keys_without_expiration() {
+    let storage = IMCModule::new(IMCConfig::default());
+    storage
+        .store_string("permanent", "abc".to_string())
+        .await
+        .unwrap();
+    storage
+        .store_with_expiry("expired", "abc".to_string(), Some(0))
+        .await
+        .unwrap();
+
+    assert!(!storage.persist("permanent").await.unwrap());
+    assert!(!storage.persist("expired").await.unwrap());
+    assert!(!storage.persist("missing").await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn only_one_concurrent_taker_gets_the_value() {
+    let storage = IMCModule::new(IMCConfig::default());
+
+    for i in 0..100 {
+        let key = format!("token-{i}");
+        storage
+            .store_string(&key, "secret".to_string())
+            .await
+            .unwrap();
+
+        let takers: Vec<_> = (0..2)
+            .map(|_| {
+                let (storage, key) = (storage.clone(), key.clone());
+                tokio::spawn(async move { storage.take_string(key).await.unwrap() })
+            })
+            .collect();
+
+        let mut taken = 0;
+        for taker in takers {
+            if taker.await.unwrap().is_some() {
+                taken += 1;
+            }
+        }
+        assert_eq!(taken, 1, "{key} was taken {taken} times");
+        assert_eq!(storage.load_string(&key).await.unwrap(), None);
+    }
+}
+
+#[tokio::test]
+async fn take_ignores_expired_values() {
+    let storage = IMCModule::new(IMCConfig::default());
+    storage
+        .store_with_expiry("token", "secret".to_string(), Some(0))
+        .await
+        .unwrap();
+
+    assert_eq!(storage.take_string("token").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn increment_creates_a_missing_key() {
+    let storage = IMCModule::new(IMCConfig::default());
+
+    assert_eq!(storage.atomic_increment("hits", 5).await.unwrap(), Some(5));
+    assert_eq!(storage.atomic_increment("hits", 2).await.unwrap(), Some(7));
+    assert_eq!(storage.atomic_load("hits").await.unwrap(), Some(7));
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn sync_increment_creates_a_missing_key() {
+    use crate::sync::AtomicStorage as SyncAtomicStorage;
+
+    let storage = IMCModule::new(IMCConfig::default());
+
+    assert_eq!(
+        SyncAtomicStorage::atomic_increment(&storage, "hits", 5).unwrap(),
+        Some(5)
+    );
+    assert_eq!(
+        SyncAtomicStorage::atomic_load(&storage, "hits").unwrap(),
+        Some(5)
+    );
+}
+
+#[tokio::test]
+async fn float_nan_round_trips_and_stays_nan() {
+    let storage = IMCModule::new(IMCConfig::default());
+
+    storage.atomic_store_f64("sum", f64::NAN).await.unwrap();
+    assert!(storage
+        .atomic_load_f64("sum")
+        .await
+        .unwrap()
+        .unwrap()
+        .is_nan());
+    assert!(storage
+        .atomic_increment_f64("sum", 1.0)
+        .await
+        .unwrap()
+        .is_nan());
+}
+
+#[tokio::test]
+async fn float_infinities_follow_ieee_arithmetic() {
+    let storage = IMCModule::new(IMCConfig::default());
+
+    storage.atomic_store_f64("sum", f64::MAX).await.unwrap();
+    assert_eq!(
+        storage.atomic_increment_f64("sum", f64::MAX).await.unwrap(),
+        f64::INFINITY
+    );
+    assert_eq!(
+        storage.atomic_increment_f64("sum", 1.0).await.unwrap(),
+        f64::INFINITY
+    );
+    assert!(storage
+        .atomic_increment_f64("sum", f64::NEG_INFINITY)
+        .await
+        .unwrap()
+        .is_nan());
+}
+
+#[tokio::test]
+async fn stats_count_hits_misses_and_evictions() {
+    let storage = IMCModule::new(IMCConfig {
+        max_entries: Some(2),
+        ..IMCConfig::default()
+    });
+    storage.store_string("a", "1".to_string()).await.unwrap();
+    storage.store_string("b", "2".to_string()).await.unwrap();
+    storage.store_raw("raw", vec![1, 2, 3]).await.unwrap();
+
+    let stats = storage.stats();
+    assert_eq!((stats.hits, stats.misses, stats.evictions), (0, 0, 0));
+    assert_eq!(stats.len, 3);
+
+    // The string store is full, so its least recently used entry makes room
+    storage.store_string("c", "3".to_string()).await.unwrap();
+    storage.load_string("b").await.unwrap();
+    storage.load_raw("raw").await.unwrap();
+    storage.load_string("a").await.unwrap();
+
+    let stats = storage.stats();
+    assert_eq!((stats.hits, stats.misses, stats.evictions), (2, 1, 1));
+    assert_eq!(stats.len, 3);
+}
+
+#[tokio::test]
+async fn clear_empties_every_store() {
+    let storage = IMCModule::new(IMCConfig::default());
+    storage.store_string("s", "a".to_string()).await.unwrap();
+    storage.store_raw("r", vec![1]).await.unwrap();
+    storage.atomic_store("n", 1).await.unwrap();
+    storage.atomic_store_f64("f", 1.5).await.unwrap();
+
+    AdminStorage::clear(&storage).await.unwrap();
+
+    assert_eq!(storage.load_string("s").await.unwrap(), None);
+    assert_eq!(storage.load_raw("r").await.unwrap(), None);
+    assert_eq!(storage.atomic_load("n").await.unwrap(), None);
+    assert_eq!(storage.atomic_load_f64("f").await.unwrap(), None);
+    assert_eq!(storage.stats().len, 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_loads_evict_an_expired_value_once() {
+    let storage = IMCModule::new(IMCConfig {
+        max_entries: Some(100),
+        ..IMCConfig::default()
+    });
+
+    for i in 0..50 {
+        let key = format!("raw-{i}");
+        storage
+            .store_raw_with_expiry(&key, vec![1, 2, 3], Some(0))
+            .await
+            .unwrap();
+
+        let loaders: Vec<_> = (0..4)
+            .map(|_| {
+                let (storage, key) = (storage.clone(), key.clone());
+                tokio::spawn(async move { storage.load_raw(key).await.unwrap() })
+            })
+            .collect();
+        for loader in loaders {
+            assert_eq!(loader.await.unwrap(), None);
+        }
+    }
+
+    let stats = storage.stats();
+    assert_eq!((stats.misses, stats.evictions, stats.len), (200, 50, 0));
+    // Each key was untracked by the one load that evicted it
+    assert_eq!(storage.data_lru.as_ref().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn rename_overwrites_the_destination() {
+    let (storage, _) = module(IMCConfig::default());
+    storage
+        .store_with_expiry("tmp", "new".to_string(), Some(60))
+        .await
+        .unwrap();
+    storage
+        .store_string("live", "old".to_string())
+        .await
+        .unwrap();
+
+    storage.rename("tmp", "live").await.unwrap();
+
+    assert_eq!(storage.load_string("tmp").await.unwrap(), None);
+    assert_eq!(
+        storage.load_string("live").await.unwrap().as_deref(),
+        Some("new")
+    );
+    assert_eq!(storage.ttl("live").await.unwrap(), Some(60));
+    assert_eq!(storage.stats().len, 1);
+}
+
+#[tokio::test]
+async fn rename_of_a_missing_key_fails() {
+    let (storage, _) = module(IMCConfig::default());
+
+    let result = storage.rename("missing", "other").await;
+    assert!(matches!(result, Err(StorageError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn decrement_goes_below_zero() {
+    let (storage, _) = module(IMCConfig::default());
+    storage.atomic_store("stock", 3).await.unwrap();
+
+    assert_eq!(
+        storage.atomic_decrement("stock", 5).await.unwrap(),
+        Some(-2)
+    );
+    assert_eq!(storage.atomic_decrement("new", 1).await.unwrap(), Some(-1));
+    assert_eq!(storage.atomic_load("stock").await.unwrap(), Some(-2));
+}
+
+#[tokio::test]
+async fn increment_past_i64_max_fails_and_keeps_the_value() {
+    let (storage, _) = module(IMCConfig::default());
+    storage.atomic_store("n", i64::MAX - 1).await.unwrap();
+
+    assert!(matches!(
+        storage.atomic_increment("n", 5).await,
+        Err(StorageError::Overflow(_))
+    ));
+    assert_eq!(storage.atomic_load("n").await.unwrap(), Some(i64::MAX - 1));
+    assert_eq!(
+        storage.atomic_increment("n", 1).await.unwrap(),
+        Some(i64::MAX)
+    );
+}
+
+#[tokio::test]
+async fn decrement_by_i64_min_fails() {
+    let (storage, _) = module(IMCConfig::default());
+    storage.atomic_store("n", -2).await.unwrap();
+
+    // The delta itself cannot be negated, even though the result would fit
+    assert!(matches!(
+        storage.atomic_decrement("n", i64::MIN).await,
+        Err(StorageError::Overflow(_))
+    ));
+    assert_eq!(storage.atomic_load("n").await.unwrap(), Some(-2));
+}
+
+#[tokio::test]
+async fn millisecond_expiry_elapses_between_whole_seconds() {
+    let (storage, clock) = module(IMCConfig::default());
+    storage
+        .store_with_expiry_ms("token", "abc".to_string(), Some(100))
+        .await
+        .unwrap();
+
+    clock.advance_millis(50);
+    assert_eq!(
+        storage.load_string("token").await.unwrap().as_deref(),
+        Some("abc")
+    );
+
+    clock.advance_millis(100);
+    assert_eq!(storage.load_string("token").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn len_counts_string_binary_and_integer_entries() {
+    let (storage, clock) = module(IMCConfig::default());
+    for i in 0..5 {
+        storage
+            .store_string(format!("s{i}"), "a".to_string())
+            .await
+            .unwrap();
+    }
+    storage.store_raw("r", vec![1]).await.unwrap();
+    storage.atomic_store("n", 1).await.unwrap();
+    storage
+        .store_with_expiry("short", "b".to_string(), Some(1))
+        .await
+        .unwrap();
+    assert_eq!(AdminStorage::len(&storage).await.unwrap(), 8);
+
+    // Expired entries are counted until they are evicted
+    clock.advance(1);
+    assert_eq!(AdminStorage::len(&storage).await.unwrap(), 8);
+    storage.clear_expired().await.unwrap();
+    assert_eq!(AdminStorage::len(&storage).await.unwrap(), 7);
+}
+
+#[tokio::test]
+async fn float_increments_accumulate_within_tolerance() {
+    let (storage, _) = module(IMCConfig::default());
+
+    let mut total = 0.0;
+    for _ in 0..10 {
+        total = storage.atomic_increment_f64("sum", 0.1).await.unwrap();
+    }
+
+    assert!((total - 1.0).abs() < 1e-9, "sum was {total}");
+    assert_eq!(storage.atomic_load_f64("sum").await.unwrap(), Some(total));
+}
+
+#[tokio::test]
+async fn clear_expired_evicts_only_expired_entries() {
+    let (storage, clock) = module(IMCConfig {
+        max_entries: Some(100),
+        ..IMCConfig::default()
+    });
+    for i in 0..3 {
+        storage
+            .store_with_expiry(format!("short{i}"), "a".to_string(), Some(10))
+            .await
+            .unwrap();
+    }
+    storage
+        .store_raw_with_expiry("raw", vec![1], Some(10))
+        .await
+        .unwrap();
+    storage
+        .store_with_expiry("long", "b".to_string(), Some(60))
+        .await
+        .unwrap();
+    storage
+        .store_string("permanent", "c".to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(storage.clear_expired().await.unwrap(), 0);
+
+    clock.advance(10);
+    assert_eq!(storage.clear_expired().await.unwrap(), 4);
+    assert_eq!(storage.stats().evictions, 4);
+    assert_eq!(storage.stats().len, 2);
+    // Swept keys are no longer tracked for LRU eviction
+    assert_eq!(storage.string_lru.as_ref().unwrap().len(), 2);
+    assert_eq!(storage.data_lru.as_ref().unwrap().len(), 0);
+
+    clock.advance(60);
+    assert_eq!(storage.clear_expired().await.unwrap(), 1);
+    assert_eq!(
+        storage.load_string("permanent").await.unwrap().as_deref(),
+        Some("c")
+    );
+}
+
+#[tokio::test]
+async fn ttl_reports_an_expired_key_as_missing() {
+    let (storage, clock) = module(IMCConfig::default());
+    storage
+        .store_with_expiry("token", "abc".to_string(), Some(1))
+        .await
+        .unwrap();
+    assert_eq!(storage.ttl("token").await.unwrap(), Some(1));
+
+    clock.advance(1);
+    assert_eq!(storage.ttl("token").await.unwrap(), None);
+    assert!(!storage.string_store.contains_key("token"));
+    assert_eq!(storage.ttl("missing").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn exists_sees_keys_in_every_store() {
+    let (storage, _) = module(IMCConfig::default());
+    storage.atomic_increment_f64("float", 0.5).await.unwrap();
+    storage.push_back("list", "a".to_string()).await.unwrap();
+    storage.set_add("set", "a".to_string()).await.unwrap();
+    storage
+        .hash_set("hash", "field".to_string(), "a".to_string())
+        .await
+        .unwrap();
+
+    for key in ["float", "list", "set", "hash"] {
+        assert!(
+            storage.exists(key).await.unwrap(),
+            "{key} was reported missing"
+        );
+    }
+    assert!(!storage.exists("missing").await.unwrap());
+}
+
+#[tokio::test]
+async fn expiry_operations_untrack_the_keys_they_evict() {
+    let (storage, clock) = module(IMCConfig {
+        max_entries: Some(100),
+        ..IMCConfig::default()
+    });
+    for key in ["ttl", "expire", "longer", "persist", "exists"] {
+        storage
+            .store_with_expiry(key, "a".to_string(), Some(10))
+            .await
+            .unwrap();
+    }
+    clock.advance(10);
+
+    assert_eq!(storage.ttl("ttl").await.unwrap(), None);
+    assert!(!storage.expire("expire", 60).await.unwrap());
+    assert!(!storage.expire_if_longer("longer", 60).await.unwrap());
+    assert!(!storage.persist("persist").await.unwrap());
+    assert!(!storage.exists("exists").await.unwrap());
+
+    let stats = storage.stats();
+    assert_eq!((stats.evictions, stats.len), (5, 0));
+    assert_eq!(storage.string_lru.as_ref().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn sweeps_keep_keys_written_again_tracked() {
+    let (storage, _) = module(IMCConfig {
+        max_entries: Some(100),
+        ..IMCConfig::default()
+    });
+    let lru = storage.string_lru.as_ref().unwrap();
+    storage
+        .store_string("present", "a".to_string())
+        .await
+        .unwrap();
+    lru.touch("removed");
+    let since = lru.tick();
+    storage
+        .store_string("touched", "a".to_string())
+        .await
+        .unwrap();
+    storage.string_store.remove("touched");
+
+    let swept = ["present", "touched", "removed"].map(str::to_string);
+    lru.forget_swept(&storage.string_store, &swept, since);
+
+    // Only the key neither back in the store nor admitted since the sweep began is dropped
+    assert_eq!(lru.len(), 2);
+}
@@ -0,0 +1,256 @@
+//! Write-ahead log of the string and binary stores and deletes of an [`IMCModule`].
+//!
+//! Each record is a little-endian `u32` length followed by the bincode
+//! encoding of a [`WalRecord`]. Records are handed to a background thread,
+//! which buffers them and flushes whenever it has caught up, so writers never
+//! wait on the disk.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{insert_keep_ttl, insert_sized, is_expired, released, IMCModule};
+use crate::codec::{BincodeCodec, Codec};
+use crate::errors::StorageError;
+
+/// A logged write.
+///
+/// Expiration times are absolute Unix timestamps in milliseconds, so a
+/// replayed entry expires at the same time as the original.
+#[derive(Serialize, Deserialize)]
+pub(super) enum WalRecord {
+    /// A string stored with an optional expiration time
+    StoreString {
+        key: String,
+        value: String,
+        expiry: Option<u64>,
+    },
+    /// A string stored while keeping the key's existing expiration
+    StoreStringKeepTtl { key: String, value: String },
+    /// A string removed by a delete or take
+    DeleteString { key: String },
+    /// Bytes stored with an optional expiration time
+    StoreRaw {
+        key: String,
+        value: Vec<u8>,
+        expiry: Option<u64>,
+    },
+    /// Bytes stored while keeping the key's existing expiration
+    StoreRawKeepTtl { key: String, value: Vec<u8> },
+    /// Bytes removed by a delete
+    DeleteRaw { key: String },
+    /// Every store cleared
+    Clear,
+}
+
+/// Handle for appending records to the log.
+///
+/// The writer thread stops once every clone of the handle has been dropped,
+/// after flushing the records still queued.
+#[derive(Clone)]
+pub(super) struct Wal {
+    sender: tokio::sync::mpsc::UnboundedSender<WalRecord>,
+}
+
+impl Wal {
+    /// Opens the log for appending and starts the thread writing to it.
+    ///
+    /// # Arguments
+    /// * `path` - The log file, created if missing
+    /// * `valid_len` - The length of the log's intact records, as found by
+    ///   replaying it; anything after it is a torn record and is cut off
+    pub(super) fn open(path: &Path, valid_len: u64) -> Result<Self, StorageError> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        if file.metadata()?.len() > valid_len {
+            file.set_len(valid_len)?;
+        }
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<WalRecord>();
+
+        std::thread::Builder::new()
+            .name("tsot-wal".to_string())
+            .spawn(move || {
+                let mut writer = BufWriter::new(file);
+                while let Some(record) = receiver.blocking_recv() {
+                    // Write everything already queued before flushing
+                    let mut next = Some(record);
+                    while let Some(record) = next {
+                        if let Err(_e) = write_record(&mut writer, &record) {
+                            #[cfg(feature = "tracing")]
+                            tracing::error!(error = %_e, "write-ahead log stopped");
+                            return;
+                        }
+                        next = receiver.try_recv().ok();
+                    }
+                    if let Err(_e) = writer.flush() {
+                        #[cfg(feature = "tracing")]
+                        tracing::error!(error = %_e, "write-ahead log stopped");
+                        return;
+                    }
+                }
+            })?;
+
+        Ok(Self { sender })
+    }
+
+    /// Queues a record to be appended.
+    ///
+    /// Records are dropped if the writer thread has stopped after a failed write.
+    pub(super) fn append(&self, record: WalRecord) {
+        let _ = self.sender.send(record);
+    }
+}
+
+impl IMCModule {
+    /// Replays the log at `path` and logs every later write to it.
+    ///
+    /// # Panics
+    /// Panics if the log cannot be read or opened for appending.
+    pub(super) fn attach_wal(mut self, path: &Path) -> Self {
+        let (_, valid_len) = self
+            .replay(path)
+            .unwrap_or_else(|e| panic!("failed to replay {}: {e}", path.display()));
+        let wal = Wal::open(path, valid_len)
+            .unwrap_or_else(|e| panic!("failed to open {}: {e}", path.display()));
+        self.wal = Some(wal);
+        self
+    }
+
+    /// Appends a record to the write-ahead log, if there is one.
+    ///
+    /// The record is only built when it will be written.
+    pub(super) fn log(&self, record: impl FnOnce() -> WalRecord) {
+        if let Some(wal) = &self.wal {
+            wal.append(record());
+        }
+    }
+
+    /// Applies the writes recorded in a write-ahead log.
+    ///
+    /// Called on construction when `IMCConfig::wal_path` is set. Entries whose
+    /// expiration time has passed are removed rather than restored. A record
+    /// cut short at the end of the file, e.g. by a crash during a write, is
+    /// ignored. A missing file is treated as an empty log.
+    ///
+    /// # Arguments
+    /// * `path` - The log file to replay
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The number of records applied
+    /// * `Err(StorageError::Io)` - If the file cannot be read
+    /// * `Err(StorageError::DeserializationError)` - If a record is corrupted
+    pub fn replay_wal(&self, path: &Path) -> Result<u64, StorageError> {
+        self.replay(path).map(|(applied, _)| applied)
+    }
+
+    /// Replays a log, also returning the length of its intact records.
+    pub(super) fn replay(&self, path: &Path) -> Result<(u64, u64), StorageError> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok((0, 0)),
+            Err(e) => return Err(e.into()),
+        };
+        let mut reader = BufReader::new(file);
+        let current_time = self.now_millis()?;
+        let live = |expiry: Option<u64>| !expiry.is_some_and(|e| is_expired(e, current_time));
+
+        let mut applied = 0;
+        let mut valid_len = 0;
+        while let Some((record, len)) = read_record(&mut reader)? {
+            match record {
+                WalRecord::StoreString { key, value, expiry } if live(expiry) => {
+                    self.admit_string(&key);
+                    insert_sized(
+                        &self.string_store,
+                        &self.counters.string_bytes,
+                        key.clone(),
+                        (self.intern(value), expiry),
+                    );
+                    self.shrink_strings(&key);
+                }
+                WalRecord::StoreString { key, .. } | WalRecord::DeleteString { key } => {
+                    self.forget_string(&key);
+                    released(&self.counters.string_bytes, self.string_store.remove(&key));
+                }
+                WalRecord::StoreStringKeepTtl { key, value } => {
+                    self.admit_string(&key);
+                    insert_keep_ttl(
+                        &self.string_store,
+                        &self.counters.string_bytes,
+                        key.clone(),
+                        self.intern(value),
+                        current_time,
+                    );
+                    self.shrink_strings(&key);
+                }
+                WalRecord::StoreRaw { key, value, expiry } if live(expiry) => {
+                    self.admit_raw(&key);
+                    insert_sized(
+                        &self.data_store,
+                        &self.counters.data_bytes,
+                        key.clone(),
+                        (value, expiry),
+                    );
+                    self.shrink_raw(&key);
+                }
+                WalRecord::StoreRaw { key, .. } | WalRecord::DeleteRaw { key } => {
+                    self.forget_raw(&key);
+                    released(&self.counters.data_bytes, self.data_store.remove(&key));
+                }
+                WalRecord::StoreRawKeepTtl { key, value } => {
+                    self.admit_raw(&key);
+                    insert_keep_ttl(
+                        &self.data_store,
+                        &self.counters.data_bytes,
+                        key.clone(),
+                        value,
+                        current_time,
+                    );
+                    self.shrink_raw(&key);
+                }
+                WalRecord::Clear => self.clear_all(),
+            }
+            applied += 1;
+            valid_len += len;
+        }
+
+        Ok((applied, valid_len))
+    }
+}
+
+/// Appends a length-prefixed record.
+fn write_record(writer: &mut impl Write, record: &WalRecord) -> Result<(), StorageError> {
+    let bytes = BincodeCodec.encode(record)?;
+    let len = u32::try_from(bytes.len()).map_err(|_| {
+        StorageError::DeserializationError(format!("record of {} bytes is too large", bytes.len()))
+    })?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Reads the next record and its length in bytes, or `None` at the end of the log.
+///
+/// A record cut short by the end of the file counts as the end of the log.
+fn read_record(reader: &mut impl Read) -> Result<Option<(WalRecord, u64)>, StorageError> {
+    let mut len = [0; 4];
+    if !read_full(reader, &mut len)? {
+        return Ok(None);
+    }
+    let mut bytes = vec![0; u32::from_le_bytes(len) as usize];
+    if !read_full(reader, &mut bytes)? {
+        return Ok(None);
+    }
+    let record = BincodeCodec.decode(&bytes)?;
+    Ok(Some((record, len.len() as u64 + bytes.len() as u64)))
+}
+
+/// Fills `buf`, returning `false` if the end of the file is reached first.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> Result<bool, StorageError> {
+    match reader.read_exact(buf) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
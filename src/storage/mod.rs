@@ -1,5 +1,8 @@
+#[cfg(all(feature = "imc", feature = "redis"))]
+pub mod backend;
 #[cfg(feature = "imc")]
 pub mod imc;
+pub mod null;
 
 #[cfg(feature = "redis")]
 pub mod redis;
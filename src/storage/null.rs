@@ -0,0 +1,274 @@
+//! A storage backend that stores nothing.
+//!
+//! [`NullStorage`] accepts every write and reports every key as missing, so
+//! caching can be switched off by substituting it for a real backend without
+//! changing call sites. It has no dependencies and is always available.
+
+use crate::{errors::StorageError, types::StoreState};
+
+/// Storage backend that discards every write.
+///
+/// Stores always report [`StoreState::New`], loads always return `None`, and
+/// deletes always succeed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullStorage;
+
+impl NullStorage {
+    /// Creates a new null backend.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use async_trait::async_trait;
+
+    use super::{NullStorage, StorageError, StoreState};
+    use crate::asynchronous::{
+        AtomicStorage, RawStorage, RawStorageWithExpiry, StringStorage, StringStorageWithExpiry,
+    };
+
+    #[async_trait]
+    impl StringStorageWithExpiry for NullStorage {
+        async fn store_with_expiry(
+            &self,
+            _key: impl AsRef<str> + Send,
+            _value: String,
+            _expiry: Option<u64>,
+        ) -> Result<StoreState, StorageError> {
+            Ok(StoreState::New)
+        }
+
+        async fn store_keep_ttl(
+            &self,
+            _key: impl AsRef<str> + Send,
+            _value: String,
+        ) -> Result<StoreState, StorageError> {
+            Ok(StoreState::New)
+        }
+    }
+
+    #[async_trait]
+    impl StringStorage for NullStorage {
+        async fn load_string(
+            &self,
+            _key: impl AsRef<str> + Send,
+        ) -> Result<Option<String>, StorageError> {
+            Ok(None)
+        }
+
+        async fn delete_string(&self, _key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        async fn take_string(
+            &self,
+            _key: impl AsRef<str> + Send,
+        ) -> Result<Option<String>, StorageError> {
+            Ok(None)
+        }
+    }
+
+    #[async_trait]
+    impl RawStorageWithExpiry for NullStorage {
+        async fn store_raw_with_expiry(
+            &self,
+            _key: impl AsRef<str> + Send,
+            _value: Vec<u8>,
+            _expiry: Option<u64>,
+        ) -> Result<StoreState, StorageError> {
+            Ok(StoreState::New)
+        }
+
+        async fn store_raw_keep_ttl(
+            &self,
+            _key: impl AsRef<str> + Send,
+            _value: Vec<u8>,
+        ) -> Result<StoreState, StorageError> {
+            Ok(StoreState::New)
+        }
+    }
+
+    #[async_trait]
+    impl RawStorage for NullStorage {
+        async fn load_raw(
+            &self,
+            _key: impl AsRef<str> + Send,
+        ) -> Result<Option<Vec<u8>>, StorageError> {
+            Ok(None)
+        }
+
+        async fn delete_raw(&self, _key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AtomicStorage for NullStorage {
+        async fn atomic_store(
+            &self,
+            _key: impl AsRef<str> + Send,
+            _value: i64,
+        ) -> Result<StoreState, StorageError> {
+            Ok(StoreState::New)
+        }
+
+        async fn atomic_load(
+            &self,
+            _key: impl AsRef<str> + Send,
+        ) -> Result<Option<i64>, StorageError> {
+            Ok(None)
+        }
+
+        async fn atomic_delete(&self, _key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        async fn atomic_increment(
+            &self,
+            _key: impl AsRef<str> + Send,
+            _value: i64,
+        ) -> Result<Option<i64>, StorageError> {
+            Ok(None)
+        }
+
+        /// Returns the value a freshly initialized key would have.
+        async fn atomic_increment_or_init(
+            &self,
+            _key: impl AsRef<str> + Send,
+            delta: i64,
+            init: i64,
+        ) -> Result<i64, StorageError> {
+            init.checked_add(delta)
+                .ok_or_else(|| StorageError::Overflow(format!("{init} + {delta}")))
+        }
+
+        /// Never swaps, as no key ever holds a value.
+        async fn atomic_compare_and_swap(
+            &self,
+            _key: impl AsRef<str> + Send,
+            _expected: i64,
+            _new: i64,
+        ) -> Result<bool, StorageError> {
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(feature = "sync")]
+mod sync_impl {
+    use super::{NullStorage, StorageError, StoreState};
+    use crate::sync::{
+        AtomicStorage, RawStorage, RawStorageWithExpiry, StringStorage, StringStorageWithExpiry,
+    };
+
+    impl StringStorageWithExpiry for NullStorage {
+        fn store_with_expiry(
+            &self,
+            _key: impl AsRef<str>,
+            _value: String,
+            _expiry: Option<u64>,
+        ) -> Result<StoreState, StorageError> {
+            Ok(StoreState::New)
+        }
+
+        fn store_keep_ttl(
+            &self,
+            _key: impl AsRef<str>,
+            _value: String,
+        ) -> Result<StoreState, StorageError> {
+            Ok(StoreState::New)
+        }
+    }
+
+    impl StringStorage for NullStorage {
+        fn load_string(&self, _key: impl AsRef<str>) -> Result<Option<String>, StorageError> {
+            Ok(None)
+        }
+
+        fn delete_string(&self, _key: impl AsRef<str>) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn take_string(&self, _key: impl AsRef<str>) -> Result<Option<String>, StorageError> {
+            Ok(None)
+        }
+    }
+
+    impl RawStorageWithExpiry for NullStorage {
+        fn store_raw_with_expiry(
+            &self,
+            _key: impl AsRef<str>,
+            _value: Vec<u8>,
+            _expiry: Option<u64>,
+        ) -> Result<StoreState, StorageError> {
+            Ok(StoreState::New)
+        }
+
+        fn store_raw_keep_ttl(
+            &self,
+            _key: impl AsRef<str>,
+            _value: Vec<u8>,
+        ) -> Result<StoreState, StorageError> {
+            Ok(StoreState::New)
+        }
+    }
+
+    impl RawStorage for NullStorage {
+        fn load_raw(&self, _key: impl AsRef<str>) -> Result<Option<Vec<u8>>, StorageError> {
+            Ok(None)
+        }
+
+        fn delete_raw(&self, _key: impl AsRef<str>) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    impl AtomicStorage for NullStorage {
+        fn atomic_store(
+            &self,
+            _key: impl AsRef<str>,
+            _value: i64,
+        ) -> Result<StoreState, StorageError> {
+            Ok(StoreState::New)
+        }
+
+        fn atomic_load(&self, _key: impl AsRef<str>) -> Result<Option<i64>, StorageError> {
+            Ok(None)
+        }
+
+        fn atomic_delete(&self, _key: impl AsRef<str>) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn atomic_increment(
+            &self,
+            _key: impl AsRef<str>,
+            _value: i64,
+        ) -> Result<Option<i64>, StorageError> {
+            Ok(None)
+        }
+
+        /// Returns the value a freshly initialized key would have.
+        fn atomic_increment_or_init(
+            &self,
+            _key: impl AsRef<str>,
+            delta: i64,
+            init: i64,
+        ) -> Result<i64, StorageError> {
+            init.checked_add(delta)
+                .ok_or_else(|| StorageError::Overflow(format!("{init} + {delta}")))
+        }
+
+        /// Never swaps, as no key ever holds a value.
+        fn atomic_compare_and_swap(
+            &self,
+            _key: impl AsRef<str>,
+            _expected: i64,
+            _new: i64,
+        ) -> Result<bool, StorageError> {
+            Ok(false)
+        }
+    }
+}
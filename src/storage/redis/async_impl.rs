@@ -1,45 +1,99 @@
 use async_trait::async_trait;
 use fred::{
-    interfaces::KeysInterface,
-    types::{Expiration, RedisKey},
+    error::RedisError,
+    interfaces::ClientLike,
+    interfaces::{HashesInterface, KeysInterface, ListInterface, LuaInterface, SetsInterface},
+    types::{Expiration, RedisKey, RedisValue, ScanResult, ScanType, SetOptions},
 };
+use futures::{Stream, StreamExt};
+use std::collections::HashMap;
 
 use crate::{
     asynchronous::{
-        AtomicStorage, RawStorage, RawStorageWithExpiry, StringStorage, StringStorageWithExpiry,
+        AtomicStorage, BatchRawStorage, BatchStringStorage, BoundedIncrement, BulkDelete,
+        ConditionalStringStorage, FloatAtomicStorage, HashStorage, HealthCheck, KeyExists,
+        KeyListing, KeyRename, ListStorage, RateLimiter, RawExport, RawStorage,
+        RawStorageWithExpiry, SetStorage, StringAppend, StringExpiry, StringGetSet, StringRange,
+        StringStorage, StringStorageWithExpiry, VersionedStorage,
     },
     errors::StorageError,
     types::StoreState,
 };
 
-use super::RedisStorageModule;
+use super::{RedisStorageModule, EXPIRED};
 
 #[async_trait]
 impl StringStorageWithExpiry for RedisStorageModule {
     /// Store a string with optional expiry
+    ///
+    /// Without an expiry, the configured `default_expiry` is applied; if there is
+    /// none, SET clears any TTL already set on the key
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
     async fn store_with_expiry(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: String,
         expiry: Option<u64>,
     ) -> Result<StoreState, StorageError> {
-        let key = RedisKey::from(key);
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "store_with_expiry");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
 
-        // Determine expiration
-        let expiration = expiry.map(|seconds| Expiration::EX(seconds as i64));
+        // Determine expiration, falling back to the configured default
+        let expiration = self.expiration(expiry);
 
         // Check if key exists before setting
-        let exists = self
-            .client
-            .exists(&key)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+        let exists = self.client.exists(&key).await?;
 
         // Set the value with optional expiration
         self.client
             .set::<String, _, _>(key, value, expiration, None, false)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+            .await?;
+
+        // Return store state based on previous existence
+        Ok(if exists {
+            StoreState::Updated
+        } else {
+            StoreState::New
+        })
+    }
+
+    /// Store a string while keeping any existing TTL (SET ... KEEPTTL)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn store_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<StoreState, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "store_keep_ttl");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
+
+        // Check if key exists before setting
+        let exists = self.client.exists(&key).await?;
+
+        // Set the value, preserving the current expiration
+        self.client
+            .set::<String, _, _>(key, value, Some(Expiration::KEEPTTL), None, false)
+            .await?;
 
         // Return store state based on previous existence
         Ok(if exists {
@@ -53,59 +107,147 @@ impl StringStorageWithExpiry for RedisStorageModule {
 #[async_trait]
 impl StringStorage for RedisStorageModule {
     /// Load a string value
-    async fn load_string(&self, key: String) -> Result<Option<String>, StorageError> {
-        let key = RedisKey::from(key);
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn load_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "load_string");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
 
         // Get the value from Redis
-        let result: Option<String> = self
-            .client
-            .get(&key)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+        let result: Option<String> = self.client.get(&key).await?;
 
         Ok(result)
     }
 
     /// Delete a string value
-    async fn delete_string(&self, key: String) -> Result<(), StorageError> {
-        let key = RedisKey::from(key);
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn delete_string(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "delete_string");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
 
         // Delete the key
-        self.client
-            .del::<u64, _>(&key)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+        self.client.del::<u64, _>(&key).await?;
 
         Ok(())
     }
+
+    /// Load and delete a string value using GETDEL
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn take_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "take_string");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client.getdel(&key).await.map_err(StorageError::from)
+    }
 }
 
 #[async_trait]
 impl RawStorageWithExpiry for RedisStorageModule {
     /// Store binary data with optional expiry
+    ///
+    /// Without an expiry, the configured `default_expiry` is applied; if there is
+    /// none, SET clears any TTL already set on the key
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
     async fn store_raw_with_expiry(
         &self,
-        key: String,
+        key: impl AsRef<str> + Send,
         value: Vec<u8>,
         expiry: Option<u64>,
     ) -> Result<StoreState, StorageError> {
-        let key = RedisKey::from(key);
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "store_raw_with_expiry");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
 
-        // Determine expiration
-        let expiration = expiry.map(|seconds| Expiration::EX(seconds as i64));
+        // Determine expiration, falling back to the configured default
+        let expiration = self.expiration(expiry);
 
         // Check if key exists before setting
-        let exists = self
-            .client
-            .exists(&key)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+        let exists = self.client.exists(&key).await?;
 
         // Set the value with optional expiration
         self.client
             .set::<String, _, _>(key, value, expiration, None, false)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+            .await?;
+
+        // Return store state based on previous existence
+        Ok(if exists {
+            StoreState::Updated
+        } else {
+            StoreState::New
+        })
+    }
+
+    /// Store binary data while keeping any existing TTL (SET ... KEEPTTL)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+    ) -> Result<StoreState, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "store_raw_keep_ttl");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
+
+        // Check if key exists before setting
+        let exists = self.client.exists(&key).await?;
+
+        // Set the value, preserving the current expiration
+        self.client
+            .set::<String, _, _>(key, value, Some(Expiration::KEEPTTL), None, false)
+            .await?;
 
         // Return store state based on previous existence
         Ok(if exists {
@@ -119,51 +261,109 @@ impl RawStorageWithExpiry for RedisStorageModule {
 #[async_trait]
 impl RawStorage for RedisStorageModule {
     /// Load binary data
-    async fn load_raw(&self, key: String) -> Result<Option<Vec<u8>>, StorageError> {
-        let key = RedisKey::from(key);
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn load_raw(&self, key: impl AsRef<str> + Send) -> Result<Option<Vec<u8>>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "load_raw");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
 
         // Get the value from Redis
-        let result: Option<Vec<u8>> = self
-            .client
-            .get(&key)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+        let result: Option<Vec<u8>> = self.client.get(&key).await?;
 
         Ok(result)
     }
 
     /// Delete binary data
-    async fn delete_raw(&self, key: String) -> Result<(), StorageError> {
-        let key = RedisKey::from(key);
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn delete_raw(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "delete_raw");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
 
         // Delete the key
-        self.client
-            .del::<u64, _>(&key)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+        self.client.del::<u64, _>(&key).await?;
 
         Ok(())
     }
 }
 
+/// Lua script that initializes an integer if it is missing, then increments it.
+///
+/// The key is created with SET NX and incremented with INCRBY. Returns the new value.
+const INCREMENT_OR_INIT_SCRIPT: &str = r#"
+redis.call('SET', KEYS[1], ARGV[2], 'NX')
+return redis.call('INCRBY', KEYS[1], ARGV[1])
+"#;
+
+/// Lua script that replaces an integer only if it equals the expected value.
+///
+/// Values are compared as strings, since Lua numbers cannot represent every
+/// i64 exactly. Returns 1 if the value was swapped, 0 otherwise.
+const ATOMIC_COMPARE_AND_SWAP_SCRIPT: &str = r#"
+local current = redis.call('GET', KEYS[1])
+if current ~= ARGV[1] then
+    return 0
+end
+redis.call('SET', KEYS[1], ARGV[2], 'KEEPTTL')
+return 1
+"#;
+
 #[async_trait]
 impl AtomicStorage for RedisStorageModule {
     /// Store an atomic integer value
-    async fn atomic_store(&self, key: String, value: i64) -> Result<StoreState, StorageError> {
-        let key = RedisKey::from(key);
+    ///
+    /// Overwriting an existing key keeps its TTL (SET ... KEEPTTL); a new key
+    /// gets the configured `default_expiry`, if any
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn atomic_store(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<StoreState, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "atomic_store");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
 
         // Check if key exists before setting
-        let exists = self
-            .client
-            .exists(&key)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+        let exists = self.client.exists(&key).await?;
+
+        // Plain SET would clear the TTL of an existing key, so keep it instead
+        let expiration = if exists {
+            Some(Expiration::KEEPTTL)
+        } else {
+            self.expiration(None)
+        };
 
-        // Set the value
         self.client
-            .set::<String, _, _>(key, value.to_string(), None, None, false)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+            .set::<String, _, _>(key, value.to_string(), expiration, None, false)
+            .await?;
 
         // Return store state based on previous existence
         Ok(if exists {
@@ -174,15 +374,23 @@ impl AtomicStorage for RedisStorageModule {
     }
 
     /// Load an atomic integer value
-    async fn atomic_load(&self, key: String) -> Result<Option<i64>, StorageError> {
-        let key = RedisKey::from(key);
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn atomic_load(&self, key: impl AsRef<str> + Send) -> Result<Option<i64>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "atomic_load");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
 
         // Get the value from Redis
-        let result: Option<String> = self
-            .client
-            .get(&key)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+        let result: Option<String> = self.client.get(&key).await?;
 
         // Parse the value to i64
         let parsed_result = result
@@ -196,29 +404,1354 @@ impl AtomicStorage for RedisStorageModule {
     }
 
     /// Delete an atomic integer value
-    async fn atomic_delete(&self, key: String) -> Result<(), StorageError> {
-        let key = RedisKey::from(key);
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn atomic_delete(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "atomic_delete");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
 
         // Delete the key
-        self.client
-            .del::<u64, _>(&key)
-            .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+        self.client.del::<u64, _>(&key).await?;
 
         Ok(())
     }
 
     /// Increment an atomic integer value
-    async fn atomic_increment(&self, key: String, value: i64) -> Result<Option<i64>, StorageError> {
-        let key = RedisKey::from(key);
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn atomic_increment(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<Option<i64>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "atomic_increment");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
 
         // Use Redis INCRBY command to increment
         let result = self
             .client
             .incr_by(&key, value)
             .await
-            .map_err(|e| StorageError::ConnectionError(e.to_string()))?;
+            .map_err(increment_error)?;
 
         Ok(Some(result))
     }
+
+    /// Decrement an atomic integer value using DECRBY
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn atomic_decrement(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<Option<i64>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "atomic_decrement");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
+
+        let result = self
+            .client
+            .decr_by(&key, value)
+            .await
+            .map_err(increment_error)?;
+
+        Ok(Some(result))
+    }
+
+    /// Increment an atomic integer value, initializing it if missing, using a Lua script
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn atomic_increment_or_init(
+        &self,
+        key: impl AsRef<str> + Send,
+        delta: i64,
+        init: i64,
+    ) -> Result<i64, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "atomic_increment_or_init");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
+
+        // Initializing and incrementing in one script keeps it to a single round trip
+        self.client
+            .eval(
+                INCREMENT_OR_INIT_SCRIPT,
+                key,
+                vec![delta.to_string(), init.to_string()],
+            )
+            .await
+            .map_err(increment_error)
+    }
+
+    /// Swap an atomic integer value if it matches, using a Lua script
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(backend = "redis", key = %key.as_ref()),
+            err(level = "warn")
+        )
+    )]
+    async fn atomic_compare_and_swap(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected: i64,
+        new: i64,
+    ) -> Result<bool, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "atomic_compare_and_swap");
+        #[cfg(feature = "tracing")]
+        let _latency = super::Latency::start();
+        let key = RedisKey::from(self.key(key)?);
+
+        let swapped: i64 = self
+            .client
+            .eval(
+                ATOMIC_COMPARE_AND_SWAP_SCRIPT,
+                key,
+                vec![expected.to_string(), new.to_string()],
+            )
+            .await?;
+
+        Ok(swapped == 1)
+    }
+}
+
+#[async_trait]
+impl RawExport for RedisStorageModule {
+    /// List string keys using cursor-based SCAN ... TYPE string
+    ///
+    /// Lists, sets and hashes hold no raw value, so they are left out rather
+    /// than failing `export_raw` with WRONGTYPE
+    async fn scan_raw_keys(&self, prefix: Option<String>) -> Result<Vec<String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "scan_raw_keys");
+        let pattern = match prefix {
+            Some(prefix) => format!("{}*", escape_pattern(&prefix)),
+            None => "*".to_string(),
+        };
+
+        self.scan_keys(pattern, Some(ScanType::String)).await
+    }
+
+    /// Export binary data along with its remaining TTL
+    async fn export_raw(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<(Vec<u8>, Option<u64>)>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "export_raw");
+        let key = RedisKey::from(self.key(key)?);
+
+        // Get the value from Redis
+        let value: Option<Vec<u8>> = self.client.get(&key).await?;
+
+        let Some(value) = value else {
+            return Ok(None);
+        };
+
+        // TTL returns -1 for keys without expiry and -2 for missing keys
+        let ttl: i64 = self.client.ttl(&key).await?;
+
+        Ok(match ttl {
+            -2 => None,
+            -1 => Some((value, None)),
+            ttl => Some((value, Some(ttl as u64))),
+        })
+    }
+}
+
+/// Lua script that updates a versioned record only if its version matches.
+///
+/// Records are stored as hashes with `version` and `value` fields; a missing
+/// record is at version 0. Returns the new version, or nil on conflict.
+const COMPARE_AND_SET_VERSIONED_SCRIPT: &str = r#"
+local current = tonumber(redis.call('HGET', KEYS[1], 'version') or '0')
+if current ~= tonumber(ARGV[1]) then
+    return false
+end
+local version = current + 1
+redis.call('HSET', KEYS[1], 'version', version, 'value', ARGV[2])
+return version
+"#;
+
+#[async_trait]
+impl VersionedStorage for RedisStorageModule {
+    /// Load a versioned record stored as a hash
+    async fn load_versioned(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<(u64, String)>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "load_versioned");
+        let key = RedisKey::from(self.key(key)?);
+
+        // Get both fields of the record
+        let mut record: HashMap<String, String> = self.client.hgetall(&key).await?;
+
+        let (Some(version), Some(value)) = (record.remove("version"), record.remove("value"))
+        else {
+            return Ok(None);
+        };
+
+        let version = version
+            .parse::<u64>()
+            .map_err(|_| StorageError::DeserializationError("Invalid version".to_string()))?;
+
+        Ok(Some((version, value)))
+    }
+
+    /// Update a versioned record if its version matches, using a Lua script
+    async fn compare_and_set_versioned(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected_version: u64,
+        new_value: String,
+    ) -> Result<Option<u64>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "compare_and_set_versioned");
+        let key = RedisKey::from(self.key(key)?);
+
+        // The script returns nil when the version doesn't match
+        self.client
+            .eval(
+                COMPARE_AND_SET_VERSIONED_SCRIPT,
+                key,
+                vec![expected_version.to_string(), new_value],
+            )
+            .await
+            .map_err(StorageError::from)
+    }
+}
+
+#[async_trait]
+impl KeyExists for RedisStorageModule {
+    /// Check whether a key exists
+    async fn exists(&self, key: impl AsRef<str> + Send) -> Result<bool, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "exists");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client.exists(&key).await.map_err(StorageError::from)
+    }
+
+    /// Check several keys with one pipelined EXISTS per key
+    ///
+    /// A single multi-key EXISTS only reports how many keys exist, so each key
+    /// is checked separately to get index-aligned results
+    async fn exists_many(&self, keys: Vec<String>) -> Result<Vec<bool>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "exists_many");
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let pipeline = self.client.pipeline();
+        for key in keys {
+            let key = RedisKey::from(self.key(key)?);
+            pipeline.exists::<(), _>(key).await?;
+        }
+
+        let results: Vec<RedisValue> = pipeline.all().await?;
+
+        Ok(results
+            .iter()
+            .map(|count| count.as_i64().is_some_and(|count| count > 0))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl StringExpiry for RedisStorageModule {
+    /// Get the remaining TTL of a key using the TTL command
+    ///
+    /// Keys without a TTL and missing keys both yield `None`
+    async fn ttl(&self, key: impl AsRef<str> + Send) -> Result<Option<u64>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "ttl");
+        let key = RedisKey::from(self.key(key)?);
+
+        // TTL returns -1 for keys without expiry and -2 for missing keys
+        let ttl: i64 = self.client.ttl(&key).await?;
+
+        Ok(u64::try_from(ttl).ok())
+    }
+
+    /// Set the TTL of an existing key using the EXPIRE command
+    async fn expire(&self, key: impl AsRef<str> + Send, expiry: u64) -> Result<bool, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "expire");
+        let key = RedisKey::from(self.key(key)?);
+
+        // EXPIRE returns false when the key doesn't exist
+        self.client
+            .expire(&key, expiry as i64)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    /// Extend the TTL of a key using EXPIRE with the GT flag (Redis 7.0+)
+    ///
+    /// Keys without a TTL count as never expiring, so GT leaves them untouched
+    async fn expire_if_longer(
+        &self,
+        key: impl AsRef<str> + Send,
+        expiry: u64,
+    ) -> Result<bool, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "expire_if_longer");
+        let key = RedisKey::from(self.key(key)?);
+
+        // EXPIRE returns 1 only when the TTL was set
+        let updated: i64 = self
+            .client
+            .custom(
+                fred::cmd!("EXPIRE"),
+                vec![
+                    RedisValue::from(key),
+                    RedisValue::from(expiry as i64),
+                    RedisValue::from("GT"),
+                ],
+            )
+            .await?;
+
+        Ok(updated == 1)
+    }
+
+    /// Remove the TTL of a key using the PERSIST command
+    async fn persist(&self, key: impl AsRef<str> + Send) -> Result<bool, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "persist");
+        let key = RedisKey::from(self.key(key)?);
+
+        // PERSIST returns false when the key doesn't exist or has no TTL
+        self.client.persist(&key).await.map_err(StorageError::from)
+    }
+
+    /// Store a string expiring at a Unix timestamp (SET ... EXAT)
+    async fn store_with_expiry_at(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        at_unix_secs: u64,
+    ) -> Result<StoreState, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "store_with_expiry_at");
+        let key = RedisKey::from(self.key(key)?);
+
+        // EXAT rejects 0, which lies in the past like any other elapsed timestamp
+        let expiration = match at_unix_secs {
+            0 => EXPIRED,
+            at_unix_secs => Expiration::EXAT(at_unix_secs as i64),
+        };
+
+        let exists = self.client.exists(&key).await?;
+
+        self.client
+            .set::<String, _, _>(key, value, Some(expiration), None, false)
+            .await?;
+
+        Ok(if exists {
+            StoreState::Updated
+        } else {
+            StoreState::New
+        })
+    }
+
+    /// Store a string with an optional expiry in milliseconds (SET ... PX)
+    ///
+    /// Without an expiry, the configured `default_expiry` is applied
+    async fn store_with_expiry_ms(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry_ms: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "store_with_expiry_ms");
+        let key = RedisKey::from(self.key(key)?);
+
+        let expiration = match expiry_ms {
+            Some(0) => Some(EXPIRED),
+            Some(ms) => Some(Expiration::PX(ms as i64)),
+            None => self.expiration(None),
+        };
+
+        let exists = self.client.exists(&key).await?;
+
+        self.client
+            .set::<String, _, _>(key, value, expiration, None, false)
+            .await?;
+
+        Ok(if exists {
+            StoreState::Updated
+        } else {
+            StoreState::New
+        })
+    }
+}
+
+/// Lua script that replaces a string only if it matches the expected value.
+///
+/// ARGV[1] is "1" when a current value is expected (given in ARGV[2]) and "0"
+/// when the key must be absent. Returns 1 if the value was swapped, 0 otherwise.
+const COMPARE_AND_SWAP_SCRIPT: &str = r#"
+local current = redis.call('GET', KEYS[1])
+if ARGV[1] == '1' then
+    if current ~= ARGV[2] then
+        return 0
+    end
+    redis.call('SET', KEYS[1], ARGV[3], 'KEEPTTL')
+else
+    if current then
+        return 0
+    end
+    redis.call('SET', KEYS[1], ARGV[3])
+end
+return 1
+"#;
+
+#[async_trait]
+impl ConditionalStringStorage for RedisStorageModule {
+    /// Store a string only if the key is absent (SET ... NX)
+    async fn store_if_absent(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "store_if_absent");
+        let key = RedisKey::from(self.key(key)?);
+
+        // SET NX replies with OK when written and nil otherwise
+        let result: Option<String> = self
+            .client
+            .set(
+                key,
+                value,
+                self.expiration(expiry),
+                Some(SetOptions::NX),
+                false,
+            )
+            .await?;
+
+        Ok(if result.is_some() {
+            StoreState::New
+        } else {
+            StoreState::Unchanged
+        })
+    }
+
+    /// Swap a string value if it matches, using a Lua script
+    async fn compare_and_swap(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected: Option<String>,
+        new: String,
+    ) -> Result<StoreState, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "compare_and_swap");
+        let key = RedisKey::from(self.key(key)?);
+
+        // A successful swap creates the key if no current value was expected
+        let written = if expected.is_some() {
+            StoreState::Updated
+        } else {
+            StoreState::New
+        };
+
+        // ARGV[1] flags whether a current value is expected at all
+        let args = match expected {
+            Some(expected) => vec!["1".to_string(), expected, new],
+            None => vec!["0".to_string(), String::new(), new],
+        };
+
+        let swapped: i64 = self.client.eval(COMPARE_AND_SWAP_SCRIPT, key, args).await?;
+
+        Ok(if swapped == 1 {
+            written
+        } else {
+            StoreState::Unchanged
+        })
+    }
+}
+
+#[async_trait]
+impl FloatAtomicStorage for RedisStorageModule {
+    /// Store a floating-point value
+    ///
+    /// Overwriting an existing key keeps its TTL (SET ... KEEPTTL); a new key
+    /// gets the configured `default_expiry`, if any
+    async fn atomic_store_f64(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: f64,
+    ) -> Result<StoreState, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "atomic_store_f64");
+        let key = RedisKey::from(self.key(key)?);
+
+        // Check if key exists before setting
+        let exists = self.client.exists(&key).await?;
+
+        // Plain SET would clear the TTL of an existing key, so keep it instead
+        let expiration = if exists {
+            Some(Expiration::KEEPTTL)
+        } else {
+            self.expiration(None)
+        };
+
+        self.client
+            .set::<String, _, _>(key, value.to_string(), expiration, None, false)
+            .await?;
+
+        // Return store state based on previous existence
+        Ok(if exists {
+            StoreState::Updated
+        } else {
+            StoreState::New
+        })
+    }
+
+    /// Load a floating-point value
+    async fn atomic_load_f64(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<f64>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "atomic_load_f64");
+        let key = RedisKey::from(self.key(key)?);
+
+        // Get the value from Redis
+        let result: Option<String> = self.client.get(&key).await?;
+
+        // Parse the value to f64
+        result
+            .map(|s| {
+                s.parse::<f64>()
+                    .map_err(|_| StorageError::DeserializationError("Invalid float".to_string()))
+            })
+            .transpose()
+    }
+
+    /// Increment a floating-point value using INCRBYFLOAT
+    ///
+    /// The addition happens server-side, which replies with the new value as a
+    /// string. Redis rejects increments that would produce NaN or infinity, and
+    /// a stored value that isn't a float fails with `DeserializationError`
+    async fn atomic_increment_f64(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: f64,
+    ) -> Result<f64, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "atomic_increment_f64");
+        let key = RedisKey::from(self.key(key)?);
+
+        let reply: String = self
+            .client
+            .incr_by_float(&key, value)
+            .await
+            .map_err(float_increment_error)?;
+
+        reply
+            .parse::<f64>()
+            .map_err(|_| StorageError::DeserializationError(format!("Invalid float: {reply}")))
+    }
+}
+
+#[async_trait]
+impl BatchStringStorage for RedisStorageModule {
+    /// Store multiple strings in a single pipelined round-trip
+    ///
+    /// In cluster mode the commands are routed to each key's node individually,
+    /// so keys may span hash slots
+    ///
+    /// Without an expiry, the configured `default_expiry` is applied
+    async fn store_many(
+        &self,
+        entries: Vec<(String, String)>,
+        expiry: Option<u64>,
+    ) -> Result<Vec<StoreState>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "store_many");
+        if entries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let expiration = self.expiration(expiry);
+        let pipeline = self.client.pipeline();
+
+        // Queue an EXISTS before each SET so every entry reports its own store state
+        for (key, value) in entries {
+            let key = RedisKey::from(self.key(key)?);
+            pipeline.exists::<(), _>(&key).await?;
+            pipeline
+                .set::<(), _, _>(key, value, expiration.clone(), None, false)
+                .await?;
+        }
+
+        let results: Vec<RedisValue> = pipeline.all().await?;
+
+        // Results alternate between the EXISTS reply and the SET reply
+        Ok(results
+            .chunks(2)
+            .map(|pair| match pair[0].as_i64() {
+                Some(0) => StoreState::New,
+                _ => StoreState::Updated,
+            })
+            .collect())
+    }
+
+    /// Load multiple strings with a single MGET
+    ///
+    /// In cluster mode all keys must map to the same hash slot, otherwise Redis
+    /// rejects the command with a CROSSSLOT error
+    async fn load_many(&self, keys: Vec<String>) -> Result<Vec<Option<String>>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "load_many");
+        // MGET requires at least one key
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<RedisKey> = keys
+            .into_iter()
+            .map(|key| self.key(key).map(RedisKey::from))
+            .collect::<Result<_, _>>()?;
+
+        self.client.mget(keys).await.map_err(StorageError::from)
+    }
+}
+
+#[async_trait]
+impl KeyListing for RedisStorageModule {
+    /// List keys under a prefix using cursor-based SCAN rather than the blocking KEYS
+    ///
+    /// Covers every key in the database, whichever kind of value it holds
+    async fn keys_with_prefix(&self, prefix: String) -> Result<Vec<String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "keys_with_prefix");
+        self.scan_keys(format!("{}*", escape_pattern(&prefix)), None)
+            .await
+    }
+}
+
+// Only available with the `redis-admin` feature, since clearing a shared
+// Redis database is destructive
+#[cfg(feature = "redis-admin")]
+#[async_trait]
+impl crate::asynchronous::AdminStorage for RedisStorageModule {
+    /// Remove every key in the current database with FLUSHDB
+    ///
+    /// Other databases on the same server are left untouched
+    async fn clear(&self) -> Result<(), StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "clear");
+        // Cluster nodes only have database 0, so flush every primary node
+        if self.client.is_clustered() {
+            return self
+                .client
+                .flushall_cluster()
+                .await
+                .map_err(StorageError::from);
+        }
+
+        // fred has no dedicated FLUSHDB command, and FLUSHALL would wipe every database
+        self.client
+            .custom::<(), RedisValue>(fred::cmd!("FLUSHDB"), vec![])
+            .await
+            .map_err(StorageError::from)
+    }
+
+    /// Count the keys in the current database with DBSIZE
+    ///
+    /// In cluster mode the sizes of all primary nodes are summed
+    async fn len(&self) -> Result<usize, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "len");
+        if self.client.is_clustered() {
+            let primaries = self
+                .client
+                .cached_cluster_state()
+                .map(|state| state.unique_primary_nodes())
+                .unwrap_or_default();
+
+            let mut total = 0;
+            for server in primaries {
+                let size: i64 = self
+                    .client
+                    .with_cluster_node(server)
+                    .custom(fred::cmd!("DBSIZE"), Vec::<RedisValue>::new())
+                    .await?;
+                total += size as usize;
+            }
+            return Ok(total);
+        }
+
+        let size: i64 = self
+            .client
+            .custom(fred::cmd!("DBSIZE"), Vec::<RedisValue>::new())
+            .await?;
+        Ok(size as usize)
+    }
+}
+
+#[async_trait]
+impl HealthCheck for RedisStorageModule {
+    /// Check that the server responds to PING
+    async fn ping(&self) -> Result<(), StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "ping");
+        self.client.ping::<()>().await.map_err(StorageError::from)
+    }
+}
+
+impl RedisStorageModule {
+    /// Collect all keys matching a glob pattern, following the SCAN cursor to completion
+    ///
+    /// In cluster mode every primary node is scanned. With `kind` set, only keys
+    /// of that type are returned
+    async fn scan_keys(
+        &self,
+        pattern: String,
+        kind: Option<ScanType>,
+    ) -> Result<Vec<String>, StorageError> {
+        if self.client.is_clustered() {
+            collect_scan(self.client.scan_cluster(pattern, Some(100), kind)).await
+        } else {
+            collect_scan(self.client.scan(pattern, Some(100), kind)).await
+        }
+    }
+
+    /// Stream all keys matching a glob pattern, following the SCAN cursor lazily
+    ///
+    /// Keys are fetched a page of 100 at a time, with the next page requested
+    /// as soon as the previous one arrives, so memory stays bounded however
+    /// large the keyspace. As with any SCAN, keys modified during the scan may
+    /// be missed or yielded twice. Keys are returned as stored, so long keys
+    /// appear hashed when `hash_keys_over` is set. In cluster mode every
+    /// primary node is scanned
+    ///
+    /// # Arguments
+    /// * `pattern` - The glob pattern keys must match, e.g. `user:*`
+    ///
+    /// # Returns
+    /// A stream of the matching keys, with any failed SCAN reported as an error item
+    pub fn scan_iter(
+        &self,
+        pattern: String,
+    ) -> impl Stream<Item = Result<String, StorageError>> + Send {
+        let pages = if self.client.is_clustered() {
+            self.client.scan_cluster(pattern, Some(100), None).boxed()
+        } else {
+            self.client.scan(pattern, Some(100), None).boxed()
+        };
+
+        pages.flat_map(|page| {
+            let mut keys: Vec<Result<String, StorageError>> = Vec::new();
+            match page {
+                Ok(mut page) => {
+                    if let Some(results) = page.take_results() {
+                        keys.extend(
+                            results
+                                .into_iter()
+                                .filter_map(|key| key.into_string())
+                                .map(Ok),
+                        );
+                    }
+                    // Request the next page of results
+                    if let Err(e) = page.next() {
+                        keys.push(Err(e.into()));
+                    }
+                }
+                Err(e) => keys.push(Err(e.into())),
+            }
+            futures::stream::iter(keys)
+        })
+    }
+}
+
+/// Number of keys removed by a single DEL in `delete_prefix`
+const DELETE_BATCH_SIZE: usize = 500;
+
+#[async_trait]
+impl BulkDelete for RedisStorageModule {
+    /// Delete keys under a prefix found with SCAN, in batches of DEL commands
+    ///
+    /// Not atomic: keys created under the prefix while the scan runs may
+    /// survive, and a failed batch leaves earlier batches deleted
+    async fn delete_prefix(&self, prefix: String) -> Result<u64, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "delete_prefix");
+        let keys = self
+            .scan_keys(format!("{}*", escape_pattern(&prefix)), None)
+            .await?;
+
+        // A multi-key DEL is rejected in a cluster when the keys hash to different slots
+        let batch_size = if self.client.is_clustered() {
+            1
+        } else {
+            DELETE_BATCH_SIZE
+        };
+
+        let mut deleted = 0;
+        for batch in keys.chunks(batch_size) {
+            deleted += self.client.del::<u64, _>(batch.to_vec()).await?;
+        }
+
+        Ok(deleted)
+    }
+}
+
+/// Drain a stream of SCAN pages into the keys they contain
+async fn collect_scan(
+    pages: impl Stream<Item = Result<ScanResult, RedisError>>,
+) -> Result<Vec<String>, StorageError> {
+    let mut keys = Vec::new();
+    let mut pages = std::pin::pin!(pages);
+
+    while let Some(page) = pages.next().await {
+        let mut page = page?;
+
+        if let Some(results) = page.take_results() {
+            keys.extend(results.into_iter().filter_map(|key| key.into_string()));
+        }
+
+        // Request the next page of results
+        page.next()?;
+    }
+
+    Ok(keys)
+}
+
+/// Escape glob metacharacters so a literal prefix can be used in a MATCH pattern
+fn escape_pattern(prefix: &str) -> String {
+    let mut escaped = String::with_capacity(prefix.len());
+    for c in prefix.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Map an INCRBY/DECRBY error, reporting Redis' overflow rejection as such
+fn increment_error(error: RedisError) -> StorageError {
+    if error.details().contains("would overflow") {
+        StorageError::Overflow(error.details().to_string())
+    } else {
+        error.into()
+    }
+}
+
+/// Map an INCRBYFLOAT error, reporting a stored value that isn't a float as such
+fn float_increment_error(error: RedisError) -> StorageError {
+    if error.details().contains("not a valid float") {
+        StorageError::DeserializationError(error.details().to_string())
+    } else {
+        error.into()
+    }
+}
+
+/// Lua script that counts a request in a fixed rate limit window.
+///
+/// The counter is incremented with INCR and given its expiry only when this
+/// created it, so later requests don't extend the window. Returns the count.
+const CHECK_RATE_SCRIPT: &str = r#"
+local count = redis.call('INCR', KEYS[1])
+if count == 1 then
+    redis.call('EXPIRE', KEYS[1], ARGV[1])
+end
+return count
+"#;
+
+#[async_trait]
+impl RateLimiter for RedisStorageModule {
+    /// Count a request with INCR and a conditional EXPIRE, run atomically in Lua
+    async fn check_rate(
+        &self,
+        key: impl AsRef<str> + Send,
+        limit: i64,
+        window_secs: u64,
+    ) -> Result<bool, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "check_rate");
+        let key = RedisKey::from(self.key(key)?);
+
+        // Running both commands in one script keeps a counter from being left without a TTL
+        let count: i64 = self
+            .client
+            .eval(CHECK_RATE_SCRIPT, key, vec![window_secs.to_string()])
+            .await?;
+
+        Ok(count <= limit)
+    }
+}
+
+#[async_trait]
+impl StringAppend for RedisStorageModule {
+    /// Append to a string value using APPEND, which keeps any existing TTL
+    async fn append(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<u64, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "append");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client
+            .append(key, value)
+            .await
+            .map_err(StorageError::from)
+    }
+}
+
+/// Largest offset passed to GETRANGE; Redis strings are at most 512 MB
+const MAX_STRING_OFFSET: usize = 512 * 1024 * 1024;
+
+/// Lua script that reads a byte range of a string, distinguishing a missing key.
+///
+/// ARGV[1] and ARGV[2] are the start and exclusive end offsets. Returns nil if
+/// the key doesn't exist, since GETRANGE alone replies with an empty string.
+const GET_RANGE_SCRIPT: &str = r#"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+    return false
+end
+local first = tonumber(ARGV[1])
+local last = tonumber(ARGV[2]) - 1
+if last < first then
+    return ''
+end
+return redis.call('GETRANGE', KEYS[1], first, last)
+"#;
+
+#[async_trait]
+impl StringRange for RedisStorageModule {
+    /// Read a byte range of a string value using GETRANGE
+    async fn get_range(
+        &self,
+        key: impl AsRef<str> + Send,
+        start: usize,
+        end: usize,
+    ) -> Result<Option<String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "get_range");
+        let key = RedisKey::from(self.key(key)?);
+
+        // Clamp offsets so they stay exact integers in Lua
+        let args = vec![
+            start.min(MAX_STRING_OFFSET).to_string(),
+            end.min(MAX_STRING_OFFSET).to_string(),
+        ];
+
+        // GETRANGE works on bytes, so the range may split a UTF-8 character
+        let result: Option<Vec<u8>> = self.client.eval(GET_RANGE_SCRIPT, key, args).await?;
+
+        Ok(result.map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+    }
+}
+
+#[async_trait]
+impl ListStorage for RedisStorageModule {
+    /// Append a value to a list using RPUSH
+    async fn push_back(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<u64, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "push_back");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client
+            .rpush(key, value)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    /// Prepend a value to a list using LPUSH
+    async fn push_front(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<u64, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "push_front");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client
+            .lpush(key, value)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    /// Remove the last value of a list using RPOP
+    async fn pop_back(&self, key: impl AsRef<str> + Send) -> Result<Option<String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "pop_back");
+        let key = RedisKey::from(self.key(key)?);
+
+        // Without a count, RPOP replies with a single value or nil
+        self.client
+            .rpop(key, None)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    /// Remove the first value of a list using LPOP
+    async fn pop_front(&self, key: impl AsRef<str> + Send) -> Result<Option<String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "pop_front");
+        let key = RedisKey::from(self.key(key)?);
+
+        // Without a count, LPOP replies with a single value or nil
+        self.client
+            .lpop(key, None)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    /// Get the length of a list using LLEN
+    async fn list_len(&self, key: impl AsRef<str> + Send) -> Result<u64, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "list_len");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client.llen(key).await.map_err(StorageError::from)
+    }
+
+    /// Get a range of list values using LRANGE
+    async fn list_range(
+        &self,
+        key: impl AsRef<str> + Send,
+        start: i64,
+        stop: i64,
+    ) -> Result<Vec<String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "list_range");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client
+            .lrange(key, start, stop)
+            .await
+            .map_err(StorageError::from)
+    }
+}
+
+#[async_trait]
+impl SetStorage for RedisStorageModule {
+    /// Add a member to a set using SADD
+    async fn set_add(
+        &self,
+        key: impl AsRef<str> + Send,
+        member: String,
+    ) -> Result<bool, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "set_add");
+        let key = RedisKey::from(self.key(key)?);
+
+        // SADD replies with the number of members that were newly added
+        let added: i64 = self.client.sadd(key, member).await?;
+
+        Ok(added == 1)
+    }
+
+    /// Remove a member from a set using SREM
+    async fn set_remove(
+        &self,
+        key: impl AsRef<str> + Send,
+        member: String,
+    ) -> Result<bool, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "set_remove");
+        let key = RedisKey::from(self.key(key)?);
+
+        // SREM replies with the number of members that were removed
+        let removed: i64 = self.client.srem(key, member).await?;
+
+        Ok(removed == 1)
+    }
+
+    /// Check set membership using SISMEMBER
+    async fn set_contains(
+        &self,
+        key: impl AsRef<str> + Send,
+        member: String,
+    ) -> Result<bool, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "set_contains");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client
+            .sismember(key, member)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    /// Get every member of a set using SMEMBERS
+    async fn set_members(&self, key: impl AsRef<str> + Send) -> Result<Vec<String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "set_members");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client.smembers(key).await.map_err(StorageError::from)
+    }
+
+    /// Get the size of a set using SCARD
+    async fn set_len(&self, key: impl AsRef<str> + Send) -> Result<u64, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "set_len");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client.scard(key).await.map_err(StorageError::from)
+    }
+}
+
+#[async_trait]
+impl HashStorage for RedisStorageModule {
+    /// Set a field of a map using HSET
+    async fn hash_set(
+        &self,
+        key: impl AsRef<str> + Send,
+        field: String,
+        value: String,
+    ) -> Result<StoreState, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "hash_set");
+        let key = RedisKey::from(self.key(key)?);
+
+        // HSET replies with the number of fields that were newly created
+        let created: i64 = self.client.hset(key, (field, value)).await?;
+
+        Ok(if created == 1 {
+            StoreState::New
+        } else {
+            StoreState::Updated
+        })
+    }
+
+    /// Get a field of a map using HGET
+    async fn hash_get(
+        &self,
+        key: impl AsRef<str> + Send,
+        field: String,
+    ) -> Result<Option<String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "hash_get");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client
+            .hget(key, field)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    /// Get every field of a map using HGETALL
+    async fn hash_get_all(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<HashMap<String, String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "hash_get_all");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client.hgetall(key).await.map_err(StorageError::from)
+    }
+
+    /// Delete a field of a map using HDEL
+    async fn hash_delete(
+        &self,
+        key: impl AsRef<str> + Send,
+        field: String,
+    ) -> Result<bool, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "hash_delete");
+        let key = RedisKey::from(self.key(key)?);
+
+        // HDEL replies with the number of fields that were removed
+        let removed: i64 = self.client.hdel(key, field).await?;
+
+        Ok(removed == 1)
+    }
+
+    /// Get the number of fields in a map using HLEN
+    async fn hash_len(&self, key: impl AsRef<str> + Send) -> Result<u64, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "hash_len");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client.hlen(key).await.map_err(StorageError::from)
+    }
+}
+
+#[async_trait]
+impl KeyRename for RedisStorageModule {
+    /// Rename a key with RENAME, which keeps its TTL and replaces the destination
+    ///
+    /// In a cluster both keys must hash to the same slot
+    async fn rename(
+        &self,
+        from: impl AsRef<str> + Send,
+        to: impl AsRef<str> + Send,
+    ) -> Result<(), StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "rename");
+        let source = RedisKey::from(self.key(&from)?);
+        let destination = RedisKey::from(self.key(to)?);
+
+        self.client
+            .rename::<(), _, _>(source, destination)
+            .await
+            .map_err(|e| {
+                // RENAME fails with "ERR no such key" when the source is missing
+                if e.details().contains("no such key") {
+                    StorageError::NotFound(from.as_ref().to_string())
+                } else {
+                    e.into()
+                }
+            })
+    }
+}
+
+#[async_trait]
+impl BatchRawStorage for RedisStorageModule {
+    /// Load multiple binary values with a single MGET
+    ///
+    /// In cluster mode all keys must map to the same hash slot, otherwise Redis
+    /// rejects the command with a CROSSSLOT error
+    async fn load_raw_many(&self, keys: Vec<String>) -> Result<Vec<Option<Vec<u8>>>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "load_raw_many");
+        // MGET requires at least one key
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<RedisKey> = keys
+            .into_iter()
+            .map(|key| self.key(key).map(RedisKey::from))
+            .collect::<Result<_, _>>()?;
+
+        self.client.mget(keys).await.map_err(StorageError::from)
+    }
+}
+
+#[async_trait]
+impl StringGetSet for RedisStorageModule {
+    /// Replace a string and return the previous one with SET ... GET (Redis 6.2+)
+    ///
+    /// The new value is stored without expiration; `default_expiry` is not applied
+    async fn get_set(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<Option<String>, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "get_set");
+        let key = RedisKey::from(self.key(key)?);
+
+        self.client
+            .set(key, value, None, None, true)
+            .await
+            .map_err(StorageError::from)
+    }
+}
+
+/// Lua script that increments an integer and clamps it into a range.
+///
+/// INCRBY does the addition, so a non-integer value or an overflow fails as
+/// it would outside the script. Lua numbers are doubles, which can't hold
+/// every `i64`, so the new value is read back with GET and compared with the
+/// bounds as decimal strings; the clamp is exact over the whole `i64` range.
+const INCREMENT_BOUNDED_SCRIPT: &str = r#"
+local function compare(a, b)
+    local a_negative = a:sub(1, 1) == '-'
+    local b_negative = b:sub(1, 1) == '-'
+    if a_negative ~= b_negative then
+        return a_negative and -1 or 1
+    end
+    local sign = a_negative and -1 or 1
+    if #a ~= #b then
+        return (#a < #b and -1 or 1) * sign
+    end
+    if a == b then
+        return 0
+    end
+    return (a < b and -1 or 1) * sign
+end
+
+redis.call('INCRBY', KEYS[1], ARGV[1])
+local value = redis.call('GET', KEYS[1])
+if compare(value, ARGV[2]) < 0 then
+    value = ARGV[2]
+    redis.call('SET', KEYS[1], value, 'KEEPTTL')
+elseif compare(value, ARGV[3]) > 0 then
+    value = ARGV[3]
+    redis.call('SET', KEYS[1], value, 'KEEPTTL')
+end
+return value
+"#;
+
+#[async_trait]
+impl BoundedIncrement for RedisStorageModule {
+    /// Increment with INCRBY and clamp the result, run atomically in Lua
+    async fn atomic_increment_bounded(
+        &self,
+        key: impl AsRef<str> + Send,
+        delta: i64,
+        min: i64,
+        max: i64,
+    ) -> Result<i64, StorageError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::OpTimer::start("redis", "atomic_increment_bounded");
+        if min > max {
+            return Err(StorageError::InvalidArgument(format!(
+                "min ({min}) is greater than max ({max})"
+            )));
+        }
+        let key = RedisKey::from(self.key(key)?);
+        let args = vec![delta.to_string(), min.to_string(), max.to_string()];
+
+        self.client
+            .eval(INCREMENT_BOUNDED_SCRIPT, key, args)
+            .await
+            .map_err(increment_error)
+    }
 }
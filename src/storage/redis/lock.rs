@@ -0,0 +1,222 @@
+//! Distributed locks built on Redis.
+//!
+//! A lock is a key holding a random token, created with `SET ... NX PX` so it
+//! can only be taken while absent and is released automatically once its TTL
+//! runs out. Releasing checks the token in a Lua script, so a holder whose lock
+//! already expired can never release a lock since taken by someone else.
+//!
+//! This is a single-instance lock, not the multi-node Redlock algorithm: if the
+//! Redis primary fails over before the lock key is replicated, two holders may
+//! briefly coexist. Use it for efficiency, not where correctness depends on
+//! strict mutual exclusion.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use fred::{
+    interfaces::{KeysInterface, LuaInterface},
+    types::{Expiration, RedisKey, SetOptions},
+};
+
+use crate::errors::StorageError;
+
+use super::RedisStorageModule;
+
+/// Prefix of the keys holding locks
+const LOCK_KEY_PREFIX: &str = "lock:";
+/// How long `acquire` keeps retrying by default
+pub const DEFAULT_LOCK_WAIT: Duration = Duration::from_secs(5);
+/// Delay between attempts made by `acquire` by default
+pub const DEFAULT_LOCK_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Lua script that deletes a lock only if it still holds the given token.
+///
+/// Returns 1 if the lock was released, 0 if it had expired or was taken by
+/// someone else.
+const RELEASE_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+/// Distributed lock manager backed by a Redis storage module
+#[derive(Clone)]
+pub struct RedisLock {
+    /// Storage module whose connection holds the locks
+    storage: RedisStorageModule,
+    /// How long `acquire` keeps retrying before giving up
+    wait: Duration,
+    /// Delay between attempts made by `acquire`
+    retry_delay: Duration,
+}
+
+/// A held lock
+///
+/// Dropping the guard does not release the lock, since releasing requires a
+/// round-trip to Redis; call [`LockGuard::release`] instead. A guard that is
+/// dropped or leaked keeps the lock until its TTL expires.
+#[must_use = "the lock is held until released or its TTL expires"]
+pub struct LockGuard {
+    /// Storage module used to release the lock
+    storage: RedisStorageModule,
+    /// Key holding the lock
+    key: RedisKey,
+    /// Token identifying this holder
+    token: String,
+}
+
+impl RedisStorageModule {
+    /// Create a lock manager using this module's connection
+    ///
+    /// # Returns
+    /// A `RedisLock` with the default wait and retry delay
+    pub fn lock(&self) -> RedisLock {
+        RedisLock::new(self.clone())
+    }
+}
+
+impl RedisLock {
+    /// Create a lock manager
+    ///
+    /// # Arguments
+    /// * `storage` - Storage module whose connection holds the locks
+    ///
+    /// # Returns
+    /// A `RedisLock` with the default wait and retry delay
+    pub fn new(storage: RedisStorageModule) -> Self {
+        Self {
+            storage,
+            wait: DEFAULT_LOCK_WAIT,
+            retry_delay: DEFAULT_LOCK_RETRY_DELAY,
+        }
+    }
+
+    /// Set how long `acquire` waits for a held lock to become free
+    ///
+    /// # Arguments
+    /// * `wait` - How long to keep retrying before giving up
+    /// * `retry_delay` - Delay between attempts
+    pub fn with_wait(mut self, wait: Duration, retry_delay: Duration) -> Self {
+        self.wait = wait;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Acquire a lock, waiting for it to become free
+    ///
+    /// Retries every `retry_delay` until the lock is taken or `wait` has passed.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the lock
+    /// * `ttl_ms` - Milliseconds after which the lock is released automatically
+    ///
+    /// # Returns
+    /// `Some(LockGuard)` if the lock was taken, `None` if it stayed held by
+    /// someone else for the whole wait, or `StorageError::InvalidArgument` if
+    /// `ttl_ms` is zero or too large for Redis
+    pub async fn acquire(
+        &self,
+        name: String,
+        ttl_ms: u64,
+    ) -> Result<Option<LockGuard>, StorageError> {
+        let deadline = Instant::now() + self.wait;
+
+        loop {
+            if let Some(guard) = self.try_acquire(name.clone(), ttl_ms).await? {
+                return Ok(Some(guard));
+            }
+
+            if Instant::now() + self.retry_delay > deadline {
+                return Ok(None);
+            }
+            tokio::time::sleep(self.retry_delay).await;
+        }
+    }
+
+    /// Acquire a lock if it is free, without waiting (SET ... NX PX)
+    ///
+    /// # Arguments
+    /// * `name` - Name of the lock
+    /// * `ttl_ms` - Milliseconds after which the lock is released automatically
+    ///
+    /// # Returns
+    /// `Some(LockGuard)` if the lock was taken, `None` if it is held by someone else,
+    /// or `StorageError::InvalidArgument` if `ttl_ms` is zero or too large for Redis
+    pub async fn try_acquire(
+        &self,
+        name: String,
+        ttl_ms: u64,
+    ) -> Result<Option<LockGuard>, StorageError> {
+        // Redis rejects a PX that is not a positive 64-bit integer
+        let ttl = i64::try_from(ttl_ms)
+            .ok()
+            .filter(|ttl| *ttl > 0)
+            .ok_or_else(|| {
+                StorageError::InvalidArgument(format!(
+                    "lock TTL ({ttl_ms} ms) must be between 1 and {} ms",
+                    i64::MAX
+                ))
+            })?;
+        let key = RedisKey::from(self.storage.key(format!("{LOCK_KEY_PREFIX}{name}"))?);
+        let token = new_token();
+
+        // SET NX replies with OK when written and nil otherwise
+        let result: Option<String> = self
+            .storage
+            .client
+            .set(
+                key.clone(),
+                token.as_str(),
+                Some(Expiration::PX(ttl)),
+                Some(SetOptions::NX),
+                false,
+            )
+            .await?;
+
+        Ok(result.map(|_| LockGuard {
+            storage: self.storage.clone(),
+            key,
+            token,
+        }))
+    }
+}
+
+impl LockGuard {
+    /// Release the lock if it is still held by this guard
+    ///
+    /// # Returns
+    /// `true` if the lock was released, `false` if it had already expired
+    /// (and may since have been taken by someone else)
+    pub async fn release(self) -> Result<bool, StorageError> {
+        let released: i64 = self
+            .storage
+            .client
+            .eval(RELEASE_SCRIPT, self.key, vec![self.token])
+            .await?;
+
+        Ok(released == 1)
+    }
+}
+
+/// Generate a token that is unique to one acquisition
+///
+/// `RandomState` is seeded from the operating system's randomness, and the
+/// counter keeps tokens distinct within this process.
+fn new_token() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    let mut halves = [0u64; 2];
+    for (index, half) in halves.iter_mut().enumerate() {
+        let mut hasher = RandomState::new().build_hasher();
+        (count, index).hash(&mut hasher);
+        *half = hasher.finish();
+    }
+
+    format!("{:016x}{:016x}", halves[0], halves[1])
+}
@@ -0,0 +1,182 @@
+//! Batched execution of independent commands in a single round-trip.
+//!
+//! A [`Pipeline`] only queues operations; nothing is sent until
+//! [`Pipeline::execute`] runs them as one `fred` pipeline. Commands are not
+//! executed atomically: other clients' commands may interleave with them, and
+//! one failing command does not prevent the others from running.
+
+use fred::{
+    interfaces::KeysInterface,
+    types::{RedisKey, RedisValue},
+};
+
+use crate::{errors::StorageError, types::StoreState};
+
+use super::RedisStorageModule;
+
+/// An operation queued on a [`Pipeline`] or transaction
+///
+/// Keys are kept as given by the caller and mapped when the operation is queued
+pub(super) enum PipelineOp {
+    Set {
+        key: String,
+        value: String,
+        expiry: Option<u64>,
+    },
+    Del {
+        key: String,
+    },
+    Incr {
+        key: String,
+        delta: i64,
+    },
+}
+
+/// Result of one operation of an executed [`Pipeline`] or transaction, in queue order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineResult {
+    /// A string was stored
+    Set(StoreState),
+    /// A key was deleted; `true` if it existed
+    Del(bool),
+    /// An integer was incremented to the contained value
+    Incr(i64),
+}
+
+/// Builder queuing operations to run in a single round-trip
+pub struct Pipeline {
+    /// Storage module whose connection executes the pipeline
+    storage: RedisStorageModule,
+    /// Operations to run, in order
+    ops: Vec<PipelineOp>,
+}
+
+impl RedisStorageModule {
+    /// Start a pipeline of operations on this module's connection
+    ///
+    /// # Returns
+    /// An empty `Pipeline`
+    pub fn pipeline(&self) -> Pipeline {
+        Pipeline {
+            storage: self.clone(),
+            ops: Vec::new(),
+        }
+    }
+}
+
+impl Pipeline {
+    /// Queue storing a string with optional expiry, like `store_with_expiry`
+    ///
+    /// Without an expiry, the configured `default_expiry` is applied
+    pub fn set(mut self, key: String, value: String, expiry: Option<u64>) -> Self {
+        self.ops.push(PipelineOp::Set { key, value, expiry });
+        self
+    }
+
+    /// Queue deleting a key
+    pub fn del(mut self, key: String) -> Self {
+        self.ops.push(PipelineOp::Del { key });
+        self
+    }
+
+    /// Queue incrementing an integer, like `atomic_increment`
+    pub fn incr(mut self, key: String, delta: i64) -> Self {
+        self.ops.push(PipelineOp::Incr { key, delta });
+        self
+    }
+
+    /// Get the number of queued operations
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Check whether no operations are queued
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Send every queued operation in a single round-trip
+    ///
+    /// # Returns
+    /// One result per queued operation, in queue order. Fails if any command
+    /// fails, although the other commands may still have been applied. A key
+    /// rejected by the configured validator fails the pipeline before it is sent
+    pub async fn execute(self) -> Result<Vec<PipelineResult>, StorageError> {
+        if self.ops.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let pipeline = self.storage.client.pipeline();
+        queue_ops(&pipeline, &self.storage, &self.ops).await?;
+
+        let replies: Vec<RedisValue> = pipeline.all().await?;
+
+        collect_results(&self.ops, replies)
+    }
+}
+
+/// Queue the commands of `ops` on a pipeline or transaction
+pub(super) async fn queue_ops<C: KeysInterface + Sync>(
+    client: &C,
+    storage: &RedisStorageModule,
+    ops: &[PipelineOp],
+) -> Result<(), StorageError> {
+    for op in ops {
+        match op {
+            PipelineOp::Set { key, value, expiry } => {
+                let key = RedisKey::from(storage.key(key)?);
+                // Queue an EXISTS before the SET so it reports its own store state
+                client.exists::<(), _>(&key).await?;
+                client
+                    .set::<(), _, _>(
+                        key,
+                        value.as_str(),
+                        storage.expiration(*expiry),
+                        None,
+                        false,
+                    )
+                    .await?;
+            }
+            PipelineOp::Del { key } => {
+                let key = RedisKey::from(storage.key(key)?);
+                client.del::<(), _>(key).await?;
+            }
+            PipelineOp::Incr { key, delta } => {
+                let key = RedisKey::from(storage.key(key)?);
+                client.incr_by::<(), _>(key, *delta).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Match the replies of executed commands to the operations that queued them
+pub(super) fn collect_results(
+    ops: &[PipelineOp],
+    replies: Vec<RedisValue>,
+) -> Result<Vec<PipelineResult>, StorageError> {
+    // A SET takes two replies (EXISTS, SET); every other operation takes one
+    let mut replies = replies.into_iter();
+    ops.iter()
+        .map(|op| match op {
+            PipelineOp::Set { .. } => {
+                let exists = replies.next().and_then(|reply| reply.as_i64());
+                replies.next();
+                Ok(PipelineResult::Set(match exists {
+                    Some(0) => StoreState::New,
+                    _ => StoreState::Updated,
+                }))
+            }
+            PipelineOp::Del { .. } => {
+                let removed = replies.next().and_then(|reply| reply.as_i64());
+                Ok(PipelineResult::Del(removed == Some(1)))
+            }
+            PipelineOp::Incr { .. } => replies
+                .next()
+                .and_then(|reply| reply.as_i64())
+                .map(PipelineResult::Incr)
+                .ok_or_else(|| StorageError::DeserializationError("Invalid integer".to_string())),
+        })
+        .collect()
+}
@@ -0,0 +1,114 @@
+//! Redis pub/sub.
+//!
+//! Each subscription opens its own connection, since a connection in
+//! subscriber mode cannot run regular commands. The connection uses the
+//! module's configuration and reconnect policy, and re-subscribes to its
+//! channel after reconnecting, so the stream survives connection loss.
+//! Messages published while the connection is down are not delivered.
+
+use async_trait::async_trait;
+use fred::{
+    clients::SubscriberClient,
+    interfaces::{ClientLike, EventInterface, PubsubInterface},
+    types::Message,
+};
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+use crate::{
+    asynchronous::{MessageStream, PubSub},
+    errors::StorageError,
+};
+
+use super::RedisStorageModule;
+
+#[async_trait]
+impl PubSub for RedisStorageModule {
+    /// Publish a message using PUBLISH
+    ///
+    /// In cluster mode only subscribers connected to the same node are counted
+    async fn publish(&self, channel: String, message: String) -> Result<u64, StorageError> {
+        self.client
+            .publish(channel, message)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    /// Subscribe to a channel on a dedicated connection
+    ///
+    /// The stream only ends if the connection is closed for good, e.g. because
+    /// the reconnect policy gave up
+    async fn subscribe(&self, channel: String) -> Result<MessageStream, StorageError> {
+        let subscriber = SubscriberClient::new(
+            self.client.client_config(),
+            Some(self.client.perf_config()),
+            Some(self.client.connection_config().clone()),
+            self.client.client_reconnect_policy(),
+        );
+
+        subscriber.connect();
+        subscriber.wait_for_connect().await?;
+
+        // Re-subscribe to the tracked channels whenever the connection is re-established
+        let _ = subscriber.manage_subscriptions();
+
+        // Listen before subscribing so no message published in between is missed
+        let messages = subscriber.message_rx();
+        subscriber.subscribe(channel.as_str()).await?;
+
+        let subscription = Subscription {
+            client: subscriber,
+            channel,
+            messages,
+        };
+
+        Ok(Box::pin(futures::stream::unfold(
+            subscription,
+            |mut subscription| async move {
+                let message = subscription.next().await?;
+                Some((message, subscription))
+            },
+        )))
+    }
+}
+
+/// A subscribed connection, closed when dropped
+struct Subscription {
+    /// Connection in subscriber mode
+    client: SubscriberClient,
+    /// The subscribed channel
+    channel: String,
+    /// Messages received on the connection
+    messages: Receiver<Message>,
+}
+
+impl Subscription {
+    /// Wait for the next message on the subscribed channel
+    ///
+    /// Skips messages the receiver lagged behind on; returns `None` once the
+    /// connection is closed
+    async fn next(&mut self) -> Option<String> {
+        loop {
+            match self.messages.recv().await {
+                Ok(message) if *message.channel == *self.channel => {
+                    if let Some(value) = message.value.as_string() {
+                        return Some(value);
+                    }
+                }
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        // Closing requires a round-trip, so do it in the background when possible
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let client = self.client.clone();
+            runtime.spawn(async move {
+                let _ = client.quit().await;
+            });
+        }
+    }
+}
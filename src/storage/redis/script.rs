@@ -0,0 +1,82 @@
+//! Server-side Lua scripting.
+//!
+//! Scripts are loaded once with SCRIPT LOAD and afterwards invoked by their
+//! SHA1 digest with EVALSHA, so the script body is not resent on every call.
+//! Digests are cached per module and shared by its clones.
+
+use fred::{
+    error::RedisError,
+    interfaces::{ClientLike, LuaInterface},
+    types::{RedisKey, RedisValue},
+};
+
+use crate::errors::StorageError;
+
+use super::RedisStorageModule;
+
+impl RedisStorageModule {
+    /// Run a Lua script on the server
+    ///
+    /// The script is loaded on first use and then run with EVALSHA. If the
+    /// server has lost it, e.g. after a restart or SCRIPT FLUSH, it is loaded
+    /// again and the call retried once. Keys are hashed like every other key
+    /// of this module, so scripts see the same keys the storage traits use.
+    ///
+    /// # Arguments
+    /// * `script` - The Lua source of the script
+    /// * `keys` - The keys the script accesses, available as `KEYS`
+    /// * `args` - Additional arguments, available as `ARGV`
+    ///
+    /// # Returns
+    /// The script's reply, or `StorageError::ConnectionError` if it fails
+    pub async fn eval_script(
+        &self,
+        script: &str,
+        keys: Vec<String>,
+        args: Vec<String>,
+    ) -> Result<RedisValue, StorageError> {
+        let keys: Vec<RedisKey> = keys
+            .into_iter()
+            .map(|key| self.key(key).map(RedisKey::from))
+            .collect::<Result<_, _>>()?;
+
+        let cached = self.scripts.get(script).map(|sha| sha.value().clone());
+        let sha = match cached {
+            Some(sha) => sha,
+            None => self.load_script(script).await?,
+        };
+
+        match self
+            .client
+            .evalsha(sha.as_str(), keys.clone(), args.clone())
+            .await
+        {
+            Err(e) if is_noscript(&e) => {
+                let sha = self.load_script(script).await?;
+                self.client
+                    .evalsha(sha, keys, args)
+                    .await
+                    .map_err(StorageError::from)
+            }
+            result => result.map_err(StorageError::from),
+        }
+    }
+
+    /// Load a script on the server and cache its digest
+    async fn load_script(&self, script: &str) -> Result<String, StorageError> {
+        // In cluster mode every primary needs the script, since keys decide the node
+        let sha: String = if self.client.is_clustered() {
+            self.client.script_load_cluster(script).await
+        } else {
+            self.client.script_load(script).await
+        }?;
+
+        self.scripts.insert(script.to_string(), sha.clone());
+        Ok(sha)
+    }
+}
+
+/// Check whether an error reports that the server doesn't know a script
+fn is_noscript(error: &RedisError) -> bool {
+    error.details().starts_with("NOSCRIPT")
+}
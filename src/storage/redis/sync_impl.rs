@@ -0,0 +1,661 @@
+//! Blocking Redis implementation of the synchronous storage traits.
+//!
+//! `fred` only offers an async client, so every method drives the matching
+//! async implementation to completion on the runtime owned by the module.
+//! This keeps the two implementations behaviorally identical.
+
+use std::future::Future;
+
+use crate::{
+    asynchronous,
+    errors::StorageError,
+    sync::{
+        AtomicStorage, BatchRawStorage, BatchStringStorage, BoundedIncrement, BulkDelete,
+        ConditionalStringStorage, FloatAtomicStorage, HashStorage, HealthCheck, KeyExists,
+        KeyListing, KeyRename, ListStorage, RateLimiter, RawStorage, RawStorageWithExpiry,
+        SetStorage, StringAppend, StringExpiry, StringGetSet, StringRange, StringStorage,
+        StringStorageWithExpiry, VersionedStorage,
+    },
+    types::StoreState,
+};
+
+use super::RedisStorageModule;
+
+impl RedisStorageModule {
+    /// Run an async operation to completion on the module's runtime
+    ///
+    /// Fails for modules created with [`RedisStorageModule::new`], which have no
+    /// runtime of their own; use [`RedisStorageModule::new_blocking`] instead
+    fn block_on<T>(
+        &self,
+        future: impl Future<Output = Result<T, StorageError>>,
+    ) -> Result<T, StorageError> {
+        match &self.runtime {
+            Some(runtime) => runtime.block_on(future),
+            None => Err(StorageError::ConnectionError(
+                "blocking operations require a module created with `new_blocking`".to_string(),
+            )),
+        }
+    }
+}
+
+impl StringStorageWithExpiry for RedisStorageModule {
+    /// Store a string with optional expiry
+    fn store_with_expiry(
+        &self,
+        key: impl AsRef<str>,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.block_on(asynchronous::StringStorageWithExpiry::store_with_expiry(
+            self,
+            key.as_ref(),
+            value,
+            expiry,
+        ))
+    }
+
+    /// Store a string while keeping any existing TTL
+    fn store_keep_ttl(
+        &self,
+        key: impl AsRef<str>,
+        value: String,
+    ) -> Result<StoreState, StorageError> {
+        self.block_on(asynchronous::StringStorageWithExpiry::store_keep_ttl(
+            self,
+            key.as_ref(),
+            value,
+        ))
+    }
+}
+
+impl StringStorage for RedisStorageModule {
+    /// Load a string value
+    fn load_string(&self, key: impl AsRef<str>) -> Result<Option<String>, StorageError> {
+        self.block_on(asynchronous::StringStorage::load_string(self, key.as_ref()))
+    }
+
+    /// Delete a string value
+    fn delete_string(&self, key: impl AsRef<str>) -> Result<(), StorageError> {
+        self.block_on(asynchronous::StringStorage::delete_string(
+            self,
+            key.as_ref(),
+        ))
+    }
+
+    /// Load and delete a string value
+    fn take_string(&self, key: impl AsRef<str>) -> Result<Option<String>, StorageError> {
+        self.block_on(asynchronous::StringStorage::take_string(self, key.as_ref()))
+    }
+}
+
+impl RawStorageWithExpiry for RedisStorageModule {
+    /// Store raw bytes with optional expiry
+    fn store_raw_with_expiry(
+        &self,
+        key: impl AsRef<str>,
+        value: Vec<u8>,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.block_on(asynchronous::RawStorageWithExpiry::store_raw_with_expiry(
+            self,
+            key.as_ref(),
+            value,
+            expiry,
+        ))
+    }
+
+    /// Store raw bytes while keeping any existing TTL
+    fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str>,
+        value: Vec<u8>,
+    ) -> Result<StoreState, StorageError> {
+        self.block_on(asynchronous::RawStorageWithExpiry::store_raw_keep_ttl(
+            self,
+            key.as_ref(),
+            value,
+        ))
+    }
+}
+
+impl RawStorage for RedisStorageModule {
+    /// Load raw bytes
+    fn load_raw(&self, key: impl AsRef<str>) -> Result<Option<Vec<u8>>, StorageError> {
+        self.block_on(asynchronous::RawStorage::load_raw(self, key.as_ref()))
+    }
+
+    /// Delete raw bytes
+    fn delete_raw(&self, key: impl AsRef<str>) -> Result<(), StorageError> {
+        self.block_on(asynchronous::RawStorage::delete_raw(self, key.as_ref()))
+    }
+}
+
+impl AtomicStorage for RedisStorageModule {
+    /// Store an integer value
+    fn atomic_store(&self, key: impl AsRef<str>, value: i64) -> Result<StoreState, StorageError> {
+        self.block_on(asynchronous::AtomicStorage::atomic_store(
+            self,
+            key.as_ref(),
+            value,
+        ))
+    }
+
+    /// Load an integer value
+    fn atomic_load(&self, key: impl AsRef<str>) -> Result<Option<i64>, StorageError> {
+        self.block_on(asynchronous::AtomicStorage::atomic_load(self, key.as_ref()))
+    }
+
+    /// Delete an integer value
+    fn atomic_delete(&self, key: impl AsRef<str>) -> Result<(), StorageError> {
+        self.block_on(asynchronous::AtomicStorage::atomic_delete(
+            self,
+            key.as_ref(),
+        ))
+    }
+
+    /// Increment an integer value
+    fn atomic_increment(
+        &self,
+        key: impl AsRef<str>,
+        value: i64,
+    ) -> Result<Option<i64>, StorageError> {
+        self.block_on(asynchronous::AtomicStorage::atomic_increment(
+            self,
+            key.as_ref(),
+            value,
+        ))
+    }
+
+    /// Decrement an integer value
+    fn atomic_decrement(
+        &self,
+        key: impl AsRef<str>,
+        value: i64,
+    ) -> Result<Option<i64>, StorageError> {
+        self.block_on(asynchronous::AtomicStorage::atomic_decrement(
+            self,
+            key.as_ref(),
+            value,
+        ))
+    }
+
+    /// Increment an integer value, initializing it if missing
+    fn atomic_increment_or_init(
+        &self,
+        key: impl AsRef<str>,
+        delta: i64,
+        init: i64,
+    ) -> Result<i64, StorageError> {
+        self.block_on(asynchronous::AtomicStorage::atomic_increment_or_init(
+            self,
+            key.as_ref(),
+            delta,
+            init,
+        ))
+    }
+
+    /// Replace an integer value if it matches the expected one
+    fn atomic_compare_and_swap(
+        &self,
+        key: impl AsRef<str>,
+        expected: i64,
+        new: i64,
+    ) -> Result<bool, StorageError> {
+        self.block_on(asynchronous::AtomicStorage::atomic_compare_and_swap(
+            self,
+            key.as_ref(),
+            expected,
+            new,
+        ))
+    }
+}
+
+impl VersionedStorage for RedisStorageModule {
+    /// Load a versioned record
+    fn load_versioned(&self, key: impl AsRef<str>) -> Result<Option<(u64, String)>, StorageError> {
+        self.block_on(asynchronous::VersionedStorage::load_versioned(
+            self,
+            key.as_ref(),
+        ))
+    }
+
+    /// Replace a versioned record if its version matches
+    fn compare_and_set_versioned(
+        &self,
+        key: impl AsRef<str>,
+        expected_version: u64,
+        new_value: String,
+    ) -> Result<Option<u64>, StorageError> {
+        self.block_on(asynchronous::VersionedStorage::compare_and_set_versioned(
+            self,
+            key.as_ref(),
+            expected_version,
+            new_value,
+        ))
+    }
+}
+
+impl KeyExists for RedisStorageModule {
+    /// Check whether a key exists
+    fn exists(&self, key: impl AsRef<str>) -> Result<bool, StorageError> {
+        self.block_on(asynchronous::KeyExists::exists(self, key.as_ref()))
+    }
+
+    /// Check several keys with one pipelined EXISTS per key
+    fn exists_many(&self, keys: Vec<String>) -> Result<Vec<bool>, StorageError> {
+        self.block_on(asynchronous::KeyExists::exists_many(self, keys))
+    }
+}
+
+impl StringExpiry for RedisStorageModule {
+    /// Get the remaining time-to-live of a key
+    fn ttl(&self, key: impl AsRef<str>) -> Result<Option<u64>, StorageError> {
+        self.block_on(asynchronous::StringExpiry::ttl(self, key.as_ref()))
+    }
+
+    /// Set a new expiration on a key
+    fn expire(&self, key: impl AsRef<str>, expiry: u64) -> Result<bool, StorageError> {
+        self.block_on(asynchronous::StringExpiry::expire(
+            self,
+            key.as_ref(),
+            expiry,
+        ))
+    }
+
+    /// Extend the expiration of a key if the new one is later
+    fn expire_if_longer(&self, key: impl AsRef<str>, expiry: u64) -> Result<bool, StorageError> {
+        self.block_on(asynchronous::StringExpiry::expire_if_longer(
+            self,
+            key.as_ref(),
+            expiry,
+        ))
+    }
+
+    /// Remove the expiration from a key
+    fn persist(&self, key: impl AsRef<str>) -> Result<bool, StorageError> {
+        self.block_on(asynchronous::StringExpiry::persist(self, key.as_ref()))
+    }
+
+    /// Store a string expiring at a Unix timestamp
+    fn store_with_expiry_at(
+        &self,
+        key: impl AsRef<str>,
+        value: String,
+        at_unix_secs: u64,
+    ) -> Result<StoreState, StorageError> {
+        self.block_on(asynchronous::StringExpiry::store_with_expiry_at(
+            self,
+            key.as_ref(),
+            value,
+            at_unix_secs,
+        ))
+    }
+
+    /// Store a string with an optional expiry in milliseconds
+    fn store_with_expiry_ms(
+        &self,
+        key: impl AsRef<str>,
+        value: String,
+        expiry_ms: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.block_on(asynchronous::StringExpiry::store_with_expiry_ms(
+            self,
+            key.as_ref(),
+            value,
+            expiry_ms,
+        ))
+    }
+}
+
+impl ConditionalStringStorage for RedisStorageModule {
+    /// Store a string only if the key doesn't exist
+    fn store_if_absent(
+        &self,
+        key: impl AsRef<str>,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.block_on(asynchronous::ConditionalStringStorage::store_if_absent(
+            self,
+            key.as_ref(),
+            value,
+            expiry,
+        ))
+    }
+
+    /// Replace a string if it matches the expected value
+    fn compare_and_swap(
+        &self,
+        key: impl AsRef<str>,
+        expected: Option<String>,
+        new: String,
+    ) -> Result<StoreState, StorageError> {
+        self.block_on(asynchronous::ConditionalStringStorage::compare_and_swap(
+            self,
+            key.as_ref(),
+            expected,
+            new,
+        ))
+    }
+}
+
+impl FloatAtomicStorage for RedisStorageModule {
+    /// Store a floating-point value
+    fn atomic_store_f64(
+        &self,
+        key: impl AsRef<str>,
+        value: f64,
+    ) -> Result<StoreState, StorageError> {
+        self.block_on(asynchronous::FloatAtomicStorage::atomic_store_f64(
+            self,
+            key.as_ref(),
+            value,
+        ))
+    }
+
+    /// Load a floating-point value
+    fn atomic_load_f64(&self, key: impl AsRef<str>) -> Result<Option<f64>, StorageError> {
+        self.block_on(asynchronous::FloatAtomicStorage::atomic_load_f64(
+            self,
+            key.as_ref(),
+        ))
+    }
+
+    /// Increment a floating-point value
+    fn atomic_increment_f64(&self, key: impl AsRef<str>, value: f64) -> Result<f64, StorageError> {
+        self.block_on(asynchronous::FloatAtomicStorage::atomic_increment_f64(
+            self,
+            key.as_ref(),
+            value,
+        ))
+    }
+}
+
+impl BatchStringStorage for RedisStorageModule {
+    /// Store multiple strings in a single pipelined round-trip
+    fn store_many(
+        &self,
+        entries: Vec<(String, String)>,
+        expiry: Option<u64>,
+    ) -> Result<Vec<StoreState>, StorageError> {
+        self.block_on(asynchronous::BatchStringStorage::store_many(
+            self, entries, expiry,
+        ))
+    }
+
+    /// Load multiple strings with a single MGET
+    fn load_many(&self, keys: Vec<String>) -> Result<Vec<Option<String>>, StorageError> {
+        self.block_on(asynchronous::BatchStringStorage::load_many(self, keys))
+    }
+}
+
+impl KeyListing for RedisStorageModule {
+    /// List keys under a prefix using cursor-based SCAN
+    fn keys_with_prefix(&self, prefix: String) -> Result<Vec<String>, StorageError> {
+        self.block_on(asynchronous::KeyListing::keys_with_prefix(self, prefix))
+    }
+}
+
+impl HealthCheck for RedisStorageModule {
+    /// Check that the server responds to PING
+    fn ping(&self) -> Result<(), StorageError> {
+        self.block_on(asynchronous::HealthCheck::ping(self))
+    }
+}
+
+// Only available with the `redis-admin` feature, since clearing a shared
+// Redis database is destructive
+#[cfg(feature = "redis-admin")]
+impl crate::sync::AdminStorage for RedisStorageModule {
+    /// Remove every key in the current database with FLUSHDB
+    fn clear(&self) -> Result<(), StorageError> {
+        self.block_on(asynchronous::AdminStorage::clear(self))
+    }
+
+    /// Count the keys in the current database with DBSIZE
+    fn len(&self) -> Result<usize, StorageError> {
+        self.block_on(asynchronous::AdminStorage::len(self))
+    }
+}
+
+impl RateLimiter for RedisStorageModule {
+    /// Count a request against a fixed-window rate limit
+    fn check_rate(
+        &self,
+        key: impl AsRef<str>,
+        limit: i64,
+        window_secs: u64,
+    ) -> Result<bool, StorageError> {
+        self.block_on(asynchronous::RateLimiter::check_rate(
+            self,
+            key.as_ref(),
+            limit,
+            window_secs,
+        ))
+    }
+}
+
+impl StringAppend for RedisStorageModule {
+    /// Append to a string value
+    fn append(&self, key: impl AsRef<str>, value: String) -> Result<u64, StorageError> {
+        self.block_on(asynchronous::StringAppend::append(
+            self,
+            key.as_ref(),
+            value,
+        ))
+    }
+}
+
+impl StringRange for RedisStorageModule {
+    /// Read a byte range of a string value
+    fn get_range(
+        &self,
+        key: impl AsRef<str>,
+        start: usize,
+        end: usize,
+    ) -> Result<Option<String>, StorageError> {
+        self.block_on(asynchronous::StringRange::get_range(
+            self,
+            key.as_ref(),
+            start,
+            end,
+        ))
+    }
+}
+
+impl ListStorage for RedisStorageModule {
+    /// Append a value to a list
+    fn push_back(&self, key: impl AsRef<str>, value: String) -> Result<u64, StorageError> {
+        self.block_on(asynchronous::ListStorage::push_back(
+            self,
+            key.as_ref(),
+            value,
+        ))
+    }
+
+    /// Prepend a value to a list
+    fn push_front(&self, key: impl AsRef<str>, value: String) -> Result<u64, StorageError> {
+        self.block_on(asynchronous::ListStorage::push_front(
+            self,
+            key.as_ref(),
+            value,
+        ))
+    }
+
+    /// Remove the last value of a list
+    fn pop_back(&self, key: impl AsRef<str>) -> Result<Option<String>, StorageError> {
+        self.block_on(asynchronous::ListStorage::pop_back(self, key.as_ref()))
+    }
+
+    /// Remove the first value of a list
+    fn pop_front(&self, key: impl AsRef<str>) -> Result<Option<String>, StorageError> {
+        self.block_on(asynchronous::ListStorage::pop_front(self, key.as_ref()))
+    }
+
+    /// Get the length of a list
+    fn list_len(&self, key: impl AsRef<str>) -> Result<u64, StorageError> {
+        self.block_on(asynchronous::ListStorage::list_len(self, key.as_ref()))
+    }
+
+    /// Get a range of list values
+    fn list_range(
+        &self,
+        key: impl AsRef<str>,
+        start: i64,
+        stop: i64,
+    ) -> Result<Vec<String>, StorageError> {
+        self.block_on(asynchronous::ListStorage::list_range(
+            self,
+            key.as_ref(),
+            start,
+            stop,
+        ))
+    }
+}
+
+impl SetStorage for RedisStorageModule {
+    /// Add a member to a set
+    fn set_add(&self, key: impl AsRef<str>, member: String) -> Result<bool, StorageError> {
+        self.block_on(asynchronous::SetStorage::set_add(
+            self,
+            key.as_ref(),
+            member,
+        ))
+    }
+
+    /// Remove a member from a set
+    fn set_remove(&self, key: impl AsRef<str>, member: String) -> Result<bool, StorageError> {
+        self.block_on(asynchronous::SetStorage::set_remove(
+            self,
+            key.as_ref(),
+            member,
+        ))
+    }
+
+    /// Check set membership
+    fn set_contains(&self, key: impl AsRef<str>, member: String) -> Result<bool, StorageError> {
+        self.block_on(asynchronous::SetStorage::set_contains(
+            self,
+            key.as_ref(),
+            member,
+        ))
+    }
+
+    /// Get every member of a set
+    fn set_members(&self, key: impl AsRef<str>) -> Result<Vec<String>, StorageError> {
+        self.block_on(asynchronous::SetStorage::set_members(self, key.as_ref()))
+    }
+
+    /// Get the size of a set
+    fn set_len(&self, key: impl AsRef<str>) -> Result<u64, StorageError> {
+        self.block_on(asynchronous::SetStorage::set_len(self, key.as_ref()))
+    }
+}
+
+impl HashStorage for RedisStorageModule {
+    /// Set a field of a map
+    fn hash_set(
+        &self,
+        key: impl AsRef<str>,
+        field: String,
+        value: String,
+    ) -> Result<StoreState, StorageError> {
+        self.block_on(asynchronous::HashStorage::hash_set(
+            self,
+            key.as_ref(),
+            field,
+            value,
+        ))
+    }
+
+    /// Get a field of a map
+    fn hash_get(
+        &self,
+        key: impl AsRef<str>,
+        field: String,
+    ) -> Result<Option<String>, StorageError> {
+        self.block_on(asynchronous::HashStorage::hash_get(
+            self,
+            key.as_ref(),
+            field,
+        ))
+    }
+
+    /// Get every field of a map
+    fn hash_get_all(
+        &self,
+        key: impl AsRef<str>,
+    ) -> Result<std::collections::HashMap<String, String>, StorageError> {
+        self.block_on(asynchronous::HashStorage::hash_get_all(self, key.as_ref()))
+    }
+
+    /// Delete a field of a map
+    fn hash_delete(&self, key: impl AsRef<str>, field: String) -> Result<bool, StorageError> {
+        self.block_on(asynchronous::HashStorage::hash_delete(
+            self,
+            key.as_ref(),
+            field,
+        ))
+    }
+
+    /// Get the number of fields in a map
+    fn hash_len(&self, key: impl AsRef<str>) -> Result<u64, StorageError> {
+        self.block_on(asynchronous::HashStorage::hash_len(self, key.as_ref()))
+    }
+}
+
+impl BulkDelete for RedisStorageModule {
+    /// Delete every key under a prefix
+    fn delete_prefix(&self, prefix: String) -> Result<u64, StorageError> {
+        self.block_on(asynchronous::BulkDelete::delete_prefix(self, prefix))
+    }
+}
+
+impl KeyRename for RedisStorageModule {
+    /// Rename a key
+    fn rename(&self, from: impl AsRef<str>, to: impl AsRef<str>) -> Result<(), StorageError> {
+        self.block_on(asynchronous::KeyRename::rename(
+            self,
+            from.as_ref(),
+            to.as_ref(),
+        ))
+    }
+}
+
+impl BatchRawStorage for RedisStorageModule {
+    /// Load multiple binary values with a single MGET
+    fn load_raw_many(&self, keys: Vec<String>) -> Result<Vec<Option<Vec<u8>>>, StorageError> {
+        self.block_on(asynchronous::BatchRawStorage::load_raw_many(self, keys))
+    }
+}
+
+impl StringGetSet for RedisStorageModule {
+    /// Replace a string and return the previous one
+    fn get_set(&self, key: impl AsRef<str>, value: String) -> Result<Option<String>, StorageError> {
+        self.block_on(asynchronous::StringGetSet::get_set(
+            self,
+            key.as_ref(),
+            value,
+        ))
+    }
+}
+
+impl BoundedIncrement for RedisStorageModule {
+    /// Increment an integer and clamp it into a range
+    fn atomic_increment_bounded(
+        &self,
+        key: impl AsRef<str>,
+        delta: i64,
+        min: i64,
+        max: i64,
+    ) -> Result<i64, StorageError> {
+        self.block_on(asynchronous::BoundedIncrement::atomic_increment_bounded(
+            self,
+            key.as_ref(),
+            delta,
+            min,
+            max,
+        ))
+    }
+}
@@ -0,0 +1,284 @@
+//! Tests against a live Redis server.
+//!
+//! Set `TSOT_REDIS_URL` (e.g. `redis://127.0.0.1:6379/15`) to run them; without
+//! it every test returns early. Each test only touches keys under its own
+//! `tsot-test:` prefix (`lock:tsot-test:` for locks), but use a scratch
+//! database all the same.
+
+use std::time::Duration;
+
+use crate::asynchronous::{
+    AtomicStorage, FloatAtomicStorage, HashStorage, KeyExists, ListStorage, StringExpiry,
+    StringStorage, StringStorageWithExpiry,
+};
+use crate::errors::StorageError;
+use crate::types::StoreState;
+
+use super::{PipelineResult, RedisStorageConfig, RedisStorageModule};
+
+/// Reads the config of the server named by `TSOT_REDIS_URL`, or returns `None` if it is unset.
+fn config() -> Option<RedisStorageConfig> {
+    let url = std::env::var("TSOT_REDIS_URL").ok()?;
+    Some(RedisStorageConfig::from_url(&url).expect("invalid TSOT_REDIS_URL"))
+}
+
+/// Connects to the server named by `TSOT_REDIS_URL`, or returns `None` if it is unset.
+async fn connect() -> Option<RedisStorageModule> {
+    Some(connect_with(config()?).await)
+}
+
+/// Connects with the given config.
+async fn connect_with(config: RedisStorageConfig) -> RedisStorageModule {
+    RedisStorageModule::new(config)
+        .await
+        .expect("failed to connect to Redis")
+}
+
+#[tokio::test]
+async fn pipeline_applies_every_queued_set() {
+    let Some(storage) = connect().await else {
+        return;
+    };
+    let keys: Vec<String> = (0..50).map(|i| format!("tsot-test:pipeline:{i}")).collect();
+    for key in &keys {
+        storage.delete_string(key).await.unwrap();
+    }
+
+    let pipeline = keys.iter().fold(storage.pipeline(), |pipeline, key| {
+        pipeline.set(key.clone(), format!("value of {key}"), Some(60))
+    });
+    assert_eq!(pipeline.len(), keys.len());
+
+    let results = pipeline.execute().await.unwrap();
+    assert_eq!(results.len(), keys.len());
+    assert!(results
+        .iter()
+        .all(|result| matches!(result, PipelineResult::Set(StoreState::New))));
+    for key in &keys {
+        assert_eq!(
+            storage.load_string(key).await.unwrap(),
+            Some(format!("value of {key}"))
+        );
+        storage.delete_string(key).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn atomic_store_keeps_the_ttl_of_an_existing_key() {
+    let Some(storage) = connect().await else {
+        return;
+    };
+    let key = "tsot-test:atomic-ttl";
+    storage.atomic_delete(key).await.unwrap();
+
+    assert!(matches!(
+        storage.atomic_store(key, 1).await.unwrap(),
+        StoreState::New
+    ));
+    assert!(storage.expire(key, 60).await.unwrap());
+    assert!(matches!(
+        storage.atomic_store(key, 2).await.unwrap(),
+        StoreState::Updated
+    ));
+
+    let ttl = storage.ttl(key).await.unwrap();
+    assert!(matches!(ttl, Some(1..=60)), "TTL was {ttl:?}");
+    assert_eq!(storage.atomic_load(key).await.unwrap(), Some(2));
+    storage.atomic_delete(key).await.unwrap();
+}
+
+#[tokio::test]
+async fn float_increments_accumulate_within_tolerance() {
+    let Some(storage) = connect().await else {
+        return;
+    };
+    let key = "tsot-test:float-sum";
+    storage.delete_string(key).await.unwrap();
+
+    let mut total = 0.0;
+    for _ in 0..10 {
+        total = storage.atomic_increment_f64(key, 0.1).await.unwrap();
+    }
+
+    assert!((total - 1.0).abs() < 1e-9, "sum was {total}");
+    assert_eq!(storage.atomic_load_f64(key).await.unwrap(), Some(total));
+    storage.delete_string(key).await.unwrap();
+}
+
+#[cfg(feature = "imc")]
+#[tokio::test]
+async fn migrate_keeps_permanent_keys_permanent() {
+    use crate::asynchronous::{RawStorage, RawStorageWithExpiry};
+    use crate::storage::imc::{IMCConfig, IMCModule};
+
+    let Some(mut config) = config() else {
+        return;
+    };
+    config.default_expiry = Some(60);
+    let storage = connect_with(config).await;
+    let (permanent, expiring) = ("tsot-test:migrate:permanent", "tsot-test:migrate:expiring");
+
+    let src = IMCModule::new(IMCConfig::default());
+    src.store_raw(permanent, vec![1]).await.unwrap();
+    src.store_raw_with_expiry(expiring, vec![2], Some(30))
+        .await
+        .unwrap();
+
+    let copied = crate::migrate::migrate(&src, &storage, Some("tsot-test:migrate:".to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(copied, 2);
+    assert_eq!(storage.load_raw(permanent).await.unwrap(), Some(vec![1]));
+    // The destination's default expiry is not applied to a key without one
+    assert_eq!(storage.ttl(permanent).await.unwrap(), None);
+    let ttl = storage.ttl(expiring).await.unwrap();
+    assert!(matches!(ttl, Some(1..=30)), "TTL was {ttl:?}");
+    storage.delete_raw(permanent).await.unwrap();
+    storage.delete_raw(expiring).await.unwrap();
+}
+
+#[tokio::test]
+async fn zero_expiry_stores_an_already_expired_value() {
+    let Some(storage) = connect().await else {
+        return;
+    };
+    let key = "tsot-test:zero-expiry";
+
+    storage.store_string(key, "live".to_string()).await.unwrap();
+    storage
+        .store_with_expiry(key, "expired".to_string(), Some(0))
+        .await
+        .unwrap();
+    assert_eq!(storage.load_string(key).await.unwrap(), None);
+
+    storage.store_string(key, "live".to_string()).await.unwrap();
+    storage
+        .store_with_expiry_ms(key, "expired".to_string(), Some(0))
+        .await
+        .unwrap();
+    assert_eq!(storage.load_string(key).await.unwrap(), None);
+
+    storage
+        .store_with_expiry_at(key, "expired".to_string(), 0)
+        .await
+        .unwrap();
+    assert_eq!(storage.load_string(key).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn ttl_is_none_for_missing_and_permanent_keys() {
+    let Some(storage) = connect().await else {
+        return;
+    };
+    let key = "tsot-test:ttl";
+    storage.delete_string(key).await.unwrap();
+
+    assert_eq!(storage.ttl(key).await.unwrap(), None);
+    storage.store_string(key, "abc".to_string()).await.unwrap();
+    assert_eq!(storage.ttl(key).await.unwrap(), None);
+    storage
+        .store_with_expiry(key, "abc".to_string(), Some(60))
+        .await
+        .unwrap();
+    let ttl = storage.ttl(key).await.unwrap();
+    assert!(matches!(ttl, Some(1..=60)), "TTL was {ttl:?}");
+    storage.delete_string(key).await.unwrap();
+}
+
+/// Runs one call sequence against a backend and records every result.
+#[cfg(feature = "imc")]
+async fn agreement_log<S>(storage: &S) -> Vec<String>
+where
+    S: StringStorage + AtomicStorage + ListStorage + HashStorage + KeyExists + Sync,
+{
+    let (string, counter) = ("tsot-test:agree:string", "tsot-test:agree:counter");
+    let (list, hash) = ("tsot-test:agree:list", "tsot-test:agree:hash");
+    vec![
+        format!("{:?}", storage.atomic_increment(counter, 2).await),
+        format!("{:?}", storage.atomic_increment(counter, -5).await),
+        format!("{:?}", storage.atomic_load(counter).await),
+        format!("{:?}", storage.exists(counter).await),
+        format!("{:?}", storage.load_string(string).await),
+        format!(
+            "{:?}",
+            storage.store_string(string, "abc".to_string()).await
+        ),
+        format!("{:?}", storage.load_string(string).await),
+        format!("{:?}", storage.exists(string).await),
+        format!("{:?}", storage.delete_string(string).await),
+        format!("{:?}", storage.exists(string).await),
+        format!("{:?}", storage.push_back(list, "a".to_string()).await),
+        format!("{:?}", storage.exists(list).await),
+        format!(
+            "{:?}",
+            storage
+                .hash_set(hash, "field".to_string(), "a".to_string())
+                .await
+        ),
+        format!("{:?}", storage.exists(hash).await),
+    ]
+}
+
+#[cfg(feature = "imc")]
+#[tokio::test]
+async fn backends_agree_on_a_call_sequence() {
+    use crate::storage::imc::{IMCConfig, IMCModule};
+
+    let Some(storage) = connect().await else {
+        return;
+    };
+    let keys = ["string", "counter", "list", "hash"].map(|name| format!("tsot-test:agree:{name}"));
+    for key in &keys {
+        storage.delete_string(key).await.unwrap();
+    }
+
+    let expected = agreement_log(&IMCModule::new(IMCConfig::default())).await;
+    assert_eq!(agreement_log(&storage).await, expected);
+    for key in &keys {
+        storage.delete_string(key).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn lock_is_exclusive_until_released() {
+    let Some(storage) = connect().await else {
+        return;
+    };
+    let lock = storage
+        .lock()
+        .with_wait(Duration::ZERO, Duration::from_millis(10));
+    let name = "tsot-test:lock".to_string();
+
+    let guard = lock.try_acquire(name.clone(), 60_000).await.unwrap();
+    let guard = guard.expect("a free lock was not acquired");
+    assert!(lock
+        .try_acquire(name.clone(), 60_000)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(lock.acquire(name.clone(), 60_000).await.unwrap().is_none());
+
+    assert!(guard.release().await.unwrap());
+    let guard = lock.acquire(name, 60_000).await.unwrap();
+    let guard = guard.expect("a released lock was not acquired");
+    assert!(guard.release().await.unwrap());
+}
+
+#[tokio::test]
+async fn lock_rejects_ttls_redis_cannot_take() {
+    let Some(storage) = connect().await else {
+        return;
+    };
+    let lock = storage.lock();
+
+    for ttl_ms in [0, u64::MAX] {
+        let result = lock
+            .try_acquire("tsot-test:lock-ttl".to_string(), ttl_ms)
+            .await;
+        assert!(
+            matches!(result, Err(StorageError::InvalidArgument(_))),
+            "TTL {ttl_ms} was accepted"
+        );
+    }
+}
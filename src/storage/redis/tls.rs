@@ -0,0 +1,62 @@
+use fred::{
+    error::{RedisError, RedisErrorKind},
+    types::{TlsConfig, TlsConnector},
+};
+use std::path::PathBuf;
+
+/// TLS settings for Redis connections
+#[derive(Clone, Debug, Default)]
+pub struct RedisTlsConfig {
+    /// PEM file with the CA certificate(s) used to verify the server
+    ///
+    /// When set, only these certificates are trusted, which allows connecting
+    /// to servers with self-signed certificates. Otherwise the platform's
+    /// native root certificates are used.
+    pub ca_cert_path: Option<PathBuf>,
+}
+
+impl RedisTlsConfig {
+    /// Build the `fred` TLS configuration
+    ///
+    /// Fails with a TLS error carrying the underlying message if the CA
+    /// certificate cannot be read or parsed
+    pub(super) fn tls_config(&self) -> Result<TlsConfig, RedisError> {
+        let connector = match &self.ca_cert_path {
+            Some(path) => {
+                let pem = std::fs::read(path).map_err(|e| {
+                    tls_error(format!(
+                        "failed to read CA certificate {}: {e}",
+                        path.display()
+                    ))
+                })?;
+
+                let mut roots = rustls::RootCertStore::empty();
+                for cert in rustls_pemfile::certs(&mut pem.as_slice()) {
+                    let cert = cert.map_err(|e| {
+                        tls_error(format!(
+                            "failed to parse CA certificate {}: {e}",
+                            path.display()
+                        ))
+                    })?;
+                    roots.add(cert).map_err(|e| {
+                        tls_error(format!("invalid CA certificate {}: {e}", path.display()))
+                    })?;
+                }
+
+                TlsConnector::from(
+                    rustls::ClientConfig::builder()
+                        .with_root_certificates(roots)
+                        .with_no_client_auth(),
+                )
+            }
+            None => TlsConnector::default_rustls()?,
+        };
+
+        Ok(TlsConfig::from(connector))
+    }
+}
+
+/// Create a TLS error with the given message
+fn tls_error(message: String) -> RedisError {
+    RedisError::new(RedisErrorKind::Tls, message)
+}
@@ -0,0 +1,183 @@
+//! Atomic execution of queued commands with `MULTI`/`EXEC`.
+//!
+//! Operations are queued with the same builder methods as a
+//! [`Pipeline`](super::Pipeline) and sent as one `fred` transaction, so no
+//! other client's command runs between them. Redis does not roll back a
+//! transaction: if one command fails at execution time, e.g. `INCR` on a
+//! non-integer, the others are still applied. In cluster mode every key of a
+//! transaction must hash to the same slot.
+//!
+//! [`RedisStorageModule::watch_transaction`] adds optimistic concurrency on
+//! top: it `WATCH`es keys, hands their current values to a closure, and only
+//! commits the closure's writes if none of the keys changed in the meantime.
+//!
+//! `IMCModule` has no equivalent. Its stores are sharded maps locked per
+//! entry, so it cannot apply several keys as one atomic step, and emulating
+//! that would only give the illusion of isolation.
+
+use fred::{
+    clients::RedisClient,
+    interfaces::{ClientLike, KeysInterface},
+    types::{ClusterHash, CustomCommand, RedisKey, RedisValue},
+};
+
+use crate::errors::StorageError;
+
+use super::{
+    pipeline::{collect_results, queue_ops, PipelineOp},
+    PipelineResult, RedisStorageModule,
+};
+
+/// Builder queuing operations to run in a single `MULTI`/`EXEC` transaction
+pub struct Transaction {
+    /// Operations to run, in order
+    ops: Vec<PipelineOp>,
+}
+
+impl RedisStorageModule {
+    /// Run operations atomically in a `MULTI`/`EXEC` transaction
+    ///
+    /// # Arguments
+    /// * `f` - Queues the operations on the given empty `Transaction`
+    ///
+    /// # Returns
+    /// One result per queued operation, in queue order. Fails if any command
+    /// fails, although Redis still applies the other commands. A key rejected
+    /// by the configured validator fails the transaction before it is sent
+    pub async fn transaction<F>(&self, f: F) -> Result<Vec<PipelineResult>, StorageError>
+    where
+        F: FnOnce(Transaction) -> Transaction,
+    {
+        let transaction = f(Transaction { ops: Vec::new() });
+        if transaction.ops.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let multi = self.client.multi();
+        queue_ops(&multi, self, &transaction.ops).await?;
+
+        let replies: Vec<RedisValue> = multi.exec(true).await?;
+
+        collect_results(&transaction.ops, replies)
+    }
+
+    /// Run a check-then-act transaction guarded by `WATCH`
+    ///
+    /// Watches `keys`, reads their string values and passes them to `f`,
+    /// which queues the writes to apply. The writes are committed with
+    /// `MULTI`/`EXEC` only if no watched key was modified after it was read;
+    /// otherwise `f` is called again with fresh values. `WATCH` state belongs
+    /// to a connection, so each call opens a dedicated connection rather than
+    /// sharing this module's. In cluster mode every key must hash to the
+    /// same slot.
+    ///
+    /// # Arguments
+    /// * `keys` - The keys to watch and read
+    /// * `max_retries` - How often to retry after a conflicting write
+    /// * `f` - Receives the current values, in the order of `keys`, and queues
+    ///   writes on the given empty `Transaction`; queuing none commits nothing
+    ///
+    /// # Returns
+    /// One result per write queued by the committed attempt, in queue order.
+    /// Fails with `StorageError::RetriesExhausted` if every attempt conflicted,
+    /// and with the command's error, e.g. `StorageError::ConnectionError`, if
+    /// a command fails
+    pub async fn watch_transaction<F>(
+        &self,
+        keys: Vec<String>,
+        max_retries: u32,
+        mut f: F,
+    ) -> Result<Vec<PipelineResult>, StorageError>
+    where
+        F: FnMut(Vec<Option<String>>, Transaction) -> Transaction,
+    {
+        let keys: Vec<RedisKey> = keys
+            .into_iter()
+            .map(|key| self.key(key).map(RedisKey::from))
+            .collect::<Result<_, _>>()?;
+
+        let client = self.client.clone_new();
+        client.connect();
+        client.wait_for_connect().await?;
+
+        let result = self.run_watched(&client, &keys, max_retries, &mut f).await;
+        let _ = client.quit().await;
+        result
+    }
+
+    /// Attempt a watched transaction on a dedicated connection until it commits
+    async fn run_watched<F>(
+        &self,
+        client: &RedisClient,
+        keys: &[RedisKey],
+        max_retries: u32,
+        f: &mut F,
+    ) -> Result<Vec<PipelineResult>, StorageError>
+    where
+        F: FnMut(Vec<Option<String>>, Transaction) -> Transaction,
+    {
+        let attempts = max_retries.saturating_add(1);
+        for _ in 0..attempts {
+            client
+                .custom::<(), _>(
+                    CustomCommand::new("WATCH", ClusterHash::FirstKey, false),
+                    keys.to_vec(),
+                )
+                .await?;
+
+            let mut values = Vec::with_capacity(keys.len());
+            for key in keys {
+                values.push(client.get::<Option<String>, _>(key).await?);
+            }
+
+            let transaction = f(values, Transaction { ops: Vec::new() });
+            // The watch is dropped along with the dedicated connection
+            if transaction.ops.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let multi = client.multi();
+            queue_ops(&multi, self, &transaction.ops).await?;
+
+            // EXEC replies with nil when a watched key was modified
+            let reply: RedisValue = multi.exec(true).await?;
+            if !reply.is_null() {
+                return collect_results(&transaction.ops, reply.into_array());
+            }
+        }
+
+        Err(StorageError::RetriesExhausted(attempts))
+    }
+}
+
+impl Transaction {
+    /// Queue storing a string with optional expiry, like `store_with_expiry`
+    ///
+    /// Without an expiry, the configured `default_expiry` is applied
+    pub fn set(mut self, key: String, value: String, expiry: Option<u64>) -> Self {
+        self.ops.push(PipelineOp::Set { key, value, expiry });
+        self
+    }
+
+    /// Queue deleting a key
+    pub fn del(mut self, key: String) -> Self {
+        self.ops.push(PipelineOp::Del { key });
+        self
+    }
+
+    /// Queue incrementing an integer, like `atomic_increment`
+    pub fn incr(mut self, key: String, delta: i64) -> Self {
+        self.ops.push(PipelineOp::Incr { key, delta });
+        self
+    }
+
+    /// Get the number of queued operations
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Check whether no operations are queued
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+}
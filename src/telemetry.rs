@@ -0,0 +1,52 @@
+//! Operation metrics reported through the `metrics` crate.
+//!
+//! With the `metrics` feature enabled, every storage trait method of both
+//! backends counts each call in [`OPERATIONS_TOTAL`] and records its
+//! duration in [`OPERATION_DURATION_SECONDS`]. Both carry a `backend` label
+//! (`imc` or `redis`) and an `op` label with the method name. Install any
+//! `metrics` recorder, e.g. a Prometheus exporter, to collect them.
+
+use std::time::Instant;
+
+/// Counter incremented once per storage operation.
+pub const OPERATIONS_TOTAL: &str = "tsot_store_total";
+
+/// Histogram of storage operation durations, in seconds.
+pub const OPERATION_DURATION_SECONDS: &str = "tsot_op_duration_seconds";
+
+/// Counts an operation when started and records its duration when dropped.
+///
+/// The duration is recorded whether the operation succeeded or failed.
+pub(crate) struct OpTimer {
+    backend: &'static str,
+    op: &'static str,
+    started: Instant,
+}
+
+impl OpTimer {
+    /// Counts an operation and starts timing it.
+    ///
+    /// # Arguments
+    /// * `backend` - The backend label, `imc` or `redis`
+    /// * `op` - The operation label, usually the trait method name
+    pub(crate) fn start(backend: &'static str, op: &'static str) -> Self {
+        metrics::counter!(OPERATIONS_TOTAL, "backend" => backend, "op" => op).increment(1);
+
+        Self {
+            backend,
+            op,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Drop for OpTimer {
+    fn drop(&mut self) {
+        metrics::histogram!(
+            OPERATION_DURATION_SECONDS,
+            "backend" => self.backend,
+            "op" => self.op
+        )
+        .record(self.started.elapsed().as_secs_f64());
+    }
+}
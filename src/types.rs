@@ -1,4 +1,30 @@
+/// Outcome of a write operation.
+///
+/// New variants may be added in future releases, so matches outside this
+/// crate need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum StoreState {
+    /// The key did not exist and was created
     New,
+    /// The key existed and its value was replaced
     Updated,
+    /// Nothing was written, e.g. because a conditional write's precondition
+    /// did not hold
+    Unchanged,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::StoreState;
+
+    #[test]
+    fn store_state_round_trips_through_serde() {
+        for state in [StoreState::New, StoreState::Updated, StoreState::Unchanged] {
+            let json = serde_json::to_string(&state).unwrap();
+            assert_eq!(serde_json::from_str::<StoreState>(&json).unwrap(), state);
+        }
+        assert_eq!(serde_json::to_string(&StoreState::New).unwrap(), "\"New\"");
+    }
 }
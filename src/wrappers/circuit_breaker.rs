@@ -0,0 +1,310 @@
+//! Failing fast while the backend is unavailable.
+//!
+//! [`CircuitBreaker`] counts consecutive transient failures of the underlying
+//! backend. Once they reach a threshold the circuit opens and calls fail
+//! immediately, without reaching the backend, until a cooldown has passed.
+//! The next call is then let through as a probe: if it succeeds the circuit
+//! closes again, otherwise it stays open for another cooldown.
+
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    asynchronous::{
+        AtomicStorage, BulkDelete, KeyListing, KeyRename, RawStorage, RawStorageWithExpiry,
+        StringStorage, StringStorageWithExpiry,
+    },
+    errors::StorageError,
+    types::StoreState,
+};
+
+/// Configuration for [`CircuitBreaker`].
+#[derive(Clone)]
+pub struct CircuitBreakerConfig {
+    /// Number of consecutive transient failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a probe is let through
+    pub cooldown: Duration,
+}
+
+/// Storage wrapper that stops calling a failing backend for a while.
+///
+/// Only transient errors (see [`StorageError::is_transient`]) count as
+/// failures; any other result shows the backend is reachable and resets the
+/// count. While the circuit is open, calls fail with
+/// `StorageError::ConnectionError("circuit open")`. The state is shared by
+/// every clone of the wrapper.
+#[derive(Clone)]
+pub struct CircuitBreaker<S> {
+    /// The wrapped storage backend
+    inner: S,
+    /// Failure threshold and cooldown
+    config: CircuitBreakerConfig,
+    /// Current state of the circuit
+    state: Arc<Mutex<CircuitState>>,
+}
+
+/// State of a [`CircuitBreaker`].
+enum CircuitState {
+    /// Calls reach the backend; holds the number of consecutive failures
+    Closed(u32),
+    /// Calls fail fast until the given time, when one probe is let through
+    Open(Instant),
+}
+
+impl<S> CircuitBreaker<S> {
+    /// Wraps a storage backend.
+    ///
+    /// # Arguments
+    /// * `inner` - The storage backend to wrap
+    /// * `config` - Failure threshold and cooldown
+    ///
+    /// # Returns
+    /// * `Self` - A new wrapper with a closed circuit
+    pub fn new(inner: S, config: CircuitBreakerConfig) -> Self {
+        Self {
+            inner,
+            config,
+            state: Arc::new(Mutex::new(CircuitState::Closed(0))),
+        }
+    }
+
+    /// Returns a reference to the wrapped storage backend.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Checks whether calls are currently failing fast.
+    pub fn is_open(&self) -> bool {
+        matches!(*self.lock(), CircuitState::Open(until) if Instant::now() < until)
+    }
+
+    /// Runs an operation if the circuit allows it and records its outcome.
+    async fn call<T>(
+        &self,
+        operation: impl Future<Output = Result<T, StorageError>>,
+    ) -> Result<T, StorageError> {
+        self.admit()?;
+        let result = operation.await;
+        self.record(result.as_ref().err());
+        result
+    }
+
+    /// Decides whether a call may reach the backend.
+    ///
+    /// Once the cooldown has passed, the first caller becomes the probe and
+    /// the cooldown restarts, so further callers keep failing fast until the
+    /// probe's outcome is recorded. A probe that is dropped before finishing
+    /// therefore only delays the next one by a cooldown.
+    fn admit(&self) -> Result<(), StorageError> {
+        let mut state = self.lock();
+        if let CircuitState::Open(until) = *state {
+            let now = Instant::now();
+            if now < until {
+                return Err(StorageError::ConnectionError("circuit open".to_string()));
+            }
+            *state = CircuitState::Open(now + self.config.cooldown);
+        }
+        Ok(())
+    }
+
+    /// Updates the circuit with the outcome of a call.
+    fn record(&self, error: Option<&StorageError>) {
+        let mut state = self.lock();
+        match error {
+            Some(error) if error.is_transient() => {
+                let failures = match *state {
+                    CircuitState::Closed(failures) => failures.saturating_add(1),
+                    // A failed probe reopens the circuit straight away
+                    CircuitState::Open(_) => self.config.failure_threshold,
+                };
+                *state = if failures >= self.config.failure_threshold {
+                    CircuitState::Open(Instant::now() + self.config.cooldown)
+                } else {
+                    CircuitState::Closed(failures)
+                };
+            }
+            _ => *state = CircuitState::Closed(0),
+        }
+    }
+
+    /// Locks the state, recovering it if a holder panicked.
+    fn lock(&self) -> std::sync::MutexGuard<'_, CircuitState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl<S> StringStorageWithExpiry for CircuitBreaker<S>
+where
+    S: StringStorageWithExpiry + Send + Sync,
+{
+    async fn store_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.call(self.inner.store_with_expiry(key, value, expiry))
+            .await
+    }
+
+    async fn store_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<StoreState, StorageError> {
+        self.call(self.inner.store_keep_ttl(key, value)).await
+    }
+}
+
+#[async_trait]
+impl<S> StringStorage for CircuitBreaker<S>
+where
+    S: StringStorage + Send + Sync,
+{
+    async fn load_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        self.call(self.inner.load_string(key)).await
+    }
+
+    async fn delete_string(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.call(self.inner.delete_string(key)).await
+    }
+
+    async fn take_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        self.call(self.inner.take_string(key)).await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorageWithExpiry for CircuitBreaker<S>
+where
+    S: RawStorageWithExpiry + Send + Sync,
+{
+    async fn store_raw_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.call(self.inner.store_raw_with_expiry(key, value, expiry))
+            .await
+    }
+
+    async fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+    ) -> Result<StoreState, StorageError> {
+        self.call(self.inner.store_raw_keep_ttl(key, value)).await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorage for CircuitBreaker<S>
+where
+    S: RawStorage + Send + Sync,
+{
+    async fn load_raw(&self, key: impl AsRef<str> + Send) -> Result<Option<Vec<u8>>, StorageError> {
+        self.call(self.inner.load_raw(key)).await
+    }
+
+    async fn delete_raw(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.call(self.inner.delete_raw(key)).await
+    }
+}
+
+#[async_trait]
+impl<S> AtomicStorage for CircuitBreaker<S>
+where
+    S: AtomicStorage + Send + Sync,
+{
+    async fn atomic_store(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<StoreState, StorageError> {
+        self.call(self.inner.atomic_store(key, value)).await
+    }
+
+    async fn atomic_load(&self, key: impl AsRef<str> + Send) -> Result<Option<i64>, StorageError> {
+        self.call(self.inner.atomic_load(key)).await
+    }
+
+    async fn atomic_delete(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.call(self.inner.atomic_delete(key)).await
+    }
+
+    async fn atomic_increment(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<Option<i64>, StorageError> {
+        self.call(self.inner.atomic_increment(key, value)).await
+    }
+
+    async fn atomic_increment_or_init(
+        &self,
+        key: impl AsRef<str> + Send,
+        delta: i64,
+        init: i64,
+    ) -> Result<i64, StorageError> {
+        self.call(self.inner.atomic_increment_or_init(key, delta, init))
+            .await
+    }
+
+    async fn atomic_compare_and_swap(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected: i64,
+        new: i64,
+    ) -> Result<bool, StorageError> {
+        self.call(self.inner.atomic_compare_and_swap(key, expected, new))
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> KeyListing for CircuitBreaker<S>
+where
+    S: KeyListing + Send + Sync,
+{
+    async fn keys_with_prefix(&self, prefix: String) -> Result<Vec<String>, StorageError> {
+        self.call(self.inner.keys_with_prefix(prefix)).await
+    }
+}
+
+#[async_trait]
+impl<S> BulkDelete for CircuitBreaker<S>
+where
+    S: BulkDelete + Send + Sync,
+{
+    async fn delete_prefix(&self, prefix: String) -> Result<u64, StorageError> {
+        self.call(self.inner.delete_prefix(prefix)).await
+    }
+}
+
+#[async_trait]
+impl<S> KeyRename for CircuitBreaker<S>
+where
+    S: KeyRename + Send + Sync,
+{
+    async fn rename(
+        &self,
+        from: impl AsRef<str> + Send,
+        to: impl AsRef<str> + Send,
+    ) -> Result<(), StorageError> {
+        self.call(self.inner.rename(from, to)).await
+    }
+}
@@ -0,0 +1,131 @@
+//! Transparent gzip compression of raw values.
+//!
+//! [`Compressed`] compresses values before storing them through the wrapped
+//! [`RawStorageWithExpiry`] and decompresses them again on load. Compressed
+//! values start with [`HEADER`], a magic marker followed by a format version;
+//! values without it are returned unchanged, so data written before the
+//! wrapper was introduced stays readable.
+
+use std::io::{Read, Write};
+
+use async_trait::async_trait;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use crate::{
+    asynchronous::{RawStorage, RawStorageWithExpiry},
+    errors::StorageError,
+    types::StoreState,
+};
+
+/// Marker prepended to every compressed value: `TSZ` followed by the format
+/// version.
+const HEADER: &[u8] = b"TSZ\x01";
+
+/// Default gzip compression level, balancing speed and size.
+pub const DEFAULT_COMPRESSION_LEVEL: u32 = 6;
+
+/// Storage wrapper that gzip-compresses raw values at rest.
+#[derive(Clone)]
+pub struct Compressed<S> {
+    /// The wrapped storage backend
+    inner: S,
+    /// The gzip compression level
+    level: Compression,
+}
+
+impl<S> Compressed<S> {
+    /// Wraps a storage backend using [`DEFAULT_COMPRESSION_LEVEL`].
+    ///
+    /// # Arguments
+    /// * `inner` - The storage backend to wrap
+    ///
+    /// # Returns
+    /// * `Self` - A new compressing wrapper
+    pub fn new(inner: S) -> Self {
+        Self::with_level(inner, DEFAULT_COMPRESSION_LEVEL)
+    }
+
+    /// Wraps a storage backend using the given compression level.
+    ///
+    /// # Arguments
+    /// * `inner` - The storage backend to wrap
+    /// * `level` - The gzip level, from 0 (no compression) to 9 (best
+    ///   compression); higher values are clamped to 9
+    ///
+    /// # Returns
+    /// * `Self` - A new compressing wrapper
+    pub fn with_level(inner: S, level: u32) -> Self {
+        Self {
+            inner,
+            level: Compression::new(level.min(9)),
+        }
+    }
+
+    /// Returns a reference to the wrapped storage backend.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Compresses a value and prepends the header.
+    fn compress(&self, value: &[u8]) -> Result<Vec<u8>, StorageError> {
+        let mut encoder = GzEncoder::new(HEADER.to_vec(), self.level);
+        encoder
+            .write_all(value)
+            .and_then(|_| encoder.finish())
+            .map_err(|e| StorageError::DeserializationError(format!("failed to compress: {e}")))
+    }
+}
+
+/// Decompresses a value written by [`Compressed`], passing values without the
+/// header through unchanged.
+fn decompress(value: Vec<u8>) -> Result<Vec<u8>, StorageError> {
+    let Some(compressed) = value.strip_prefix(HEADER) else {
+        return Ok(value);
+    };
+
+    let mut decompressed = Vec::new();
+    GzDecoder::new(compressed)
+        .read_to_end(&mut decompressed)
+        .map_err(|e| StorageError::DeserializationError(format!("failed to decompress: {e}")))?;
+
+    Ok(decompressed)
+}
+
+#[async_trait]
+impl<S> RawStorageWithExpiry for Compressed<S>
+where
+    S: RawStorageWithExpiry + Send + Sync,
+{
+    async fn store_raw_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        let value = self.compress(&value)?;
+        self.inner.store_raw_with_expiry(key, value, expiry).await
+    }
+
+    async fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+    ) -> Result<StoreState, StorageError> {
+        let value = self.compress(&value)?;
+        self.inner.store_raw_keep_ttl(key, value).await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorage for Compressed<S>
+where
+    S: RawStorage + Send + Sync,
+{
+    async fn load_raw(&self, key: impl AsRef<str> + Send) -> Result<Option<Vec<u8>>, StorageError> {
+        self.inner.load_raw(key).await?.map(decompress).transpose()
+    }
+
+    async fn delete_raw(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.inner.delete_raw(key).await
+    }
+}
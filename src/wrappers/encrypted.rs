@@ -0,0 +1,129 @@
+//! Encryption at rest for raw values.
+//!
+//! [`Encrypted`] encrypts values with AES-256-GCM before storing them through
+//! the wrapped [`RawStorageWithExpiry`] and decrypts them again on load. Each
+//! value is stored as a freshly generated random nonce followed by the
+//! ciphertext, so storing the same value twice never yields the same bytes.
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use async_trait::async_trait;
+
+use crate::{
+    asynchronous::{RawStorage, RawStorageWithExpiry},
+    errors::StorageError,
+    types::StoreState,
+};
+
+/// Length in bytes of the key accepted by [`Encrypted::new`].
+pub const ENCRYPTION_KEY_LEN: usize = 32;
+
+/// Length in bytes of the nonce prepended to every stored value.
+const NONCE_LEN: usize = 12;
+
+/// Storage wrapper that encrypts raw values at rest with AES-256-GCM.
+///
+/// Reads of values that were not written with the same key, or that were
+/// modified in storage, fail with `StorageError::Decryption`.
+#[derive(Clone)]
+pub struct Encrypted<S> {
+    /// The wrapped storage backend
+    inner: S,
+    /// The cipher initialized with the encryption key
+    cipher: Aes256Gcm,
+}
+
+impl<S> Encrypted<S> {
+    /// Wraps a storage backend.
+    ///
+    /// # Arguments
+    /// * `inner` - The storage backend to wrap
+    /// * `key` - The 256-bit encryption key
+    ///
+    /// # Returns
+    /// * `Self` - A new encrypting wrapper
+    pub fn new(inner: S, key: &[u8; ENCRYPTION_KEY_LEN]) -> Self {
+        Self {
+            inner,
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        }
+    }
+
+    /// Returns a reference to the wrapped storage backend.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Encrypts a value under a random nonce and prepends the nonce.
+    fn encrypt(&self, value: &[u8]) -> Result<Vec<u8>, StorageError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, value)
+            .map_err(|_| StorageError::Decryption("failed to encrypt value".to_string()))?;
+
+        let mut stored = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        stored.extend_from_slice(&nonce);
+        stored.extend_from_slice(&ciphertext);
+        Ok(stored)
+    }
+
+    /// Splits off the nonce and decrypts the remaining ciphertext.
+    fn decrypt(&self, stored: Vec<u8>) -> Result<Vec<u8>, StorageError> {
+        if stored.len() < NONCE_LEN {
+            return Err(StorageError::Decryption(
+                "value is too short to be encrypted".to_string(),
+            ));
+        }
+
+        let (nonce, ciphertext) = stored.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| StorageError::Decryption("authentication failed".to_string()))
+    }
+}
+
+#[async_trait]
+impl<S> RawStorageWithExpiry for Encrypted<S>
+where
+    S: RawStorageWithExpiry + Send + Sync,
+{
+    async fn store_raw_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        let value = self.encrypt(&value)?;
+        self.inner.store_raw_with_expiry(key, value, expiry).await
+    }
+
+    async fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+    ) -> Result<StoreState, StorageError> {
+        let value = self.encrypt(&value)?;
+        self.inner.store_raw_keep_ttl(key, value).await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorage for Encrypted<S>
+where
+    S: RawStorage + Send + Sync,
+{
+    async fn load_raw(&self, key: impl AsRef<str> + Send) -> Result<Option<Vec<u8>>, StorageError> {
+        self.inner
+            .load_raw(key)
+            .await?
+            .map(|stored| self.decrypt(stored))
+            .transpose()
+    }
+
+    async fn delete_raw(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.inner.delete_raw(key).await
+    }
+}
@@ -0,0 +1,318 @@
+//! Failover of reads to a secondary backend.
+//!
+//! [`Fallback`] sends every operation to a primary backend, typically
+//! `RedisStorageModule`. When a load from the primary fails with a transient
+//! error (see [`StorageError::is_transient`]), it is answered by a secondary
+//! backend instead, typically a local `IMCModule`. Writes only go to the
+//! primary, unless [`Fallback::with_mirrored_writes`] also copies them to the
+//! secondary.
+//!
+//! # Staleness
+//!
+//! The secondary only knows what was written to it. Without mirrored writes
+//! it holds whatever the application put there itself; with them, it still
+//! misses every write made through other processes or while mirroring
+//! failed. A value served during an outage may therefore be outdated, or
+//! missing although the primary has it.
+
+use async_trait::async_trait;
+
+use crate::{
+    asynchronous::{
+        AtomicStorage, RawStorage, RawStorageWithExpiry, StringStorage, StringStorageWithExpiry,
+    },
+    errors::StorageError,
+    types::StoreState,
+};
+
+/// Storage wrapper that reads from a secondary backend while the primary fails.
+///
+/// When the secondary fails as well, the primary's error is returned.
+/// Mirrored writes are best-effort: they happen after the primary write
+/// succeeded, and their failures are ignored.
+#[derive(Clone)]
+pub struct Fallback<P, S> {
+    /// The backend serving all operations while it is available
+    primary: P,
+    /// The backend answering loads the primary failed
+    secondary: S,
+    /// Whether successful writes are copied to the secondary
+    mirror_writes: bool,
+}
+
+impl<P, S> Fallback<P, S> {
+    /// Combines a primary backend with a secondary one for failed loads.
+    ///
+    /// # Arguments
+    /// * `primary` - The backend serving all operations while it is available
+    /// * `secondary` - The backend answering loads the primary failed
+    ///
+    /// # Returns
+    /// * `Self` - A new wrapper writing to the primary only
+    pub fn new(primary: P, secondary: S) -> Self {
+        Self {
+            primary,
+            secondary,
+            mirror_writes: false,
+        }
+    }
+
+    /// Copies every successful write to the secondary as well.
+    ///
+    /// # Returns
+    /// * `Self` - The wrapper with mirrored writes enabled
+    pub fn with_mirrored_writes(mut self) -> Self {
+        self.mirror_writes = true;
+        self
+    }
+
+    /// Returns a reference to the primary backend.
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    /// Returns a reference to the secondary backend.
+    pub fn secondary(&self) -> &S {
+        &self.secondary
+    }
+}
+
+#[async_trait]
+impl<P, S> StringStorageWithExpiry for Fallback<P, S>
+where
+    P: StringStorageWithExpiry + Send + Sync,
+    S: StringStorageWithExpiry + Send + Sync,
+{
+    async fn store_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        let mirrored = self.mirror_writes.then(|| value.clone());
+        let state = self.primary.store_with_expiry(key, value, expiry).await?;
+        if let Some(value) = mirrored {
+            let _ = self.secondary.store_with_expiry(key, value, expiry).await;
+        }
+
+        Ok(state)
+    }
+
+    async fn store_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        let mirrored = self.mirror_writes.then(|| value.clone());
+        let state = self.primary.store_keep_ttl(key, value).await?;
+        if let Some(value) = mirrored {
+            let _ = self.secondary.store_keep_ttl(key, value).await;
+        }
+
+        Ok(state)
+    }
+}
+
+#[async_trait]
+impl<P, S> StringStorage for Fallback<P, S>
+where
+    P: StringStorage + Send + Sync,
+    S: StringStorage + Send + Sync,
+{
+    async fn load_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        let key = key.as_ref();
+        match self.primary.load_string(key).await {
+            Err(e) if e.is_transient() => self.secondary.load_string(key).await.map_err(|_| e),
+            result => result,
+        }
+    }
+
+    async fn delete_string(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        let key = key.as_ref();
+        self.primary.delete_string(key).await?;
+        if self.mirror_writes {
+            let _ = self.secondary.delete_string(key).await;
+        }
+
+        Ok(())
+    }
+
+    /// Takes from the primary only, since a take also deletes the value
+    async fn take_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        let key = key.as_ref();
+        let value = self.primary.take_string(key).await?;
+        if self.mirror_writes {
+            let _ = self.secondary.delete_string(key).await;
+        }
+
+        Ok(value)
+    }
+}
+
+#[async_trait]
+impl<P, S> RawStorageWithExpiry for Fallback<P, S>
+where
+    P: RawStorageWithExpiry + Send + Sync,
+    S: RawStorageWithExpiry + Send + Sync,
+{
+    async fn store_raw_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        let mirrored = self.mirror_writes.then(|| value.clone());
+        let state = self
+            .primary
+            .store_raw_with_expiry(key, value, expiry)
+            .await?;
+        if let Some(value) = mirrored {
+            let _ = self
+                .secondary
+                .store_raw_with_expiry(key, value, expiry)
+                .await;
+        }
+
+        Ok(state)
+    }
+
+    async fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        let mirrored = self.mirror_writes.then(|| value.clone());
+        let state = self.primary.store_raw_keep_ttl(key, value).await?;
+        if let Some(value) = mirrored {
+            let _ = self.secondary.store_raw_keep_ttl(key, value).await;
+        }
+
+        Ok(state)
+    }
+}
+
+#[async_trait]
+impl<P, S> RawStorage for Fallback<P, S>
+where
+    P: RawStorage + Send + Sync,
+    S: RawStorage + Send + Sync,
+{
+    async fn load_raw(&self, key: impl AsRef<str> + Send) -> Result<Option<Vec<u8>>, StorageError> {
+        let key = key.as_ref();
+        match self.primary.load_raw(key).await {
+            Err(e) if e.is_transient() => self.secondary.load_raw(key).await.map_err(|_| e),
+            result => result,
+        }
+    }
+
+    async fn delete_raw(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        let key = key.as_ref();
+        self.primary.delete_raw(key).await?;
+        if self.mirror_writes {
+            let _ = self.secondary.delete_raw(key).await;
+        }
+
+        Ok(())
+    }
+}
+
+/// Mirrored atomic writes store the primary's resulting value in the
+/// secondary, rather than repeating the operation on it.
+#[async_trait]
+impl<P, S> AtomicStorage for Fallback<P, S>
+where
+    P: AtomicStorage + Send + Sync,
+    S: AtomicStorage + Send + Sync,
+{
+    async fn atomic_store(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        let state = self.primary.atomic_store(key, value).await?;
+        if self.mirror_writes {
+            let _ = self.secondary.atomic_store(key, value).await;
+        }
+
+        Ok(state)
+    }
+
+    async fn atomic_load(&self, key: impl AsRef<str> + Send) -> Result<Option<i64>, StorageError> {
+        let key = key.as_ref();
+        match self.primary.atomic_load(key).await {
+            Err(e) if e.is_transient() => self.secondary.atomic_load(key).await.map_err(|_| e),
+            result => result,
+        }
+    }
+
+    async fn atomic_delete(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        let key = key.as_ref();
+        self.primary.atomic_delete(key).await?;
+        if self.mirror_writes {
+            let _ = self.secondary.atomic_delete(key).await;
+        }
+
+        Ok(())
+    }
+
+    async fn atomic_increment(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<Option<i64>, StorageError> {
+        let key = key.as_ref();
+        let result = self.primary.atomic_increment(key, value).await?;
+        if let (true, Some(value)) = (self.mirror_writes, result) {
+            let _ = self.secondary.atomic_store(key, value).await;
+        }
+
+        Ok(result)
+    }
+
+    async fn atomic_increment_or_init(
+        &self,
+        key: impl AsRef<str> + Send,
+        delta: i64,
+        init: i64,
+    ) -> Result<i64, StorageError> {
+        let key = key.as_ref();
+        let value = self
+            .primary
+            .atomic_increment_or_init(key, delta, init)
+            .await?;
+        if self.mirror_writes {
+            let _ = self.secondary.atomic_store(key, value).await;
+        }
+
+        Ok(value)
+    }
+
+    async fn atomic_compare_and_swap(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected: i64,
+        new: i64,
+    ) -> Result<bool, StorageError> {
+        let key = key.as_ref();
+        let swapped = self
+            .primary
+            .atomic_compare_and_swap(key, expected, new)
+            .await?;
+        if self.mirror_writes && swapped {
+            let _ = self.secondary.atomic_store(key, new).await;
+        }
+
+        Ok(swapped)
+    }
+}
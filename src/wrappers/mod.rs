@@ -0,0 +1,32 @@
+//! Wrappers that add behavior on top of any storage backend.
+//!
+//! Each wrapper holds an inner storage and implements the same traits by
+//! delegating to it, so wrappers can be stacked freely.
+
+mod circuit_breaker;
+#[cfg(feature = "compression")]
+mod compressed;
+#[cfg(feature = "encryption")]
+mod encrypted;
+mod fallback;
+mod namespaced;
+mod retry;
+mod stale;
+mod tiered;
+mod timed;
+#[cfg(feature = "serde")]
+mod typed;
+
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+#[cfg(feature = "compression")]
+pub use compressed::{Compressed, DEFAULT_COMPRESSION_LEVEL};
+#[cfg(feature = "encryption")]
+pub use encrypted::{Encrypted, ENCRYPTION_KEY_LEN};
+pub use fallback::Fallback;
+pub use namespaced::{Namespaced, NAMESPACE_SEPARATOR};
+pub use retry::{Retry, RetryConfig};
+pub use stale::{StaleConfig, StaleWhileError};
+pub use tiered::Tiered;
+pub use timed::Timed;
+#[cfg(feature = "serde")]
+pub use typed::TypedStorage;
@@ -0,0 +1,237 @@
+//! Transparent key prefixing for sharing one backend between tenants.
+//!
+//! [`Namespaced`] prepends its prefix and [`NAMESPACE_SEPARATOR`] to every key
+//! before delegating, so callers work with bare keys while entries of
+//! different namespaces never collide in the underlying storage.
+
+use async_trait::async_trait;
+
+use crate::{
+    asynchronous::{
+        AtomicStorage, BulkDelete, KeyListing, KeyRename, RawStorage, RawStorageWithExpiry,
+        StringStorage, StringStorageWithExpiry,
+    },
+    errors::StorageError,
+    types::StoreState,
+};
+
+/// Separator placed between the namespace prefix and the key.
+pub const NAMESPACE_SEPARATOR: &str = ":";
+
+/// Storage wrapper that places every key under a fixed prefix.
+///
+/// A key `k` in namespace `tenant` is stored as `tenant:k`. Key listings only
+/// return keys of this namespace, with the prefix stripped again.
+#[derive(Clone)]
+pub struct Namespaced<S> {
+    /// The wrapped storage backend
+    inner: S,
+    /// The namespace prefix followed by [`NAMESPACE_SEPARATOR`]
+    prefix: String,
+}
+
+impl<S> Namespaced<S> {
+    /// Wraps a storage backend.
+    ///
+    /// # Arguments
+    /// * `inner` - The storage backend to wrap
+    /// * `prefix` - The namespace every key is placed under
+    ///
+    /// # Returns
+    /// * `Self` - A new wrapper scoped to the namespace
+    pub fn new(inner: S, prefix: impl Into<String>) -> Self {
+        Self {
+            inner,
+            prefix: format!("{}{}", prefix.into(), NAMESPACE_SEPARATOR),
+        }
+    }
+
+    /// Returns a reference to the wrapped storage backend.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Maps a key to its namespaced form.
+    fn key(&self, key: impl AsRef<str>) -> String {
+        format!("{}{}", self.prefix, key.as_ref())
+    }
+}
+
+#[async_trait]
+impl<S> StringStorageWithExpiry for Namespaced<S>
+where
+    S: StringStorageWithExpiry + Send + Sync,
+{
+    async fn store_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.inner
+            .store_with_expiry(self.key(key), value, expiry)
+            .await
+    }
+
+    async fn store_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<StoreState, StorageError> {
+        self.inner.store_keep_ttl(self.key(key), value).await
+    }
+}
+
+#[async_trait]
+impl<S> StringStorage for Namespaced<S>
+where
+    S: StringStorage + Send + Sync,
+{
+    async fn load_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        self.inner.load_string(self.key(key)).await
+    }
+
+    async fn delete_string(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.inner.delete_string(self.key(key)).await
+    }
+
+    async fn take_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        self.inner.take_string(self.key(key)).await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorageWithExpiry for Namespaced<S>
+where
+    S: RawStorageWithExpiry + Send + Sync,
+{
+    async fn store_raw_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.inner
+            .store_raw_with_expiry(self.key(key), value, expiry)
+            .await
+    }
+
+    async fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+    ) -> Result<StoreState, StorageError> {
+        self.inner.store_raw_keep_ttl(self.key(key), value).await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorage for Namespaced<S>
+where
+    S: RawStorage + Send + Sync,
+{
+    async fn load_raw(&self, key: impl AsRef<str> + Send) -> Result<Option<Vec<u8>>, StorageError> {
+        self.inner.load_raw(self.key(key)).await
+    }
+
+    async fn delete_raw(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.inner.delete_raw(self.key(key)).await
+    }
+}
+
+#[async_trait]
+impl<S> AtomicStorage for Namespaced<S>
+where
+    S: AtomicStorage + Send + Sync,
+{
+    async fn atomic_store(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<StoreState, StorageError> {
+        self.inner.atomic_store(self.key(key), value).await
+    }
+
+    async fn atomic_load(&self, key: impl AsRef<str> + Send) -> Result<Option<i64>, StorageError> {
+        self.inner.atomic_load(self.key(key)).await
+    }
+
+    async fn atomic_delete(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.inner.atomic_delete(self.key(key)).await
+    }
+
+    async fn atomic_increment(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<Option<i64>, StorageError> {
+        self.inner.atomic_increment(self.key(key), value).await
+    }
+
+    async fn atomic_increment_or_init(
+        &self,
+        key: impl AsRef<str> + Send,
+        delta: i64,
+        init: i64,
+    ) -> Result<i64, StorageError> {
+        self.inner
+            .atomic_increment_or_init(self.key(key), delta, init)
+            .await
+    }
+
+    async fn atomic_compare_and_swap(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected: i64,
+        new: i64,
+    ) -> Result<bool, StorageError> {
+        self.inner
+            .atomic_compare_and_swap(self.key(key), expected, new)
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> KeyListing for Namespaced<S>
+where
+    S: KeyListing + Send + Sync,
+{
+    async fn keys_with_prefix(&self, prefix: String) -> Result<Vec<String>, StorageError> {
+        let keys = self.inner.keys_with_prefix(self.key(prefix)).await?;
+
+        Ok(keys
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&self.prefix).map(str::to_string))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl<S> BulkDelete for Namespaced<S>
+where
+    S: BulkDelete + Send + Sync,
+{
+    async fn delete_prefix(&self, prefix: String) -> Result<u64, StorageError> {
+        self.inner.delete_prefix(self.key(prefix)).await
+    }
+}
+
+#[async_trait]
+impl<S> KeyRename for Namespaced<S>
+where
+    S: KeyRename + Send + Sync,
+{
+    async fn rename(
+        &self,
+        from: impl AsRef<str> + Send,
+        to: impl AsRef<str> + Send,
+    ) -> Result<(), StorageError> {
+        self.inner.rename(self.key(from), self.key(to)).await
+    }
+}
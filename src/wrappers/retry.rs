@@ -0,0 +1,294 @@
+//! Retrying operations that fail with transient errors.
+//!
+//! [`Retry`] reruns a failed call when the error is transient (see
+//! [`StorageError::is_transient`]), waiting an exponentially growing,
+//! jittered delay between attempts. It works with any backend and composes
+//! with [`Timed`](super::Timed), whose timeouts are transient, and
+//! [`CircuitBreaker`](super::CircuitBreaker), whose fast failures are too.
+
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    asynchronous::{
+        AtomicStorage, BulkDelete, KeyListing, KeyRename, RawStorage, RawStorageWithExpiry,
+        StringStorage, StringStorageWithExpiry,
+    },
+    errors::StorageError,
+    types::StoreState,
+};
+
+/// Configuration for [`Retry`].
+#[derive(Clone)]
+pub struct RetryConfig {
+    /// Maximum number of attempts per operation, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled after every further attempt
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between attempts
+    pub max_backoff: Duration,
+}
+
+/// Storage wrapper that retries operations failing with transient errors.
+///
+/// Each delay is drawn at random between half and all of the current backoff,
+/// so clients that failed together don't retry in lockstep. Other errors,
+/// such as `StorageError::DeserializationError`, are returned immediately, as
+/// is the error of the last attempt.
+///
+/// A transient failure doesn't prove the operation wasn't applied, e.g. when
+/// the connection dropped after Redis executed it. Retried stores and deletes
+/// are idempotent, but a retried `atomic_increment` may be applied twice.
+#[derive(Clone)]
+pub struct Retry<S> {
+    /// The wrapped storage backend
+    inner: S,
+    /// Attempt limit and backoff bounds
+    config: RetryConfig,
+}
+
+impl<S> Retry<S> {
+    /// Wraps a storage backend.
+    ///
+    /// # Arguments
+    /// * `inner` - The storage backend to wrap
+    /// * `config` - Attempt limit and backoff bounds
+    ///
+    /// # Returns
+    /// * `Self` - A new wrapper retrying failed operations
+    pub fn new(inner: S, config: RetryConfig) -> Self {
+        Self { inner, config }
+    }
+
+    /// Returns a reference to the wrapped storage backend.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Runs an operation until it succeeds, fails permanently or runs out of attempts.
+    async fn retry<T, F, Fut>(&self, mut operation: F) -> Result<T, StorageError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, StorageError>>,
+    {
+        let mut backoff = self.config.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Err(e) if e.is_transient() && attempt < self.config.max_attempts => {
+                    tokio::time::sleep(jittered(backoff)).await;
+                    backoff = backoff.saturating_mul(2).min(self.config.max_backoff);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Picks a random delay between half of `backoff` and `backoff`
+///
+/// `RandomState` is seeded from the operating system's randomness, which is
+/// plenty for spreading out retries.
+fn jittered(backoff: Duration) -> Duration {
+    let half = backoff / 2;
+    let random = RandomState::new().build_hasher().finish();
+    let fraction = (random >> 11) as f64 / (1u64 << 53) as f64;
+    half + half.mul_f64(fraction)
+}
+
+#[async_trait]
+impl<S> StringStorageWithExpiry for Retry<S>
+where
+    S: StringStorageWithExpiry + Send + Sync,
+{
+    async fn store_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.store_with_expiry(key, value.clone(), expiry))
+            .await
+    }
+
+    async fn store_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.store_keep_ttl(key, value.clone()))
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StringStorage for Retry<S>
+where
+    S: StringStorage + Send + Sync,
+{
+    async fn load_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.load_string(key)).await
+    }
+
+    async fn delete_string(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.delete_string(key)).await
+    }
+
+    async fn take_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.take_string(key)).await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorageWithExpiry for Retry<S>
+where
+    S: RawStorageWithExpiry + Send + Sync,
+{
+    async fn store_raw_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.store_raw_with_expiry(key, value.clone(), expiry))
+            .await
+    }
+
+    async fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.store_raw_keep_ttl(key, value.clone()))
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorage for Retry<S>
+where
+    S: RawStorage + Send + Sync,
+{
+    async fn load_raw(&self, key: impl AsRef<str> + Send) -> Result<Option<Vec<u8>>, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.load_raw(key)).await
+    }
+
+    async fn delete_raw(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.delete_raw(key)).await
+    }
+}
+
+#[async_trait]
+impl<S> AtomicStorage for Retry<S>
+where
+    S: AtomicStorage + Send + Sync,
+{
+    async fn atomic_store(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.atomic_store(key, value)).await
+    }
+
+    async fn atomic_load(&self, key: impl AsRef<str> + Send) -> Result<Option<i64>, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.atomic_load(key)).await
+    }
+
+    async fn atomic_delete(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.atomic_delete(key)).await
+    }
+
+    async fn atomic_increment(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<Option<i64>, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.atomic_increment(key, value)).await
+    }
+
+    async fn atomic_increment_or_init(
+        &self,
+        key: impl AsRef<str> + Send,
+        delta: i64,
+        init: i64,
+    ) -> Result<i64, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.atomic_increment_or_init(key, delta, init))
+            .await
+    }
+
+    async fn atomic_compare_and_swap(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected: i64,
+        new: i64,
+    ) -> Result<bool, StorageError> {
+        let key = key.as_ref();
+        self.retry(|| self.inner.atomic_compare_and_swap(key, expected, new))
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> KeyListing for Retry<S>
+where
+    S: KeyListing + Send + Sync,
+{
+    async fn keys_with_prefix(&self, prefix: String) -> Result<Vec<String>, StorageError> {
+        self.retry(|| self.inner.keys_with_prefix(prefix.clone()))
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> BulkDelete for Retry<S>
+where
+    S: BulkDelete + Send + Sync,
+{
+    async fn delete_prefix(&self, prefix: String) -> Result<u64, StorageError> {
+        self.retry(|| self.inner.delete_prefix(prefix.clone()))
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> KeyRename for Retry<S>
+where
+    S: KeyRename + Send + Sync,
+{
+    async fn rename(
+        &self,
+        from: impl AsRef<str> + Send,
+        to: impl AsRef<str> + Send,
+    ) -> Result<(), StorageError> {
+        let (from, to) = (from.as_ref(), to.as_ref());
+        self.retry(|| self.inner.rename(from, to)).await
+    }
+}
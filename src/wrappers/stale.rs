@@ -0,0 +1,246 @@
+//! Graceful degradation by serving stale values when the backend fails.
+//!
+//! [`StaleWhileError`] remembers recently read values in-process and, when a
+//! read from the underlying backend fails with a transient error, answers with
+//! the last known value instead. This is strictly best-effort: values older
+//! than the configured staleness window are never served, and once the window
+//! lapses the original error is returned.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+
+use crate::{
+    asynchronous::{RawStorage, RawStorageWithExpiry, StringStorage, StringStorageWithExpiry},
+    errors::StorageError,
+    types::StoreState,
+};
+
+/// Configuration for [`StaleWhileError`].
+#[derive(Clone)]
+pub struct StaleConfig {
+    /// Maximum age of a remembered value that may still be served on error
+    pub max_staleness: Duration,
+    /// Maximum number of values remembered per store
+    pub max_entries: usize,
+}
+
+/// Storage wrapper that serves last-known values during backend outages.
+///
+/// Successful reads are remembered alongside the time they were read. When a
+/// later read of the same key fails with a transient error (see
+/// [`StorageError::is_transient`]), the remembered value is returned if it is
+/// younger than [`StaleConfig::max_staleness`]. Writes and deletes always go
+/// to the underlying backend and discard the remembered value.
+#[derive(Clone)]
+pub struct StaleWhileError<S> {
+    /// The wrapped storage backend
+    inner: S,
+    /// Recently read string values
+    strings: StaleCache<String>,
+    /// Recently read binary values
+    raw: StaleCache<Vec<u8>>,
+}
+
+impl<S> StaleWhileError<S> {
+    /// Wraps a storage backend.
+    ///
+    /// # Arguments
+    /// * `inner` - The storage backend to wrap
+    /// * `config` - Staleness window and cache size limits
+    ///
+    /// # Returns
+    /// * `Self` - A new wrapper with no remembered values
+    pub fn new(inner: S, config: StaleConfig) -> Self {
+        Self {
+            inner,
+            strings: StaleCache::new(config.clone()),
+            raw: StaleCache::new(config),
+        }
+    }
+
+    /// Returns a reference to the wrapped storage backend.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+/// Bounded map of last-known values and the time they were read.
+#[derive(Clone)]
+struct StaleCache<V> {
+    config: StaleConfig,
+    entries: Arc<DashMap<String, (V, Instant)>>,
+}
+
+impl<V: Clone> StaleCache<V> {
+    fn new(config: StaleConfig) -> Self {
+        Self {
+            config,
+            entries: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Records the outcome of a successful read.
+    fn remember(&self, key: String, value: &Option<V>) {
+        let Some(value) = value else {
+            self.entries.remove(&key);
+            return;
+        };
+
+        if self.config.max_entries == 0 {
+            return;
+        }
+
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.config.max_entries {
+            self.evict_oldest();
+        }
+
+        self.entries.insert(key, (value.clone(), Instant::now()));
+    }
+
+    /// Returns the remembered value if it is still within the staleness window.
+    fn recall(&self, key: &str) -> Option<V> {
+        self.entries
+            .get(key)
+            .filter(|entry| entry.value().1.elapsed() <= self.config.max_staleness)
+            .map(|entry| entry.value().0.clone())
+    }
+
+    /// Discards the remembered value for a key.
+    fn forget(&self, key: &str) {
+        self.entries.remove(key);
+    }
+
+    fn evict_oldest(&self) {
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|entry| entry.value().1)
+            .map(|entry| entry.key().clone());
+
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+/// Resolves a failed read against the stale cache.
+fn serve_stale<V: Clone>(
+    cache: &StaleCache<V>,
+    key: &str,
+    error: StorageError,
+) -> Result<Option<V>, StorageError> {
+    match cache.recall(key) {
+        Some(value) if error.is_transient() => Ok(Some(value)),
+        _ => Err(error),
+    }
+}
+
+#[async_trait]
+impl<S> StringStorageWithExpiry for StaleWhileError<S>
+where
+    S: StringStorageWithExpiry + Send + Sync,
+{
+    async fn store_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.strings.forget(key.as_ref());
+        self.inner.store_with_expiry(key, value, expiry).await
+    }
+
+    async fn store_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<StoreState, StorageError> {
+        self.strings.forget(key.as_ref());
+        self.inner.store_keep_ttl(key, value).await
+    }
+}
+
+#[async_trait]
+impl<S> StringStorage for StaleWhileError<S>
+where
+    S: StringStorage + Send + Sync,
+{
+    async fn load_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        let key = key.as_ref();
+        match self.inner.load_string(key).await {
+            Ok(value) => {
+                self.strings.remember(key.to_string(), &value);
+                Ok(value)
+            }
+            Err(error) => serve_stale(&self.strings, key, error),
+        }
+    }
+
+    async fn delete_string(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.strings.forget(key.as_ref());
+        self.inner.delete_string(key).await
+    }
+
+    async fn take_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        self.strings.forget(key.as_ref());
+        self.inner.take_string(key).await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorageWithExpiry for StaleWhileError<S>
+where
+    S: RawStorageWithExpiry + Send + Sync,
+{
+    async fn store_raw_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.raw.forget(key.as_ref());
+        self.inner.store_raw_with_expiry(key, value, expiry).await
+    }
+
+    async fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+    ) -> Result<StoreState, StorageError> {
+        self.raw.forget(key.as_ref());
+        self.inner.store_raw_keep_ttl(key, value).await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorage for StaleWhileError<S>
+where
+    S: RawStorage + Send + Sync,
+{
+    async fn load_raw(&self, key: impl AsRef<str> + Send) -> Result<Option<Vec<u8>>, StorageError> {
+        let key = key.as_ref();
+        match self.inner.load_raw(key).await {
+            Ok(value) => {
+                self.raw.remember(key.to_string(), &value);
+                Ok(value)
+            }
+            Err(error) => serve_stale(&self.raw, key, error),
+        }
+    }
+
+    async fn delete_raw(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.raw.forget(key.as_ref());
+        self.inner.delete_raw(key).await
+    }
+}
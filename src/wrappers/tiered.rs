@@ -0,0 +1,249 @@
+//! Two-tier read-through caching.
+//!
+//! [`Tiered`] combines a fast local store (L1, typically `IMCModule`) with a
+//! shared store that acts as the source of truth (L2, typically
+//! `RedisStorageModule`). Reads are served from L1 when possible and fall back
+//! to L2 on a miss, populating L1 with the remaining TTL of the L2 entry.
+//! Writes go through to both tiers.
+//!
+//! # Consistency
+//!
+//! L1 is local to each process and is not invalidated by writes made through
+//! other processes. Until its L1 entry expires, a node may therefore keep
+//! serving a value that has since been overwritten or deleted in L2. Use
+//! [`Tiered::with_l1_max_ttl`] to bound how long such stale copies can live.
+
+use async_trait::async_trait;
+
+use crate::{
+    asynchronous::{KeyExists, StringExpiry, StringStorage, StringStorageWithExpiry},
+    errors::StorageError,
+    types::StoreState,
+};
+
+/// Read-through cache layering a local store in front of a shared one.
+#[derive(Clone)]
+pub struct Tiered<L1, L2> {
+    /// The fast local tier
+    l1: L1,
+    /// The shared tier acting as the source of truth
+    l2: L2,
+    /// Upper bound on the expiry of entries written to L1, in seconds
+    l1_max_ttl: Option<u64>,
+}
+
+impl<L1, L2> Tiered<L1, L2> {
+    /// Layers a local store in front of a shared one.
+    ///
+    /// # Arguments
+    /// * `l1` - The fast local tier
+    /// * `l2` - The shared tier acting as the source of truth
+    ///
+    /// # Returns
+    /// * `Self` - A new tiered cache whose L1 entries expire with their L2 entries
+    pub fn new(l1: L1, l2: L2) -> Self {
+        Self {
+            l1,
+            l2,
+            l1_max_ttl: None,
+        }
+    }
+
+    /// Limits how long entries are kept in L1.
+    ///
+    /// # Arguments
+    /// * `secs` - The maximum expiry of L1 entries in seconds
+    ///
+    /// # Returns
+    /// * `Self` - The tiered cache with the limit applied
+    pub fn with_l1_max_ttl(mut self, secs: u64) -> Self {
+        self.l1_max_ttl = Some(secs);
+        self
+    }
+
+    /// Returns a reference to the local tier.
+    pub fn l1(&self) -> &L1 {
+        &self.l1
+    }
+
+    /// Returns a reference to the shared tier.
+    pub fn l2(&self) -> &L2 {
+        &self.l2
+    }
+
+    /// Caps an expiry at the L1 limit.
+    fn l1_expiry(&self, expiry: Option<u64>) -> Option<u64> {
+        match (expiry, self.l1_max_ttl) {
+            (Some(expiry), Some(max)) => Some(expiry.min(max)),
+            (expiry, max) => expiry.or(max),
+        }
+    }
+}
+
+impl<L1, L2> Tiered<L1, L2>
+where
+    L1: StringStorage + Send + Sync,
+    L2: StringStorage + StringExpiry + KeyExists + Send + Sync,
+{
+    /// Copies a value read from or written to L2 into L1 with the remaining TTL of the L2 entry.
+    ///
+    /// If the key has vanished from L2 in the meantime, it is dropped from L1 instead
+    /// of being cached without an expiry.
+    async fn populate_l1(&self, key: &str, value: String) -> Result<(), StorageError> {
+        let ttl = self.l2.ttl(key).await?;
+        if ttl.is_none() && !self.l2.exists(key).await? {
+            return self.l1.delete_string(key).await;
+        }
+        self.l1
+            .store_with_expiry(key, value, self.l1_expiry(ttl))
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<L1, L2> StringStorageWithExpiry for Tiered<L1, L2>
+where
+    L1: StringStorage + Send + Sync,
+    L2: StringStorage + StringExpiry + KeyExists + Send + Sync,
+{
+    async fn store_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        let state = self
+            .l2
+            .store_with_expiry(key, value.clone(), expiry)
+            .await?;
+        match expiry {
+            Some(_) => {
+                self.l1
+                    .store_with_expiry(key, value, self.l1_expiry(expiry))
+                    .await?;
+            }
+            // L2 may apply a default expiry of its own, which L1 has to follow
+            None => self.populate_l1(key, value).await?,
+        }
+
+        Ok(state)
+    }
+
+    async fn store_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<StoreState, StorageError> {
+        let key = key.as_ref();
+        let state = self.l2.store_keep_ttl(key, value).await?;
+        // The L2 expiry is unknown here; the next read repopulates L1 with it
+        self.l1.delete_string(key).await?;
+
+        Ok(state)
+    }
+}
+
+#[async_trait]
+impl<L1, L2> StringStorage for Tiered<L1, L2>
+where
+    L1: StringStorage + Send + Sync,
+    L2: StringStorage + StringExpiry + KeyExists + Send + Sync,
+{
+    async fn load_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        let key = key.as_ref();
+        if let Some(value) = self.l1.load_string(key).await? {
+            return Ok(Some(value));
+        }
+
+        let Some(value) = self.l2.load_string(key).await? else {
+            return Ok(None);
+        };
+
+        self.populate_l1(key, value.clone()).await?;
+
+        Ok(Some(value))
+    }
+
+    async fn delete_string(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        let key = key.as_ref();
+        self.l2.delete_string(key).await?;
+        self.l1.delete_string(key).await
+    }
+
+    async fn take_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        let key = key.as_ref();
+        self.l1.delete_string(key).await?;
+        self.l2.take_string(key).await
+    }
+}
+
+#[cfg(all(test, feature = "imc"))]
+mod tests {
+    use super::Tiered;
+    use crate::{
+        asynchronous::{StringExpiry, StringStorage, StringStorageWithExpiry},
+        clock::MockClock,
+        storage::imc::{IMCConfig, IMCModule},
+    };
+
+    /// Creates two in-memory tiers whose shared clock only moves when the returned handle is advanced.
+    fn tiered() -> (Tiered<IMCModule, IMCModule>, MockClock) {
+        let clock = MockClock::new(1_000_000);
+        let l1 = IMCModule::with_clock(IMCConfig::default(), clock.clone());
+        let l2 = IMCModule::with_clock(IMCConfig::default(), clock.clone());
+        (Tiered::new(l1, l2), clock)
+    }
+
+    #[tokio::test]
+    async fn l2_hit_populates_l1_with_the_remaining_ttl() {
+        let (storage, clock) = tiered();
+        storage
+            .l2()
+            .store_with_expiry("session", "abc".to_string(), Some(60))
+            .await
+            .unwrap();
+        clock.advance(20);
+
+        assert_eq!(
+            storage.load_string("session").await.unwrap().as_deref(),
+            Some("abc")
+        );
+        assert_eq!(storage.l1().ttl("session").await.unwrap(), Some(40));
+
+        clock.advance(40);
+        assert_eq!(storage.l1().load_string("session").await.unwrap(), None);
+        assert_eq!(storage.load_string("session").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn l1_max_ttl_caps_populated_entries() {
+        let (storage, _) = tiered();
+        let storage = storage.with_l1_max_ttl(10);
+        storage
+            .l2()
+            .store_string("user", "abc".to_string())
+            .await
+            .unwrap();
+
+        storage.load_string("user").await.unwrap();
+        assert_eq!(storage.l1().ttl("user").await.unwrap(), Some(10));
+        assert_eq!(storage.l2().ttl("user").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn missing_keys_are_not_cached() {
+        let (storage, _) = tiered();
+
+        assert_eq!(storage.load_string("missing").await.unwrap(), None);
+        assert_eq!(storage.l1().load_string("missing").await.unwrap(), None);
+    }
+}
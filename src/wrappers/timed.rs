@@ -0,0 +1,325 @@
+//! Upper bound on the latency of every storage operation.
+//!
+//! [`Timed`] runs each call to the underlying backend under
+//! `tokio::time::timeout`, so a hung connection surfaces as an error instead
+//! of blocking the caller indefinitely.
+
+use std::{future::Future, time::Duration};
+
+use async_trait::async_trait;
+
+use crate::{
+    asynchronous::{
+        AtomicStorage, BulkDelete, KeyListing, KeyRename, RawStorage, RawStorageWithExpiry,
+        StringStorage, StringStorageWithExpiry,
+    },
+    errors::StorageError,
+    types::StoreState,
+};
+
+/// Storage wrapper that fails operations taking longer than a fixed timeout.
+///
+/// An operation that exceeds the timeout fails with
+/// [`StorageError::Timeout`]. The underlying operation is cancelled by being
+/// dropped, so a write that times out may or may not have been applied.
+/// Otherwise results are passed through unchanged.
+#[derive(Clone)]
+pub struct Timed<S> {
+    /// The wrapped storage backend
+    inner: S,
+    /// Maximum time an operation may take
+    timeout: Duration,
+}
+
+impl<S> Timed<S> {
+    /// Wraps a storage backend.
+    ///
+    /// # Arguments
+    /// * `inner` - The storage backend to wrap
+    /// * `timeout` - Maximum time each operation may take
+    ///
+    /// # Returns
+    /// * `Self` - A new wrapper bounding every operation by `timeout`
+    pub fn new(inner: S, timeout: Duration) -> Self {
+        Self { inner, timeout }
+    }
+
+    /// Returns a reference to the wrapped storage backend.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Runs an operation, failing with a timeout error if it takes too long.
+    async fn timed<T>(
+        &self,
+        operation: impl Future<Output = Result<T, StorageError>>,
+    ) -> Result<T, StorageError> {
+        tokio::time::timeout(self.timeout, operation)
+            .await
+            .map_err(|_| StorageError::Timeout(format!("no response within {:?}", self.timeout)))?
+    }
+}
+
+#[async_trait]
+impl<S> StringStorageWithExpiry for Timed<S>
+where
+    S: StringStorageWithExpiry + Send + Sync,
+{
+    async fn store_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.timed(self.inner.store_with_expiry(key, value, expiry))
+            .await
+    }
+
+    async fn store_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: String,
+    ) -> Result<StoreState, StorageError> {
+        self.timed(self.inner.store_keep_ttl(key, value)).await
+    }
+}
+
+#[async_trait]
+impl<S> StringStorage for Timed<S>
+where
+    S: StringStorage + Send + Sync,
+{
+    async fn load_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        self.timed(self.inner.load_string(key)).await
+    }
+
+    async fn delete_string(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.timed(self.inner.delete_string(key)).await
+    }
+
+    async fn take_string(
+        &self,
+        key: impl AsRef<str> + Send,
+    ) -> Result<Option<String>, StorageError> {
+        self.timed(self.inner.take_string(key)).await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorageWithExpiry for Timed<S>
+where
+    S: RawStorageWithExpiry + Send + Sync,
+{
+    async fn store_raw_with_expiry(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        self.timed(self.inner.store_raw_with_expiry(key, value, expiry))
+            .await
+    }
+
+    async fn store_raw_keep_ttl(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: Vec<u8>,
+    ) -> Result<StoreState, StorageError> {
+        self.timed(self.inner.store_raw_keep_ttl(key, value)).await
+    }
+}
+
+#[async_trait]
+impl<S> RawStorage for Timed<S>
+where
+    S: RawStorage + Send + Sync,
+{
+    async fn load_raw(&self, key: impl AsRef<str> + Send) -> Result<Option<Vec<u8>>, StorageError> {
+        self.timed(self.inner.load_raw(key)).await
+    }
+
+    async fn delete_raw(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.timed(self.inner.delete_raw(key)).await
+    }
+}
+
+#[async_trait]
+impl<S> AtomicStorage for Timed<S>
+where
+    S: AtomicStorage + Send + Sync,
+{
+    async fn atomic_store(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<StoreState, StorageError> {
+        self.timed(self.inner.atomic_store(key, value)).await
+    }
+
+    async fn atomic_load(&self, key: impl AsRef<str> + Send) -> Result<Option<i64>, StorageError> {
+        self.timed(self.inner.atomic_load(key)).await
+    }
+
+    async fn atomic_delete(&self, key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+        self.timed(self.inner.atomic_delete(key)).await
+    }
+
+    async fn atomic_increment(
+        &self,
+        key: impl AsRef<str> + Send,
+        value: i64,
+    ) -> Result<Option<i64>, StorageError> {
+        self.timed(self.inner.atomic_increment(key, value)).await
+    }
+
+    async fn atomic_increment_or_init(
+        &self,
+        key: impl AsRef<str> + Send,
+        delta: i64,
+        init: i64,
+    ) -> Result<i64, StorageError> {
+        self.timed(self.inner.atomic_increment_or_init(key, delta, init))
+            .await
+    }
+
+    async fn atomic_compare_and_swap(
+        &self,
+        key: impl AsRef<str> + Send,
+        expected: i64,
+        new: i64,
+    ) -> Result<bool, StorageError> {
+        self.timed(self.inner.atomic_compare_and_swap(key, expected, new))
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> KeyListing for Timed<S>
+where
+    S: KeyListing + Send + Sync,
+{
+    async fn keys_with_prefix(&self, prefix: String) -> Result<Vec<String>, StorageError> {
+        self.timed(self.inner.keys_with_prefix(prefix)).await
+    }
+}
+
+#[async_trait]
+impl<S> BulkDelete for Timed<S>
+where
+    S: BulkDelete + Send + Sync,
+{
+    async fn delete_prefix(&self, prefix: String) -> Result<u64, StorageError> {
+        self.timed(self.inner.delete_prefix(prefix)).await
+    }
+}
+
+#[async_trait]
+impl<S> KeyRename for Timed<S>
+where
+    S: KeyRename + Send + Sync,
+{
+    async fn rename(
+        &self,
+        from: impl AsRef<str> + Send,
+        to: impl AsRef<str> + Send,
+    ) -> Result<(), StorageError> {
+        self.timed(self.inner.rename(from, to)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use super::Timed;
+    use crate::{
+        asynchronous::{StringStorage, StringStorageWithExpiry},
+        errors::StorageError,
+        types::StoreState,
+    };
+
+    /// Storage that answers every call after a fixed delay.
+    struct Slow(Duration);
+
+    #[async_trait]
+    impl StringStorageWithExpiry for Slow {
+        async fn store_with_expiry(
+            &self,
+            _key: impl AsRef<str> + Send,
+            _value: String,
+            _expiry: Option<u64>,
+        ) -> Result<StoreState, StorageError> {
+            tokio::time::sleep(self.0).await;
+            Ok(StoreState::New)
+        }
+
+        async fn store_keep_ttl(
+            &self,
+            _key: impl AsRef<str> + Send,
+            _value: String,
+        ) -> Result<StoreState, StorageError> {
+            tokio::time::sleep(self.0).await;
+            Ok(StoreState::New)
+        }
+    }
+
+    #[async_trait]
+    impl StringStorage for Slow {
+        async fn load_string(
+            &self,
+            _key: impl AsRef<str> + Send,
+        ) -> Result<Option<String>, StorageError> {
+            tokio::time::sleep(self.0).await;
+            Ok(Some("value".to_string()))
+        }
+
+        async fn delete_string(&self, _key: impl AsRef<str> + Send) -> Result<(), StorageError> {
+            tokio::time::sleep(self.0).await;
+            Ok(())
+        }
+
+        async fn take_string(
+            &self,
+            _key: impl AsRef<str> + Send,
+        ) -> Result<Option<String>, StorageError> {
+            tokio::time::sleep(self.0).await;
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn slow_operations_time_out() {
+        let storage = Timed::new(Slow(Duration::from_secs(5)), Duration::from_millis(20));
+
+        assert!(matches!(
+            storage.load_string("key").await,
+            Err(StorageError::Timeout(_))
+        ));
+        assert!(matches!(
+            storage.store_string("key", "value".to_string()).await,
+            Err(StorageError::Timeout(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn fast_operations_pass_through() {
+        let storage = Timed::new(Slow(Duration::ZERO), Duration::from_secs(5));
+
+        assert_eq!(
+            storage.load_string("key").await.unwrap().as_deref(),
+            Some("value")
+        );
+        assert_eq!(
+            storage
+                .store_string("key", "value".to_string())
+                .await
+                .unwrap(),
+            StoreState::New
+        );
+    }
+}
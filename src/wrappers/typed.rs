@@ -0,0 +1,101 @@
+//! Typed access to raw storage through serde.
+//!
+//! [`TypedStorage`] encodes values with a [`Codec`] and stores the bytes
+//! through the wrapped [`RawStorage`], so callers can put and get their own
+//! types without converting them by hand. JSON is used unless another codec is
+//! chosen with [`TypedStorage::with_codec`].
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    asynchronous::RawStorage,
+    codec::{Codec, JsonCodec},
+    errors::StorageError,
+    types::StoreState,
+};
+
+/// Storage wrapper that stores serde-serializable values using a [`Codec`].
+#[derive(Clone)]
+pub struct TypedStorage<S, C = JsonCodec> {
+    /// The wrapped storage backend
+    inner: S,
+    /// The format values are encoded with
+    codec: C,
+}
+
+impl<S> TypedStorage<S> {
+    /// Wraps a storage backend, encoding values as JSON.
+    ///
+    /// # Arguments
+    /// * `inner` - The storage backend to wrap
+    ///
+    /// # Returns
+    /// * `Self` - A new typed wrapper
+    pub fn new(inner: S) -> Self {
+        Self::with_codec(inner, JsonCodec)
+    }
+}
+
+impl<S, C> TypedStorage<S, C> {
+    /// Wraps a storage backend, encoding values with the given codec.
+    ///
+    /// # Arguments
+    /// * `inner` - The storage backend to wrap
+    /// * `codec` - The format values are encoded with
+    ///
+    /// # Returns
+    /// * `Self` - A new typed wrapper
+    pub fn with_codec(inner: S, codec: C) -> Self {
+        Self { inner, codec }
+    }
+
+    /// Returns a reference to the wrapped storage backend.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S, C> TypedStorage<S, C>
+where
+    S: RawStorage + Send + Sync,
+    C: Codec,
+{
+    /// Encodes a value and stores it with optional expiry.
+    ///
+    /// # Arguments
+    /// * `key` - The key to store the value under
+    /// * `value` - The value to encode
+    /// * `expiry` - Optional expiration time in seconds
+    ///
+    /// # Returns
+    /// * `Result<StoreState, StorageError>` - Whether the value was inserted or
+    ///   updated, or `StorageError::DeserializationError` if it cannot be
+    ///   encoded
+    pub async fn put<T: Serialize + ?Sized>(
+        &self,
+        key: String,
+        value: &T,
+        expiry: Option<u64>,
+    ) -> Result<StoreState, StorageError> {
+        let bytes = self.codec.encode(value)?;
+
+        self.inner.store_raw_with_expiry(key, bytes, expiry).await
+    }
+
+    /// Loads a value and decodes it.
+    ///
+    /// # Arguments
+    /// * `key` - The key to look up
+    ///
+    /// # Returns
+    /// * `Result<Option<T>, StorageError>` - The value if found, or
+    ///   `StorageError::DeserializationError` if the stored bytes cannot be
+    ///   decoded as `T`
+    pub async fn get<T: DeserializeOwned>(&self, key: String) -> Result<Option<T>, StorageError> {
+        self.inner
+            .load_raw(key)
+            .await?
+            .map(|bytes| self.codec.decode(&bytes))
+            .transpose()
+    }
+}